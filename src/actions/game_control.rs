@@ -5,6 +5,8 @@ pub enum GameControl {
     Down,
     Left,
     Right,
+    ZoomIn,
+    ZoomOut,
 }
 
 impl GameControl {
@@ -22,6 +24,12 @@ impl GameControl {
             GameControl::Right => {
                 keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight)
             }
+            GameControl::ZoomIn => {
+                keyboard_input.pressed(KeyCode::KeyE) || keyboard_input.pressed(KeyCode::Equal)
+            }
+            GameControl::ZoomOut => {
+                keyboard_input.pressed(KeyCode::KeyQ) || keyboard_input.pressed(KeyCode::Minus)
+            }
         }
     }
 }
@@ -1,68 +1,138 @@
-use bevy::math::Vec3Swizzles;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 use crate::actions::game_control::{get_movement, GameControl};
-use crate::player::Player;
 use crate::GameState;
 
 mod game_control;
 
-pub const FOLLOW_EPSILON: f32 = 5.;
+/// Distance in logical pixels from the window border where the cursor starts panning the map.
+pub const EDGE_PAN_MARGIN: f32 = 16.;
+/// How much one "line" of the scroll wheel changes the zoom.
+pub const ZOOM_STEP: f32 = 1.1;
+/// Keyboard zoom speed, in zoom steps per second.
+pub const KEYBOARD_ZOOM_SPEED: f32 = 8.;
 
 pub struct ActionsPlugin;
 
-// This plugin listens for keyboard input and converts the input into Actions.
+// This plugin listens for keyboard, mouse and touch input and converts the input into Actions.
 // Actions can then be used as a resource in other systems to act on the player input.
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Actions>().add_systems(
             Update,
-            set_movement_actions.run_if(in_state(GameState::Playing)),
+            (set_camera_actions, add_touch_camera_actions)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
         );
     }
 }
 
 #[derive(Default, Resource)]
 pub struct Actions {
-    pub player_movement: Option<Vec2>,
+    /// Normalized direction the camera should pan in, from the keyboard or the window edges.
+    pub camera_pan: Option<Vec2>,
+    /// Distance in logical pixels the map was dragged this frame, by mouse or a single finger.
+    pub camera_drag: Option<Vec2>,
+    /// Factor to multiply the current zoom with, above 1.0 zooms out.
+    pub camera_zoom: Option<f32>,
 }
 
-pub fn set_movement_actions(
+pub fn set_camera_actions(
     mut actions: ResMut<Actions>,
+    time: Res<Time>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    touch_input: Res<Touches>,
-    player: Query<&Transform, With<Player>>,
-    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
-) -> Result {
-    let mut player_movement = Vec2::new(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    window: Query<&Window, With<PrimaryWindow>>,
+) {
+    let mut camera_pan = Vec2::new(
         get_movement(GameControl::Right, &keyboard_input)
             - get_movement(GameControl::Left, &keyboard_input),
         get_movement(GameControl::Up, &keyboard_input)
             - get_movement(GameControl::Down, &keyboard_input),
     );
 
-    if let Some(touch_position) = touch_input.first_pressed_position() {
-        if let Ok((camera, camera_transform)) = camera.single() {
-            if let Ok(touch_position) =
-                camera.viewport_to_world_2d(camera_transform, touch_position)
-            {
-                let diff = touch_position
-                    - player
-                        .single()
-                        .map(|transform| transform.translation.xy())
-                        .unwrap_or(touch_position);
-                if diff.length() > FOLLOW_EPSILON {
-                    player_movement = diff.normalize();
-                }
+    if let Ok(window) = window.single() {
+        if window.focused {
+            if let Some(cursor) = window.cursor_position() {
+                camera_pan += edge_pan_direction(cursor, window.size());
             }
         }
     }
 
-    if player_movement != Vec2::ZERO {
-        actions.player_movement = Some(player_movement.normalize());
+    let mut camera_drag = Vec2::ZERO;
+    if mouse_input.pressed(MouseButton::Middle) || mouse_input.pressed(MouseButton::Right) {
+        camera_drag += mouse_motion.delta;
+    }
+
+    let mut camera_zoom = 1.0;
+    let scroll_steps = match mouse_scroll.unit {
+        MouseScrollUnit::Line => mouse_scroll.delta.y,
+        MouseScrollUnit::Pixel => mouse_scroll.delta.y / 100.,
+    };
+    if scroll_steps != 0. {
+        camera_zoom *= ZOOM_STEP.powf(-scroll_steps);
+    }
+    let keyboard_zoom = get_movement(GameControl::ZoomOut, &keyboard_input)
+        - get_movement(GameControl::ZoomIn, &keyboard_input);
+    if keyboard_zoom != 0. {
+        camera_zoom *= ZOOM_STEP.powf(keyboard_zoom * KEYBOARD_ZOOM_SPEED * time.delta_secs());
+    }
+
+    actions.camera_pan = if camera_pan != Vec2::ZERO {
+        Some(camera_pan.normalize())
+    } else {
+        None
+    };
+    actions.camera_drag = if camera_drag != Vec2::ZERO {
+        Some(camera_drag)
     } else {
-        actions.player_movement = None;
+        None
+    };
+    actions.camera_zoom = if camera_zoom != 1.0 {
+        Some(camera_zoom)
+    } else {
+        None
+    };
+}
+
+/// Adds single finger drags and two finger pinches on top of the mouse and keyboard actions.
+pub fn add_touch_camera_actions(mut actions: ResMut<Actions>, touch_input: Res<Touches>) {
+    let touches = touch_input.iter().collect::<Vec<_>>();
+    match touches.as_slice() {
+        [touch] => {
+            actions.camera_drag = Some(actions.camera_drag.unwrap_or_default() + touch.delta());
+        }
+        [first, second] => {
+            // Pinch: the ratio between the old and the new finger distance is the zoom factor
+            let previous_distance = first
+                .previous_position()
+                .distance(second.previous_position());
+            let distance = first.position().distance(second.position());
+            if previous_distance > 0. && distance > 0. {
+                actions.camera_zoom =
+                    Some(actions.camera_zoom.unwrap_or(1.0) * previous_distance / distance);
+            }
+        }
+        _ => {}
     }
+}
 
-    Ok(())
+fn edge_pan_direction(cursor: Vec2, window_size: Vec2) -> Vec2 {
+    let mut direction = Vec2::ZERO;
+    if cursor.x < EDGE_PAN_MARGIN {
+        direction.x -= 1.;
+    } else if cursor.x > window_size.x - EDGE_PAN_MARGIN {
+        direction.x += 1.;
+    }
+    // Window coordinates grow downwards, world coordinates grow upwards
+    if cursor.y < EDGE_PAN_MARGIN {
+        direction.y += 1.;
+    } else if cursor.y > window_size.y - EDGE_PAN_MARGIN {
+        direction.y -= 1.;
+    }
+    direction
 }
@@ -0,0 +1,141 @@
+//! Plays a seat on a game server from the terminal. The game itself plays one on the map with
+//! `--connect`.
+//!
+//! cargo run --release --bin client -- --address 127.0.0.1:7878 --name Ada
+
+use advanced_civilization::network::prelude::*;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::net::TcpStream;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: client [--address HOST:PORT] [--name NAME] [--auto]";
+
+fn read_line() -> Option<String> {
+    let mut line = String::new();
+    if std::io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+    Some(line.trim().to_string())
+}
+
+/// Asks for cards until they are typed in a way the game knows.
+fn ask_for_cards(question: &str) -> BTreeMap<String, usize> {
+    loop {
+        println!("{question} (like Ochre 2, Salt):");
+        let Some(line) = read_line() else {
+            return BTreeMap::new();
+        };
+        match parse_card_list(&line) {
+            Ok(cards) => return cards,
+            Err(err) => println!("{err}"),
+        }
+    }
+}
+
+/// Asks on the terminal until a listed move id is typed in, then for the tokens or cards the
+/// move is played with.
+fn ask_for_move(view: Option<&GameView>, moves: &[MoveView]) -> MoveChoice {
+    for game_move in moves {
+        println!(
+            "{:>4}: {}",
+            game_move.id,
+            describe_action(&game_move.action, view)
+        );
+    }
+    let game_move = loop {
+        println!("Your move:");
+        let Some(line) = read_line() else {
+            // Nobody is typing any more, let the server time out
+            return MoveChoice::new(usize::MAX);
+        };
+        match line.parse::<usize>() {
+            Ok(id) => match moves.iter().find(|game_move| game_move.id == id) {
+                Some(game_move) => break game_move,
+                None => println!("Pick one of the listed moves"),
+            },
+            Err(_) => println!("Pick one of the listed moves"),
+        }
+    };
+    let mut choice = MoveChoice::new(game_move.id);
+    match &game_move.action {
+        ActionView::Expand { max_tokens, .. }
+        | ActionView::Move { max_tokens, .. }
+        | ActionView::AttackArea { max_tokens, .. }
+        | ActionView::AttackCity { max_tokens, .. }
+            if *max_tokens > 1 =>
+        {
+            println!("Tokens, 1 to {max_tokens} (all of them if left empty):");
+            choice.tokens = read_line().and_then(|line| line.parse().ok());
+        }
+        ActionView::ProposeTrade { .. } => {
+            choice.gives = ask_for_cards("Cards to give");
+            choice.asks = ask_for_cards("Cards to ask for");
+        }
+        ActionView::SettleTrade { .. } => {
+            choice.gives = ask_for_cards("Cards to hand over");
+        }
+        _ => {}
+    }
+    choice
+}
+
+fn run() -> Result<(), String> {
+    let mut address = "127.0.0.1:7878".to_string();
+    let mut name = "player".to_string();
+    let mut auto = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--address" => address = args.next().ok_or("--address needs a value")?,
+            "--name" => name = args.next().ok_or("--name needs a value")?,
+            // Always ends the turn, handy for trying out a server
+            "--auto" => auto = true,
+            _ => return Err(format!("unknown argument {flag}\n{USAGE}")),
+        }
+    }
+    let stream = TcpStream::connect(&address)
+        .map_err(|err| format!("could not connect to {address}: {err}"))?;
+
+    let scores = run_client(
+        stream,
+        &name,
+        |message| match message {
+            ServerMessage::Welcome { seat, faction, .. } => {
+                println!("You play {faction} in seat {seat}");
+            }
+            ServerMessage::State {
+                round, phase, view, ..
+            } => {
+                println!("\nRound {round}, {phase}\n{}", render_view(view));
+            }
+            ServerMessage::Error { message, .. } => println!("The server says: {message}"),
+            _ => {}
+        },
+        |view, moves| {
+            if auto {
+                MoveChoice::new(moves.len().saturating_sub(1))
+            } else {
+                ask_for_move(view, moves)
+            }
+        },
+    )?;
+    println!("\nGame over");
+    for seat_score in scores {
+        println!(
+            "seat {} {:<12} {:>6.1}",
+            seat_score.seat, seat_score.name, seat_score.score
+        );
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Hosts a game for remote players, see the `network` module for the protocol.
+//!
+//! cargo run --release --bin server -- --port 7878 --remote 2 --ai heuristic,mcts
+//!
+//! Join from the terminal with the `client` binary, or on the map with
+//! `cargo run --release -- --connect 127.0.0.1:7878 --name Ada`.
+
+use advanced_civilization::network::prelude::*;
+use advanced_civilization::tournament::prelude::*;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: server [--port N] [--remote N] [--ai random,heuristic,mcts] \
+[--rules civilization|advanced|house|FILE.rules.ron] [--rounds N] [--timeout-ms N] \
+[--mcts-iterations N] [--map PATH]";
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{flag} needs a number"))
+}
+
+fn parse_args() -> Result<(ServerConfig, u16, PathBuf), String> {
+    let mut config = ServerConfig::default();
+    let mut port = 7878;
+    let mut map = PathBuf::from("assets/maps/civilization.map.ron");
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--port" => port = parse_number(&flag, args.next())?,
+            "--remote" => config.remote_seats = parse_number(&flag, args.next())?,
+            "--ai" => {
+                config.ai_seats = args
+                    .next()
+                    .ok_or("--ai needs a list")?
+                    .split(',')
+                    .filter(|controller| !controller.is_empty())
+                    .map(|controller| controller.parse())
+                    .collect::<Result<_, _>>()?;
+            }
+            "--rules" => config.rules = parse_rules(&args.next().ok_or("--rules needs a name")?)?,
+            "--rounds" => config.max_rounds = parse_number(&flag, args.next())?,
+            "--timeout-ms" => config.timeout_ms = parse_number(&flag, args.next())?,
+            "--mcts-iterations" => config.mcts_iterations = parse_number(&flag, args.next())?,
+            "--map" => map = args.next().ok_or("--map needs a path")?.into(),
+            _ => return Err(format!("unknown argument {flag}\n{USAGE}")),
+        }
+    }
+    Ok((config, port, map))
+}
+
+fn run() -> Result<(), String> {
+    let (config, port, map_path) = parse_args()?;
+    let map = load_map(&map_path)?;
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|err| format!("could not listen on port {port}: {err}"))?;
+    println!("Waiting for {} players on port {port}", config.remote_seats);
+    let scores = run_server(&listener, &map, &config)?;
+    for seat_score in scores {
+        println!(
+            "seat {} {:<12} {:>6.1}",
+            seat_score.seat, seat_score.name, seat_score.score
+        );
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Plays AI controllers against each other without graphics and reports who is best. Every
+//! game runs the rule plugins with the AI plugins playing the seats, one game per thread.
+//!
+//! cargo run --release --bin tournament -- --controllers heuristic,mcts,random --games 100
+
+use advanced_civilization::tournament::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: tournament [--controllers random,heuristic,mcts] [--games N] \
+[--threads N] [--rounds N] [--mcts-iterations N] \
+[--rules civilization|advanced|house|FILE.rules.ron] [--personalities aggressive,trader,...] \
+[--seed N] [--map PATH] [--out PREFIX]";
+const PERSONALITIES: &str = "assets/definitions/ai.personalities.ron";
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{flag} needs a number"))
+}
+
+fn parse_args() -> Result<(TournamentConfig, PathBuf, PathBuf), String> {
+    let mut config = TournamentConfig::default();
+    let mut map = PathBuf::from("assets/maps/civilization.map.ron");
+    let mut out = PathBuf::from("tournament");
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--controllers" => {
+                config.controllers = args
+                    .next()
+                    .ok_or("--controllers needs a list")?
+                    .split(',')
+                    .map(|controller| controller.parse())
+                    .collect::<Result<_, _>>()?;
+            }
+            "--games" => config.games = parse_number(&flag, args.next())?,
+            "--threads" => config.threads = parse_number(&flag, args.next())?,
+            "--rounds" => config.max_rounds = parse_number(&flag, args.next())?,
+            "--mcts-iterations" => config.mcts_iterations = parse_number(&flag, args.next())?,
+            "--rules" => config.rules = parse_rules(&args.next().ok_or("--rules needs a name")?)?,
+            "--personalities" => {
+                let names = args.next().ok_or("--personalities needs a list")?;
+                config.personalities = load_personalities(
+                    Path::new(PERSONALITIES),
+                    &names.split(',').collect::<Vec<_>>(),
+                )?;
+            }
+            "--seed" => config.seed = parse_number(&flag, args.next())?,
+            "--map" => map = args.next().ok_or("--map needs a path")?.into(),
+            "--out" => out = args.next().ok_or("--out needs a path")?.into(),
+            _ => return Err(format!("unknown argument {flag}\n{USAGE}")),
+        }
+    }
+    Ok((config, map, out))
+}
+
+fn run() -> Result<(), String> {
+    let (config, map_path, out) = parse_args()?;
+    let map = load_map(&map_path)?;
+    let report = run_tournament(&map, &config)?;
+
+    let csv_path = out.with_extension("csv");
+    let json_path = out.with_extension("json");
+    std::fs::write(&csv_path, report_csv(&report))
+        .map_err(|err| format!("could not write {}: {err}", csv_path.display()))?;
+    std::fs::write(&json_path, report_json(&report))
+        .map_err(|err| format!("could not write {}: {err}", json_path.display()))?;
+
+    println!(
+        "{:<10} {:>7} {:>6} {:>7} {:>7} {:>7} {:>7}",
+        "controller", "elo", "games", "wins", "score", "cities", "trades"
+    );
+    for stats in report.controllers.iter() {
+        println!(
+            "{:<10} {:>7.1} {:>6} {:>7.1} {:>7.1} {:>7.1} {:>7.1}",
+            stats.controller.to_string(),
+            stats.elo,
+            stats.games,
+            stats.wins,
+            stats.average_score,
+            stats.average_cities_built,
+            stats.average_trades_made
+        );
+    }
+    println!("Wrote {} and {}", csv_path.display(), json_path.display());
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
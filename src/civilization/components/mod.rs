@@ -33,6 +33,12 @@ impl LandPassage {
     }
 }
 
+/// The areas an area reaches by sea. Nothing moves by sea yet, these are only shown on the map.
+#[derive(Component, Debug, Reflect, Default)]
+pub struct SeaPassage {
+    pub to_areas: Vec<Entity>,
+}
+
 #[derive(Component, Debug, Reflect, Default)]
 pub struct NeedsConnections {
     pub land_connections: Vec<i32>,
@@ -114,7 +120,7 @@ impl Token {
     }
 }
 
-#[derive(Component, Debug, Reflect)]
+#[derive(Component, Debug, Reflect, Clone)]
 pub struct TokenStock {
     pub max_tokens: usize,
     tokens: HashSet<Entity>,
@@ -132,6 +138,10 @@ impl TokenStock {
         self.tokens.is_empty()
     }
 
+    pub fn tokens(&self) -> &HashSet<Entity> {
+        &self.tokens
+    }
+
     pub fn return_token_to_stock(&mut self, token: Entity) {
         self.tokens.insert(token);
     }
@@ -178,7 +188,7 @@ impl TokenStock {
     }
 }
 
-#[derive(Component, Debug, Reflect, Default)]
+#[derive(Component, Debug, Reflect, Default, Clone)]
 pub struct PlayerCities {
     pub areas_and_cities: HashMap<Entity, Entity>,
 }
@@ -209,7 +219,9 @@ impl PlayerCities {
     }
 }
 
-#[derive(Component, Debug, Reflect, Default)]
+/// The tokens a player has on the board by area, see
+/// [`Populations::player_areas`](population::Populations::player_areas).
+#[derive(Debug, Reflect, Default, Clone)]
 pub struct PlayerAreas {
     areas: HashSet<Entity>,
     area_population: HashMap<Entity, HashSet<Entity>>,
@@ -350,9 +362,13 @@ impl Treasury {
     pub fn tokens_in_treasury(&self) -> usize {
         self.tokens.len()
     }
+
+    pub fn tokens(&self) -> &[Entity] {
+        &self.tokens
+    }
 }
 
-#[derive(Component, Debug, Reflect)]
+#[derive(Component, Debug, Reflect, Clone)]
 pub struct CityTokenStock {
     pub max_tokens: usize,
     tokens: Vec<Entity>,
@@ -371,6 +387,10 @@ impl CityTokenStock {
         self.tokens.is_empty()
     }
 
+    pub fn tokens(&self) -> &[Entity] {
+        &self.tokens
+    }
+
     pub fn get_token_from_stock(&mut self) -> Option<Entity> {
         self.tokens.pop()
     }
@@ -378,7 +398,27 @@ impl CityTokenStock {
     pub fn return_token_to_stock(&mut self, token: Entity) {
         self.tokens.push(token);
     }
+
+    pub fn tokens_in_stock(&self) -> usize {
+        self.tokens.len()
+    }
 }
 
 #[derive(Debug, Component)]
-pub struct ReturnTokenToStock;
\ No newline at end of file
+pub struct ReturnTokenToStock;
+
+/// The area a token on the board stands in.
+#[derive(Component, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+#[relationship(relationship_target = AreaTokens)]
+pub struct InArea(pub Entity);
+
+/// All tokens standing in an area, kept up to date by Bevy from [`InArea`].
+#[derive(Component, Debug, Reflect, Default)]
+#[relationship_target(relationship = InArea)]
+pub struct AreaTokens(Vec<Entity>);
+
+impl AreaTokens {
+    pub fn tokens(&self) -> &[Entity] {
+        &self.0
+    }
+}
@@ -1,7 +1,15 @@
+use crate::civilization::components::{AreaTokens, PlayerAreas, Token};
+use bevy::ecs::system::SystemParam;
 use bevy::platform::collections::{HashMap, HashSet};
-use bevy::prelude::{Component, Entity, Reflect, default};
+use bevy::prelude::{Component, Entity, Query, Reflect, default};
 
-#[derive(Component, Debug, Reflect, Default)]
+/// How many tokens an area supports once surplus population is removed.
+#[derive(Component, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub struct MaxPopulation(pub usize);
+
+/// The tokens in an area by player. Worked out from [`AreaTokens`] by [`Populations`], or kept
+/// by the AIs for the boards they play out.
+#[derive(Debug, Reflect, Default, Clone)]
 pub struct Population {
     player_tokens: HashMap<Entity, HashSet<Entity>>,
     pub max_population: usize,
@@ -201,6 +209,58 @@ impl Population {
     }
 }
 
+/// The populations of the areas and the areas of the players, counted from the tokens standing
+/// in them. [`InArea`](crate::civilization::components::InArea) is the only place where the
+/// board keeps track of them.
+#[derive(SystemParam)]
+pub struct Populations<'w, 's> {
+    areas: Query<'w, 's, (Entity, &'static MaxPopulation, Option<&'static AreaTokens>)>,
+    tokens: Query<'w, 's, &'static Token>,
+}
+
+impl Populations<'_, '_> {
+    pub fn get(&self, area: Entity) -> Option<Population> {
+        self.areas
+            .get(area)
+            .ok()
+            .map(|(_, max_population, area_tokens)| self.count(max_population, area_tokens))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Population)> + '_ {
+        self.areas
+            .iter()
+            .map(|(area, max_population, area_tokens)| {
+                (area, self.count(max_population, area_tokens))
+            })
+    }
+
+    pub fn player_areas(&self, player: Entity) -> PlayerAreas {
+        let mut player_areas = PlayerAreas::default();
+        for (area, _, area_tokens) in self.areas.iter() {
+            for token in area_tokens.iter().flat_map(|tokens| tokens.tokens()) {
+                if self
+                    .tokens
+                    .get(*token)
+                    .is_ok_and(|token| token.player() == player)
+                {
+                    player_areas.add_token_to_area(area, *token);
+                }
+            }
+        }
+        player_areas
+    }
+
+    fn count(&self, max_population: &MaxPopulation, area_tokens: Option<&AreaTokens>) -> Population {
+        let mut population = Population::new(max_population.0);
+        for token in area_tokens.iter().flat_map(|tokens| tokens.tokens()) {
+            if let Ok(token_component) = self.tokens.get(*token) {
+                population.add_token_to_area(token_component.player(), *token);
+            }
+        }
+        population
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,10 +2,10 @@ use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::{Trade
 use bevy::platform::collections::{HashMap, HashSet};
 use bevy::prelude::{Color, Component, Reflect, Resource};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use std::usize;
 
-pub const MIN_CARDS_REQUIRED_TO_TRADE: usize = 5;
-
 #[derive(Resource, Debug, Default)]
 pub struct CivilizationTradeCards {
     pub card_piles: HashMap<usize, Vec<TradeCard>>,
@@ -24,6 +24,13 @@ impl CivilizationTradeCards {
             card_piles: cards,
         }
     }
+    /// Shuffles every pile, the piles are dealt in the order they are built otherwise.
+    pub fn shuffle(&mut self, rng: &mut StdRng) {
+        for (_, cards) in self.card_piles.iter_mut().sorted_by_key(|(pile, _)| **pile) {
+            cards.shuffle(rng);
+        }
+    }
+
     pub fn pull_card_from(&mut self, pile: usize) -> Option<TradeCard> {
         if let Some(p) = self.card_piles.get_mut(&pile) {
             p.pop()
@@ -39,8 +46,8 @@ pub struct PlayerTradeCards {
 }
 
 impl PlayerTradeCards {
-    pub fn can_trade(&self) -> bool {
-        self.number_of_tradeable_cards() >= MIN_CARDS_REQUIRED_TO_TRADE // we have to trade away three cards to get one card... I say we need five to trade. For now!
+    pub fn can_trade(&self, min_cards: usize) -> bool {
+        self.number_of_tradeable_cards() >= min_cards
     }
 
     pub fn wants(&self) -> Vec<TradeCard> {
@@ -55,8 +62,8 @@ impl PlayerTradeCards {
             .collect_vec()
     }
 
-    pub fn get_what_we_want(&self) -> Option<HashMap<TradeCard, usize>> {
-        if self.can_trade() {
+    pub fn get_what_we_want(&self, min_cards: usize) -> Option<HashMap<TradeCard, usize>> {
+        if self.can_trade(min_cards) {
             if let Some(top_commodity) = self.top_commodity() {
                 return Some(HashMap::from([(top_commodity, 2)]));
             }
@@ -64,8 +71,8 @@ impl PlayerTradeCards {
         None
     }
 
-    pub fn get_what_we_can_pay(&self) -> Option<HashMap<TradeCard, usize>> {
-        if self.can_trade() {
+    pub fn get_what_we_can_pay(&self, min_cards: usize) -> Option<HashMap<TradeCard, usize>> {
+        if self.can_trade(min_cards) {
             if let Some(bottom_commodity) = self.worst_commodity() {
                 return Some(HashMap::from([(bottom_commodity, 2)]));
             }
@@ -73,6 +80,10 @@ impl PlayerTradeCards {
         None
     }
 
+    pub fn cards(&self) -> &HashMap<TradeCard, usize> {
+        &self.cards
+    }
+
     pub fn add_trade_card(&mut self, trade_card: TradeCard) {
         *self.cards.entry(trade_card).or_insert(0) += 1;
     }
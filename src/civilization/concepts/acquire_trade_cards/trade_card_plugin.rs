@@ -2,10 +2,10 @@ use crate::civilization::concepts::acquire_trade_cards::trade_card_events::{
     CheckIfWeCanTrade, HumanPlayerTradeCardsUpdated,
 };
 use crate::civilization::concepts::acquire_trade_cards::trade_card_systems::{
-    acquire_trade_cards, transition_to_trade,
+    acquire_trade_cards, shuffle_trade_cards, transition_to_trade,
 };
 use crate::GameActivity;
-use bevy::prelude::{in_state, App, IntoScheduleConfigs, OnEnter, Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs, OnEnter, Plugin, Startup, Update};
 use crate::civilization::concepts::acquire_trade_cards::trade_card_components::CivilizationTradeCards;
 
 pub struct TradeCardPlugin;
@@ -16,6 +16,7 @@ impl Plugin for TradeCardPlugin {
             .insert_resource(CivilizationTradeCards::new())
         .add_message::<CheckIfWeCanTrade>()
         .add_message::<HumanPlayerTradeCardsUpdated>()
+        .add_systems(Startup, shuffle_trade_cards)
         .add_systems(
             OnEnter(GameActivity::AcquireTradeCards),
             acquire_trade_cards,
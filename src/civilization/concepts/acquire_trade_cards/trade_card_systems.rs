@@ -6,11 +6,19 @@ use crate::civilization::concepts::acquire_trade_cards::trade_card_components::{
 use crate::civilization::concepts::acquire_trade_cards::trade_card_events::{
     CheckIfWeCanTrade, HumanPlayerTradeCardsUpdated,
 };
+use crate::civilization::concepts::rules::rules_resources::{GameRng, RuleSet};
 use crate::civilization::plugins::civilization_plugin::DebugOptions;
 use crate::stupid_ai::prelude::IsHuman;
 use crate::GameActivity;
 use bevy::prelude::{debug, Entity, MessageReader, MessageWriter, Has, NextState, Query, Res, ResMut};
 
+pub fn shuffle_trade_cards(
+    mut trade_card_resource: ResMut<CivilizationTradeCards>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    trade_card_resource.shuffle(&mut game_rng.0);
+}
+
 pub fn acquire_trade_cards(
     mut player_query: Query<(
         Entity,
@@ -23,6 +31,7 @@ pub fn acquire_trade_cards(
     mut check_if_we_can_trade: MessageWriter<CheckIfWeCanTrade>,
     mut pulled_card_event_writer: MessageWriter<HumanPlayerTradeCardsUpdated>,
     debug_options: Res<DebugOptions>,
+    rules: Res<RuleSet>,
 ) {
     for (player_entity, faction, player_cities, mut player_trade_cards, is_human) in player_query
         .iter_mut()
@@ -49,7 +58,7 @@ pub fn acquire_trade_cards(
                 }
             });
         } else {
-            (1..=player_cities.number_of_cities()).for_each(|pile| {
+            rules.piles_to_draw(player_cities.number_of_cities()).for_each(|pile| {
                 if let Some(pulled_card) = trade_card_resource.pull_card_from(pile) {
                     pulled_cards = true;
                     player_trade_cards.add_trade_card(pulled_card);
@@ -76,12 +85,13 @@ pub fn acquire_trade_cards(
 pub fn transition_to_trade(
     mut check_if_we_can_trade: MessageReader<CheckIfWeCanTrade>,
     players_can_trade_query: Query<(&PlayerTradeCards, Has<IsHuman>)>,
+    rules: Res<RuleSet>,
     mut next_state: ResMut<NextState<GameActivity>>,
 ) {
     for _ in check_if_we_can_trade.read() {
         if players_can_trade_query
             .iter()
-            .filter(|(trade, _)| trade.can_trade())
+            .filter(|(trade, _)| trade.can_trade(rules.min_cards_to_trade))
             .count()
             >= 2
         // && players_can_trade_query.iter().filter(|(_, is_human)| *is_human).count() > 0 {
@@ -0,0 +1,40 @@
+use bevy::prelude::{Component, Reflect};
+
+/// How an AI player likes to play. Every weight is a multiplier around 1.0, so a balanced
+/// personality plays exactly like the AI does without one.
+#[derive(Component, Debug, Clone, PartialEq, Reflect, serde::Deserialize, serde::Serialize)]
+pub struct AiPersonality {
+    pub name: String,
+    /// How much winning attacks is worth
+    pub attack: f32,
+    /// How much building and keeping cities is worth
+    pub city_building: f32,
+    /// How readily the player trades, higher takes smaller gains
+    pub trade_frequency: f32,
+    /// How lightly the player takes calamities and unsupported cities
+    pub risk_tolerance: f32,
+}
+
+impl AiPersonality {
+    pub fn new(
+        name: &str,
+        attack: f32,
+        city_building: f32,
+        trade_frequency: f32,
+        risk_tolerance: f32,
+    ) -> Self {
+        AiPersonality {
+            name: name.to_string(),
+            attack,
+            city_building,
+            trade_frequency,
+            risk_tolerance,
+        }
+    }
+}
+
+impl Default for AiPersonality {
+    fn default() -> Self {
+        AiPersonality::new("balanced", 1.0, 1.0, 1.0, 1.0)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::ai_personality::ai_personality_resources::{
+    AiPersonalities, AiPersonalitySetup,
+};
+use crate::civilization::concepts::ai_personality::ai_personality_systems::load_ai_personalities;
+use bevy::app::{App, Plugin, Startup};
+use bevy_common_assets::ron::RonAssetPlugin;
+
+pub struct AiPersonalityPlugin;
+
+impl Plugin for AiPersonalityPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AiPersonality>()
+            .init_resource::<AiPersonalitySetup>()
+            .add_plugins(RonAssetPlugin::<AiPersonalities>::new(&[
+                "personalities.ron",
+            ]))
+            .add_systems(Startup, load_ai_personalities);
+    }
+}
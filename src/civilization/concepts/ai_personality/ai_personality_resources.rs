@@ -0,0 +1,93 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use bevy::prelude::{Handle, Resource};
+
+#[derive(
+    serde::Deserialize, serde::Serialize, bevy::asset::Asset, bevy::reflect::TypePath, Clone,
+)]
+pub struct AiPersonalities {
+    pub personalities: Vec<AiPersonality>,
+}
+
+impl AiPersonalities {
+    pub fn get(&self, name: &str) -> Option<&AiPersonality> {
+        self.personalities
+            .iter()
+            .find(|personality| personality.name == name)
+    }
+}
+
+#[derive(Resource)]
+pub struct AiPersonalitiesHandle(pub Handle<AiPersonalities>);
+
+/// The personalities handed out to the players when the game is set up. Player n gets entry
+/// n - 1, wrapping around when there are more players than entries.
+#[derive(Resource, Debug, Clone)]
+pub struct AiPersonalitySetup {
+    pub personalities: Vec<String>,
+}
+
+impl AiPersonalitySetup {
+    pub fn personality_for_player(&self, n: usize) -> Option<&String> {
+        if self.personalities.is_empty() {
+            None
+        } else {
+            self.personalities
+                .get((n.saturating_sub(1)) % self.personalities.len())
+        }
+    }
+}
+
+impl Default for AiPersonalitySetup {
+    fn default() -> Self {
+        AiPersonalitySetup {
+            personalities: vec![
+                "aggressive".to_string(),
+                "expansionist".to_string(),
+                "trader".to_string(),
+                "builder".to_string(),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(names: &[&str]) -> AiPersonalitySetup {
+        AiPersonalitySetup {
+            personalities: names.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_players_get_the_personalities_in_order() {
+        let setup = setup(&["aggressive", "trader"]);
+
+        assert_eq!(setup.personality_for_player(1).unwrap(), "aggressive");
+        assert_eq!(setup.personality_for_player(2).unwrap(), "trader");
+    }
+
+    #[test]
+    fn test_personalities_wrap_around_for_more_players() {
+        let setup = setup(&["aggressive", "trader", "builder"]);
+
+        assert_eq!(setup.personality_for_player(4).unwrap(), "aggressive");
+        assert_eq!(setup.personality_for_player(6).unwrap(), "builder");
+        assert_eq!(setup.personality_for_player(7).unwrap(), "aggressive");
+    }
+
+    #[test]
+    fn test_player_zero_gets_the_first_personality() {
+        let setup = setup(&["aggressive", "trader"]);
+
+        assert_eq!(setup.personality_for_player(0).unwrap(), "aggressive");
+    }
+
+    #[test]
+    fn test_no_personalities_means_none_for_everyone() {
+        let setup = setup(&[]);
+
+        assert_eq!(setup.personality_for_player(1), None);
+    }
+}
@@ -0,0 +1,8 @@
+use crate::civilization::concepts::ai_personality::ai_personality_resources::AiPersonalitiesHandle;
+use bevy::prelude::{AssetServer, Commands, Res};
+
+pub fn load_ai_personalities(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AiPersonalitiesHandle(
+        asset_server.load("definitions/ai.personalities.ron"),
+    ));
+}
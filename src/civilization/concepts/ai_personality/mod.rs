@@ -0,0 +1,4 @@
+pub mod ai_personality_components;
+pub mod ai_personality_plugin;
+pub mod ai_personality_resources;
+pub mod ai_personality_systems;
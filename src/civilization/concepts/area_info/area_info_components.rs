@@ -0,0 +1,10 @@
+use bevy::prelude::Component;
+
+#[derive(Component, Default)]
+pub struct AreaTooltip;
+
+#[derive(Component, Default)]
+pub struct AreaTooltipText;
+
+#[derive(Component, Default)]
+pub struct AreaOverlayLabel;
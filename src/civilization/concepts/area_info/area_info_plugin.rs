@@ -0,0 +1,29 @@
+use crate::civilization::concepts::area_info::area_info_resources::{HoveredArea, MapOverlay};
+use crate::civilization::concepts::area_info::area_info_systems::*;
+use crate::GameState;
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
+
+pub struct AreaInfoPlugin;
+
+impl Plugin for AreaInfoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HoveredArea>()
+            .register_type::<MapOverlay>()
+            .init_resource::<HoveredArea>()
+            .init_resource::<MapOverlay>()
+            .add_systems(OnEnter(GameState::Playing), setup_area_tooltip)
+            .add_systems(
+                Update,
+                (
+                    find_hovered_area,
+                    update_area_tooltip,
+                    toggle_map_overlay,
+                    update_overlay_labels,
+                    draw_map_overlay.run_if(map_overlay_enabled),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
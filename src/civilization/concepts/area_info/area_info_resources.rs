@@ -0,0 +1,11 @@
+use bevy::prelude::{Entity, Reflect, Resource};
+
+#[derive(Resource, Debug, Reflect, Default)]
+pub struct HoveredArea {
+    pub area: Option<Entity>,
+}
+
+#[derive(Resource, Debug, Reflect, Default)]
+pub struct MapOverlay {
+    pub enabled: bool,
+}
@@ -0,0 +1,236 @@
+use crate::civilization::components::population::{MaxPopulation, Populations};
+use crate::civilization::components::{
+    BuiltCity, CitySite, FloodPlain, GameArea, GameCamera, LandPassage, SeaPassage, StartArea,
+    Volcano,
+};
+use crate::civilization::concepts::area_info::area_info_components::{
+    AreaOverlayLabel, AreaTooltip, AreaTooltipText,
+};
+use crate::civilization::concepts::area_info::area_info_resources::{HoveredArea, MapOverlay};
+use crate::civilization::ui::ui_builder::{BORDER_COLOR, TEXT_COLOR};
+use bevy::color::palettes::basic::{AQUA, BLUE, WHITE, YELLOW};
+use bevy::prelude::{
+    default, BackgroundColor, ButtonInput, Camera, Color, Commands, DetectChanges, Entity, Gizmos,
+    GlobalTransform, GlobalZIndex, Has, KeyCode, Name, Node, PositionType, Query, Res, ResMut,
+    Text, Text2d, TextColor, TextFont, Transform, UiRect, Val, Vec3, Visibility, Window, With,
+};
+use bevy::window::PrimaryWindow;
+
+/// How close, in world units, the cursor has to be to an area marker to hover it.
+pub const AREA_HOVER_RADIUS: f32 = 30.0;
+const TOOLTIP_OFFSET: f32 = 16.0;
+
+pub fn setup_area_tooltip(mut commands: Commands) {
+    commands
+        .spawn((
+            AreaTooltip,
+            Node {
+                position_type: PositionType::Absolute,
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(BORDER_COLOR),
+            GlobalZIndex(10),
+            Visibility::Hidden,
+        ))
+        .with_child((
+            AreaTooltipText,
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+}
+
+pub fn find_hovered_area(
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    area_query: Query<(Entity, &Transform), With<GameArea>>,
+    mut hovered_area: ResMut<HoveredArea>,
+) {
+    let cursor_in_world = window
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor| {
+            camera.single().ok().and_then(|(camera, camera_transform)| {
+                camera.viewport_to_world_2d(camera_transform, cursor).ok()
+            })
+        });
+
+    let area = cursor_in_world.and_then(|position| {
+        area_query
+            .iter()
+            .map(|(entity, transform)| {
+                (entity, transform.translation.truncate().distance(position))
+            })
+            .filter(|(_, distance)| *distance <= AREA_HOVER_RADIUS)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity)
+    });
+
+    if hovered_area.area != area {
+        hovered_area.area = area;
+    }
+}
+
+pub fn update_area_tooltip(
+    hovered_area: Res<HoveredArea>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    area_query: Query<(
+        &Name,
+        Option<&BuiltCity>,
+        Option<&StartArea>,
+        Has<CitySite>,
+        Has<FloodPlain>,
+        Has<Volcano>,
+    )>,
+    populations: Populations,
+    name_query: Query<&Name>,
+    mut tooltip_query: Query<(&mut Node, &mut Visibility), With<AreaTooltip>>,
+    mut tooltip_text_query: Query<&mut Text, With<AreaTooltipText>>,
+) {
+    let Ok((mut node, mut visibility)) = tooltip_query.single_mut() else {
+        return;
+    };
+    let cursor = window
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    if let (Some(area), Some(cursor)) = (hovered_area.area, cursor) {
+        if let (
+            Ok((name, built_city, start_area, city_site, flood_plain, volcano)),
+            Some(population),
+        ) = (area_query.get(area), populations.get(area))
+        {
+            let mut lines = vec![
+                name.to_string(),
+                format!(
+                    "Population: {}/{}",
+                    population.total_population(),
+                    population.max_population
+                ),
+            ];
+            for (player, tokens) in population.player_tokens().iter() {
+                let player_name = name_query
+                    .get(*player)
+                    .map(|n| n.to_string())
+                    .unwrap_or_default();
+                lines.push(format!("  {}: {}", player_name, tokens.len()));
+            }
+            if let Some(built_city) = built_city {
+                let owner = name_query
+                    .get(built_city.player)
+                    .map(|n| n.to_string())
+                    .unwrap_or_default();
+                lines.push(format!("City of {}", owner));
+            }
+            if city_site {
+                lines.push("City site".to_string());
+            }
+            if flood_plain {
+                lines.push("Flood plain".to_string());
+            }
+            if volcano {
+                lines.push("Volcano".to_string());
+            }
+            if let Some(start_area) = start_area {
+                lines.push(format!("Start area of {}", start_area.faction));
+            }
+
+            if let Ok(mut text) = tooltip_text_query.single_mut() {
+                text.0 = lines.join("\n");
+            }
+            node.left = Val::Px(cursor.x + TOOLTIP_OFFSET);
+            node.top = Val::Px(cursor.y + TOOLTIP_OFFSET);
+            *visibility = Visibility::Visible;
+            return;
+        }
+    }
+    *visibility = Visibility::Hidden;
+}
+
+pub fn toggle_map_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<MapOverlay>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyO) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+pub fn map_overlay_enabled(overlay: Res<MapOverlay>) -> bool {
+    overlay.enabled
+}
+
+/// Spawns a max population label above every area when the overlay is turned on, and removes
+/// them again when it is turned off.
+pub fn update_overlay_labels(
+    overlay: Res<MapOverlay>,
+    area_query: Query<(&MaxPopulation, &Transform), With<GameArea>>,
+    label_query: Query<Entity, With<AreaOverlayLabel>>,
+    mut commands: Commands,
+) {
+    if !overlay.is_changed() {
+        return;
+    }
+    for label in label_query.iter() {
+        commands.entity(label).despawn();
+    }
+    if overlay.enabled {
+        for (max_population, transform) in area_query.iter() {
+            commands.spawn((
+                AreaOverlayLabel,
+                Text2d::new(max_population.0.to_string()),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::from(YELLOW)),
+                Transform::from_translation(
+                    transform.translation.truncate().extend(5.0) + Vec3::Y * 18.0,
+                ),
+            ));
+        }
+    }
+}
+
+/// Land passages are drawn in white, sea passages in blue.
+pub fn draw_map_overlay(
+    mut gizmos: Gizmos,
+    hovered_area: Res<HoveredArea>,
+    area_query: Query<
+        (Entity, &Transform, &LandPassage, &SeaPassage, Has<CitySite>),
+        With<GameArea>,
+    >,
+    target_query: Query<&Transform, With<GameArea>>,
+) {
+    for (area, transform, land_passage, sea_passage, city_site) in area_query.iter() {
+        let position = transform.translation.truncate();
+        let passages = land_passage
+            .to_areas
+            .iter()
+            .map(|target| (target, WHITE))
+            .chain(sea_passage.to_areas.iter().map(|target| (target, BLUE)));
+        for (target, color) in passages {
+            // Every passage is listed from both ends, only draw it once
+            if *target < area {
+                continue;
+            }
+            if let Ok(target_transform) = target_query.get(*target) {
+                gizmos.line_2d(position, target_transform.translation.truncate(), color);
+            }
+        }
+        let color = if hovered_area.area == Some(area) {
+            AQUA
+        } else if city_site {
+            YELLOW
+        } else {
+            WHITE
+        };
+        gizmos.circle_2d(position, 8.0, color);
+    }
+}
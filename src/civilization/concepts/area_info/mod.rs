@@ -0,0 +1,4 @@
+pub mod area_info_components;
+pub mod area_info_plugin;
+pub mod area_info_resources;
+pub mod area_info_systems;
@@ -0,0 +1,331 @@
+use crate::civilization::components::{
+    BuiltCity, CityTokenStock, PlayerCities, TokenStock, Treasury,
+};
+use crate::civilization::concepts::scenario::scenario_functions::{
+    CITIES_PER_PLAYER, TOKENS_PER_PLAYER,
+};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::Entity;
+
+/// Everything a player keeps track of itself.
+pub struct PlayerHoldings<'a> {
+    pub player: Entity,
+    pub stock: &'a TokenStock,
+    pub treasury: &'a Treasury,
+    pub cities: &'a PlayerCities,
+    pub city_stock: &'a CityTokenStock,
+}
+
+pub struct AreaHoldings<'a> {
+    pub area: Entity,
+    /// The tokens whose InArea points here.
+    pub standing: &'a [Entity],
+    pub built_city: Option<&'a BuiltCity>,
+}
+
+/// Checks that the areas, the players and the tokens agree on where every token and city is.
+/// Tokens and city tokens map to the player they belong to. Problems come back sorted.
+pub fn board_problems(
+    players: &[PlayerHoldings],
+    areas: &[AreaHoldings],
+    tokens: &HashMap<Entity, Entity>,
+    city_tokens: &HashMap<Entity, Entity>,
+    name_of: &dyn Fn(Entity) -> String,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    token_places(players, areas, tokens, name_of, &mut problems);
+    cities_agree(players, areas, city_tokens, name_of, &mut problems);
+    for holdings in players.iter() {
+        let owned = tokens
+            .values()
+            .filter(|owner| **owner == holdings.player)
+            .count();
+        if owned != TOKENS_PER_PLAYER {
+            problems.push(format!(
+                "{} owns {} tokens instead of {}",
+                name_of(holdings.player),
+                owned,
+                TOKENS_PER_PLAYER
+            ));
+        }
+        let owned = city_tokens
+            .values()
+            .filter(|owner| **owner == holdings.player)
+            .count();
+        if owned != CITIES_PER_PLAYER {
+            problems.push(format!(
+                "{} owns {} city tokens instead of {}",
+                name_of(holdings.player),
+                owned,
+                CITIES_PER_PLAYER
+            ));
+        }
+    }
+    problems.sort();
+    problems
+}
+
+/// Every token has to be in exactly one area, stock or treasury, and belong to whoever has it.
+fn token_places(
+    players: &[PlayerHoldings],
+    areas: &[AreaHoldings],
+    tokens: &HashMap<Entity, Entity>,
+    name_of: &dyn Fn(Entity) -> String,
+    problems: &mut Vec<String>,
+) {
+    let mut places: HashMap<Entity, Vec<String>> = HashMap::default();
+    for area in areas.iter() {
+        let place = name_of(area.area);
+        for token in area.standing.iter() {
+            if !tokens.contains_key(token) {
+                problems.push(format!("{} in {} is not a token", name_of(*token), place));
+            }
+            places.entry(*token).or_default().push(place.clone());
+        }
+    }
+    let mut holders = Vec::new();
+    for holdings in players.iter() {
+        let name = name_of(holdings.player);
+        holders.push((
+            holdings.player,
+            format!("the stock of {}", name),
+            holdings.stock.tokens().iter().copied().collect(),
+        ));
+        holders.push((
+            holdings.player,
+            format!("the treasury of {}", name),
+            holdings.treasury.tokens().to_vec(),
+        ));
+    }
+    for (player, place, held) in holders {
+        for token in held {
+            match tokens.get(&token) {
+                None => problems.push(format!("{} in {} is not a token", name_of(token), place)),
+                Some(owner) if *owner != player => problems.push(format!(
+                    "{} in {} is held for {} but belongs to {}",
+                    name_of(token),
+                    place,
+                    name_of(player),
+                    name_of(*owner)
+                )),
+                Some(_) => {}
+            }
+            places.entry(token).or_default().push(place.clone());
+        }
+    }
+    for token in tokens.keys() {
+        match places.get(token) {
+            None => problems.push(format!("{} is nowhere", name_of(*token))),
+            Some(token_places) if token_places.len() > 1 => problems.push(format!(
+                "{} is in {}",
+                name_of(*token),
+                token_places.join(" and ")
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Built cities have to be on the map, in their owner's city list and out of the stock.
+fn cities_agree(
+    players: &[PlayerHoldings],
+    areas: &[AreaHoldings],
+    city_tokens: &HashMap<Entity, Entity>,
+    name_of: &dyn Fn(Entity) -> String,
+    problems: &mut Vec<String>,
+) {
+    let built = areas
+        .iter()
+        .filter_map(|area| area.built_city.map(|built_city| (area.area, built_city)))
+        .collect::<HashMap<_, _>>();
+    for (area, built_city) in built.iter() {
+        let listed = players
+            .iter()
+            .find(|holdings| holdings.player == built_city.player)
+            .and_then(|holdings| holdings.cities.areas_and_cities.get(area));
+        if listed != Some(&built_city.city) {
+            problems.push(format!(
+                "The city in {} is not in the city list of {}",
+                name_of(*area),
+                name_of(built_city.player)
+            ));
+        }
+        if city_tokens.get(&built_city.city) != Some(&built_city.player) {
+            problems.push(format!(
+                "The city in {} is not a city token of {}",
+                name_of(*area),
+                name_of(built_city.player)
+            ));
+        }
+    }
+    for holdings in players.iter() {
+        for (area, city) in holdings.cities.areas_and_cities.iter() {
+            if built
+                .get(area)
+                .is_none_or(|built_city| built_city.city != *city)
+            {
+                problems.push(format!(
+                    "{} lists a city in {} that is not there",
+                    name_of(holdings.player),
+                    name_of(*area)
+                ));
+            }
+            if holdings.city_stock.tokens().contains(city) {
+                problems.push(format!(
+                    "The city of {} in {} is also in stock",
+                    name_of(holdings.player),
+                    name_of(*area)
+                ));
+            }
+        }
+        let accounted = holdings.city_stock.tokens_in_stock() + holdings.cities.number_of_cities();
+        if accounted != CITIES_PER_PLAYER {
+            problems.push(format!(
+                "{} has {} city tokens in stock and on the map instead of {}",
+                name_of(holdings.player),
+                accounted,
+                CITIES_PER_PLAYER
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1; // Increment the counter for the next entity
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    struct Board {
+        player: Entity,
+        tokens: HashMap<Entity, Entity>,
+        city_tokens: HashMap<Entity, Entity>,
+        stock: TokenStock,
+        treasury: Treasury,
+        player_cities: PlayerCities,
+        city_stock: CityTokenStock,
+        area: Entity,
+        standing: Vec<Entity>,
+        city_area: Entity,
+        built_city: BuiltCity,
+    }
+
+    impl Board {
+        /// A player with three tokens in one area, one in the treasury and a city in another.
+        fn new() -> Self {
+            let player = create_entity();
+            let token_list = (0..TOKENS_PER_PLAYER)
+                .map(|_| create_entity())
+                .collect::<Vec<_>>();
+            let mut city_list = (0..CITIES_PER_PLAYER)
+                .map(|_| create_entity())
+                .collect::<Vec<_>>();
+            let area = create_entity();
+            let city_area = create_entity();
+            let mut treasury = Treasury::default();
+            treasury.add_token_to_treasury(token_list[3]);
+            let city = city_list.pop().unwrap();
+            let mut player_cities = PlayerCities::default();
+            player_cities.build_city_in_area(city_area, city);
+            Board {
+                player,
+                tokens: token_list.iter().map(|token| (*token, player)).collect(),
+                city_tokens: city_list
+                    .iter()
+                    .chain([&city])
+                    .map(|city| (*city, player))
+                    .collect(),
+                stock: TokenStock::new(TOKENS_PER_PLAYER, token_list[4..].to_vec()),
+                treasury,
+                player_cities,
+                city_stock: CityTokenStock::new(CITIES_PER_PLAYER, city_list),
+                area,
+                standing: token_list[..3].to_vec(),
+                city_area,
+                built_city: BuiltCity::new(city, player),
+            }
+        }
+
+        fn problems(&self) -> Vec<String> {
+            let players = [PlayerHoldings {
+                player: self.player,
+                stock: &self.stock,
+                treasury: &self.treasury,
+                cities: &self.player_cities,
+                city_stock: &self.city_stock,
+            }];
+            let areas = [
+                AreaHoldings {
+                    area: self.area,
+                    standing: &self.standing,
+                    built_city: None,
+                },
+                AreaHoldings {
+                    area: self.city_area,
+                    standing: &[],
+                    built_city: Some(&self.built_city),
+                },
+            ];
+            board_problems(
+                &players,
+                &areas,
+                &self.tokens,
+                &self.city_tokens,
+                &|entity| format!("{}", entity.index()),
+            )
+        }
+    }
+
+    #[test]
+    fn test_a_consistent_board_has_no_problems() {
+        let board = Board::new();
+
+        assert!(board.problems().is_empty());
+    }
+
+    #[test]
+    fn test_token_in_area_and_stock_is_found() {
+        let mut board = Board::new();
+        let token = board.standing[0];
+        board.stock.return_token_to_stock(token);
+
+        let problems = board.problems();
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("the stock of"));
+    }
+
+    #[test]
+    fn test_token_that_left_the_area_is_found() {
+        let mut board = Board::new();
+        let token = board.standing.pop().unwrap();
+
+        let problems = board.problems();
+
+        assert_eq!(problems, vec![format!("{} is nowhere", token.index())]);
+    }
+
+    #[test]
+    fn test_city_missing_from_the_city_list_is_found() {
+        let mut board = Board::new();
+        board.player_cities.remove_city_from_area(board.city_area);
+
+        let problems = board.problems();
+
+        assert_eq!(problems.len(), 2);
+        assert!(problems
+            .iter()
+            .any(|problem| problem.starts_with("The city in")));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::civilization::concepts::board_audit::board_audit_systems::audit_board_after_each_phase;
+use crate::GameState;
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+
+/// Checks the board for tokens and cities that went astray whenever a phase ends. Only added
+/// to debug builds.
+pub struct BoardAuditPlugin;
+
+impl Plugin for BoardAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            audit_board_after_each_phase.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::civilization::components::population::MaxPopulation;
+use crate::civilization::components::{
+    AreaTokens, BuiltCity, CityToken, CityTokenStock, PlayerCities, Token, TokenStock, Treasury,
+};
+use crate::civilization::concepts::board_audit::board_audit_functions::{
+    board_problems, AreaHoldings, PlayerHoldings,
+};
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{warn, Entity, MessageReader, Name, Query, StateTransitionEvent, With};
+
+/// Where the board keeps its tokens and cities.
+#[derive(SystemParam)]
+pub struct BoardState<'w, 's> {
+    players: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static TokenStock,
+            &'static Treasury,
+            &'static PlayerCities,
+            &'static CityTokenStock,
+        ),
+    >,
+    areas: Query<
+        'w,
+        's,
+        (
+            Entity,
+            Option<&'static AreaTokens>,
+            Option<&'static BuiltCity>,
+        ),
+        With<MaxPopulation>,
+    >,
+    tokens: Query<'w, 's, (Entity, &'static Token)>,
+    city_tokens: Query<'w, 's, (Entity, &'static CityToken)>,
+    names: Query<'w, 's, &'static Name>,
+}
+
+impl BoardState<'_, '_> {
+    pub fn problems(&self) -> Vec<String> {
+        let players = self
+            .players
+            .iter()
+            .map(
+                |(player, stock, treasury, cities, city_stock)| PlayerHoldings {
+                    player,
+                    stock,
+                    treasury,
+                    cities,
+                    city_stock,
+                },
+            )
+            .collect::<Vec<_>>();
+        let areas = self
+            .areas
+            .iter()
+            .map(|(area, standing, built_city)| AreaHoldings {
+                area,
+                standing: standing.map_or(&[], |standing| standing.tokens()),
+                built_city,
+            })
+            .collect::<Vec<_>>();
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|(entity, token)| (entity, token.player()))
+            .collect();
+        let city_tokens = self
+            .city_tokens
+            .iter()
+            .map(|(entity, city_token)| (entity, city_token.player))
+            .collect();
+        let name_of = |entity: Entity| match self.names.get(entity) {
+            Ok(name) => format!("{} ({})", name, entity),
+            Err(_) => format!("{}", entity),
+        };
+        board_problems(&players, &areas, &tokens, &city_tokens, &name_of)
+    }
+}
+
+pub fn audit_board_after_each_phase(
+    mut transitions: MessageReader<StateTransitionEvent<GameActivity>>,
+    board: BoardState,
+) {
+    for transition in transitions.read() {
+        if let Some(phase) = &transition.exited {
+            for problem in board.problems() {
+                warn!("Board audit after {:?}: {}", phase, problem);
+            }
+        }
+    }
+}
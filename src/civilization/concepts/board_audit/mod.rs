@@ -0,0 +1,3 @@
+pub mod board_audit_functions;
+pub mod board_audit_plugin;
+pub mod board_audit_systems;
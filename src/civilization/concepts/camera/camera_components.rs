@@ -0,0 +1,14 @@
+use bevy::prelude::{Component, Reflect, Vec2};
+
+/// Put on the [`GameCamera`](crate::civilization::components::GameCamera) to glide it
+/// towards a point on the map. Removed when the camera arrives or the player pans manually.
+#[derive(Component, Debug, Reflect)]
+pub struct CameraFocus {
+    pub target: Vec2,
+}
+
+impl CameraFocus {
+    pub fn new(target: Vec2) -> Self {
+        CameraFocus { target }
+    }
+}
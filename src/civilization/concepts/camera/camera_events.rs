@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct FocusCameraOnArea {
+    pub area: Entity,
+}
+
+impl FocusCameraOnArea {
+    pub fn new(area: Entity) -> Self {
+        FocusCameraOnArea { area }
+    }
+}
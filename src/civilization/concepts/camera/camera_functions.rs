@@ -0,0 +1,51 @@
+use crate::civilization::concepts::map::map_plugin::{MAP_HEIGHT, MAP_WIDTH};
+use bevy::math::Vec2;
+
+/// Keeps the visible part of the world inside the map. When the view is larger than the map
+/// along an axis, the camera is centered on the map along that axis instead.
+pub fn clamp_camera_position(position: Vec2, half_view: Vec2) -> Vec2 {
+    Vec2::new(
+        clamp_axis(position.x, half_view.x, MAP_WIDTH),
+        clamp_axis(position.y, half_view.y, MAP_HEIGHT),
+    )
+}
+
+fn clamp_axis(position: f32, half_view: f32, map_size: f32) -> f32 {
+    if half_view * 2.0 >= map_size {
+        map_size / 2.0
+    } else {
+        position.clamp(half_view, map_size - half_view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_inside_map_is_untouched() {
+        let position = Vec2::new(1000.0, 600.0);
+        assert_eq!(
+            clamp_camera_position(position, Vec2::new(200.0, 100.0)),
+            position
+        );
+    }
+
+    #[test]
+    fn test_position_is_clamped_to_map_edges() {
+        let half_view = Vec2::new(200.0, 100.0);
+        assert_eq!(
+            clamp_camera_position(Vec2::new(-500.0, 5000.0), half_view),
+            Vec2::new(200.0, MAP_HEIGHT - 100.0)
+        );
+    }
+
+    #[test]
+    fn test_view_larger_than_map_is_centered() {
+        let half_view = Vec2::new(MAP_WIDTH, 100.0);
+        assert_eq!(
+            clamp_camera_position(Vec2::new(0.0, 300.0), half_view),
+            Vec2::new(MAP_WIDTH / 2.0, 300.0)
+        );
+    }
+}
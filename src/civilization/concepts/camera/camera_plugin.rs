@@ -0,0 +1,30 @@
+use crate::actions::add_touch_camera_actions;
+use crate::civilization::concepts::camera::camera_components::CameraFocus;
+use crate::civilization::concepts::camera::camera_events::FocusCameraOnArea;
+use crate::civilization::concepts::camera::camera_resources::CameraSettings;
+use crate::civilization::concepts::camera::camera_systems::*;
+use crate::GameState;
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CameraFocus>()
+            .register_type::<CameraSettings>()
+            .init_resource::<CameraSettings>()
+            .add_message::<FocusCameraOnArea>()
+            .add_systems(
+                Update,
+                (
+                    focus_on_phase_events,
+                    focus_camera_on_area,
+                    move_camera_towards_focus,
+                    apply_camera_actions.after(add_touch_camera_actions),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::{Reflect, Resource};
+
+#[derive(Resource, Debug, Reflect)]
+pub struct CameraSettings {
+    /// World units per second at zoom 1.0
+    pub pan_speed: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    /// How quickly the camera closes in on a focus target, higher is faster
+    pub focus_speed: f32,
+    /// Glide to areas highlighted by phase events, like a city being built or eliminated
+    pub follow_phase_events: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        CameraSettings {
+            pan_speed: 800.0,
+            min_zoom: 0.25,
+            max_zoom: 2.0,
+            focus_speed: 5.0,
+            follow_phase_events: true,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::actions::Actions;
+use crate::civilization::components::GameCamera;
+use crate::civilization::concepts::camera::camera_components::CameraFocus;
+use crate::civilization::concepts::camera::camera_events::FocusCameraOnArea;
+use crate::civilization::concepts::camera::camera_functions::clamp_camera_position;
+use crate::civilization::concepts::camera::camera_resources::CameraSettings;
+use crate::civilization::concepts::check_city_support::check_city_support_events::EliminateCity;
+use crate::civilization::concepts::city_construction::city_construction_events::BuildCityCommand;
+use bevy::prelude::{
+    Camera, Commands, Entity, MessageReader, MessageWriter, Projection, Query, Res, Time,
+    Transform, Vec2, With, Without,
+};
+
+/// Pans and zooms the [`GameCamera`] from the input gathered in [`Actions`] and keeps the
+/// view inside the map bounds.
+pub fn apply_camera_actions(
+    actions: Res<Actions>,
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    mut camera_query: Query<(Entity, &Camera, &mut Transform, &mut Projection), With<GameCamera>>,
+    mut commands: Commands,
+) {
+    let Ok((camera_entity, camera, mut transform, mut projection)) = camera_query.single_mut()
+    else {
+        return;
+    };
+    let Projection::Orthographic(orthographic) = projection.as_mut() else {
+        return;
+    };
+
+    if let Some(zoom) = actions.camera_zoom {
+        orthographic.scale =
+            (orthographic.scale * zoom).clamp(settings.min_zoom, settings.max_zoom);
+    }
+
+    let mut position = transform.translation.truncate();
+    if let Some(pan) = actions.camera_pan {
+        position += pan * settings.pan_speed * orthographic.scale * time.delta_secs();
+    }
+    if let Some(drag) = actions.camera_drag {
+        // Dragging moves the map with the pointer, so the camera goes the other way
+        position += Vec2::new(-drag.x, drag.y) * orthographic.scale;
+    }
+    if actions.camera_pan.is_some() || actions.camera_drag.is_some() {
+        commands.entity(camera_entity).remove::<CameraFocus>();
+    }
+
+    let half_view = camera.logical_viewport_size().unwrap_or_default() * orthographic.scale / 2.0;
+    position = clamp_camera_position(position, half_view);
+    transform.translation = position.extend(transform.translation.z);
+}
+
+pub fn focus_camera_on_area(
+    mut focus_reader: MessageReader<FocusCameraOnArea>,
+    camera_query: Query<(Entity, &Camera, &Projection), With<GameCamera>>,
+    area_query: Query<&Transform, Without<GameCamera>>,
+    mut commands: Commands,
+) {
+    for focus in focus_reader.read() {
+        if let Ok((camera_entity, camera, projection)) = camera_query.single() {
+            if let Ok(area_transform) = area_query.get(focus.area) {
+                let scale = match projection {
+                    Projection::Orthographic(orthographic) => orthographic.scale,
+                    _ => 1.0,
+                };
+                let half_view = camera.logical_viewport_size().unwrap_or_default() * scale / 2.0;
+                commands
+                    .entity(camera_entity)
+                    .insert(CameraFocus::new(clamp_camera_position(
+                        area_transform.translation.truncate(),
+                        half_view,
+                    )));
+            }
+        }
+    }
+}
+
+pub fn move_camera_towards_focus(
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    mut camera_query: Query<(Entity, &mut Transform, &CameraFocus), With<GameCamera>>,
+    mut commands: Commands,
+) {
+    for (camera_entity, mut transform, focus) in camera_query.iter_mut() {
+        let position = transform.translation.truncate();
+        if position.distance(focus.target) < 1.0 {
+            transform.translation = focus.target.extend(transform.translation.z);
+            commands.entity(camera_entity).remove::<CameraFocus>();
+        } else {
+            let t = 1.0 - (-settings.focus_speed * time.delta_secs()).exp();
+            transform.translation = position
+                .lerp(focus.target, t)
+                .extend(transform.translation.z);
+        }
+    }
+}
+
+/// Areas that phase events point at are worth looking at, so we glide the camera there.
+pub fn focus_on_phase_events(
+    mut build_city: MessageReader<BuildCityCommand>,
+    mut eliminate_city: MessageReader<EliminateCity>,
+    settings: Res<CameraSettings>,
+    mut focus_writer: MessageWriter<FocusCameraOnArea>,
+) {
+    for event in build_city.read() {
+        if settings.follow_phase_events {
+            focus_writer.write(FocusCameraOnArea::new(event.area));
+        }
+    }
+    for event in eliminate_city.read() {
+        if settings.follow_phase_events {
+            focus_writer.write(FocusCameraOnArea::new(event.area_entity));
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod camera_components;
+pub mod camera_events;
+pub mod camera_functions;
+pub mod camera_plugin;
+pub mod camera_resources;
+pub mod camera_systems;
@@ -7,3 +7,8 @@ pub struct Census {
 
 #[derive(Component, Debug, Reflect, Default)]
 pub struct HasPopulation;
+
+/// How far along the Archaeology Succession Table the player is. Nothing moves the markers yet,
+/// so census ties come down to faction order for now.
+#[derive(Component, Debug, Reflect, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AstPosition(pub usize);
@@ -0,0 +1,97 @@
+use crate::civilization::concepts::census::census_components::AstPosition;
+use crate::civilization::concepts::rules::rules_resources::MovementOrder;
+use crate::civilization::enums::GameFaction;
+use bevy::prelude::Entity;
+use std::cmp::Reverse;
+
+/// Ranks the players by population, largest first. Ties go to the player further along the AST,
+/// then to the faction that comes first.
+pub fn rank_census(
+    mut players: Vec<(Entity, usize, AstPosition, GameFaction)>,
+) -> Vec<(Entity, usize)> {
+    players.sort_by_key(|(_, population, ast_position, faction)| {
+        (Reverse(*population), Reverse(*ast_position), *faction)
+    });
+    players
+        .into_iter()
+        .map(|(player, population, _, _)| (player, population))
+        .collect()
+}
+
+/// The order the players move in, the first to move first.
+pub fn movement_order(census_order: &[Entity], order: MovementOrder) -> Vec<Entity> {
+    match order {
+        MovementOrder::LargestFirst => census_order.to_vec(),
+        MovementOrder::SmallestFirst => census_order.iter().rev().copied().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::census::census_resources::{CensusHistory, CensusRecord};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_census_ties_go_by_ast_then_faction() {
+        let small = create_entity();
+        let crete = create_entity();
+        let egypt = create_entity();
+        let ahead = create_entity();
+
+        let ranked = rank_census(vec![
+            (small, 2, AstPosition(5), GameFaction::Egypt),
+            (crete, 4, AstPosition(1), GameFaction::Crete),
+            (egypt, 4, AstPosition(1), GameFaction::Egypt),
+            (ahead, 4, AstPosition(2), GameFaction::Thrace),
+        ]);
+
+        assert_eq!(ranked, vec![(ahead, 4), (egypt, 4), (crete, 4), (small, 2)]);
+    }
+
+    #[test]
+    fn test_population_change_between_the_last_two_censuses() {
+        let player = create_entity();
+        let mut history = CensusHistory::default();
+        history.rounds.push(CensusRecord {
+            round: 1,
+            populations: vec![(player, 3)],
+        });
+
+        assert_eq!(history.population_change(player), None);
+
+        history.rounds.push(CensusRecord {
+            round: 2,
+            populations: vec![(player, 1)],
+        });
+
+        assert_eq!(history.population_change(player), Some(-2));
+    }
+
+    #[test]
+    fn test_smallest_moves_first() {
+        let large = create_entity();
+        let small = create_entity();
+
+        assert_eq!(
+            movement_order(&[large, small], MovementOrder::SmallestFirst),
+            vec![small, large]
+        );
+        assert_eq!(
+            movement_order(&[large, small], MovementOrder::LargestFirst),
+            vec![large, small]
+        );
+    }
+}
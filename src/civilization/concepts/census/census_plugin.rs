@@ -1,5 +1,6 @@
 use crate::GameActivity;
-use crate::civilization::concepts::census::census_components::Census;
+use crate::civilization::concepts::census::census_components::{AstPosition, Census};
+use crate::civilization::concepts::census::census_resources::CensusHistory;
 use crate::civilization::concepts::census::census_systems::{
     check_areas_for_population, perform_census,
 };
@@ -10,6 +11,8 @@ pub struct CensusPlugin;
 impl Plugin for CensusPlugin {
     fn build(&self, app: &mut bevy::app::App) {
         app.register_type::<Census>()
+            .register_type::<AstPosition>()
+            .init_resource::<CensusHistory>()
             .add_systems(OnEnter(GameActivity::Census), perform_census)
             .add_systems(OnExit(GameActivity::Census), check_areas_for_population);
     }
@@ -1,5 +1,35 @@
 use bevy::prelude::{Entity, Reflect, Resource};
 
+/// The census of one round, largest population first.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct CensusRecord {
+    pub round: usize,
+    pub populations: Vec<(Entity, usize)>,
+}
+
+/// Every census taken this game, oldest first.
+#[derive(Resource, Debug, Reflect, Default)]
+pub struct CensusHistory {
+    pub rounds: Vec<CensusRecord>,
+}
+
+impl CensusHistory {
+    /// How much the player's population changed between the last two censuses.
+    pub fn population_change(&self, player: Entity) -> Option<i64> {
+        let [.., previous, latest] = self.rounds.as_slice() else {
+            return None;
+        };
+        let population = |record: &CensusRecord| {
+            record
+                .populations
+                .iter()
+                .find(|(entity, _)| *entity == player)
+                .map(|(_, population)| *population as i64)
+        };
+        Some(population(latest)? - population(previous)?)
+    }
+}
+
 #[derive(Resource, Debug, Reflect, Default)]
 pub struct GameInfoAndStuff {
     pub census_order: Vec<Entity>,
@@ -1,19 +1,18 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::Populations;
 use crate::civilization::components::*;
 use crate::civilization::concepts::census::census_components::*;
-use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::census::census_functions::rank_census;
+use crate::civilization::concepts::census::census_resources::{
+    CensusHistory, CensusRecord, GameInfoAndStuff,
+};
 use crate::GameActivity;
-use bevy::platform::collections::HashMap;
-use bevy::prelude::{Commands, Entity, Name, NextState, Query, ResMut};
+use bevy::prelude::{Commands, Entity, NextState, Query, ResMut};
 /***
 Checks and marks areas / populations with HasPopulation to
 simplify queries later. This is normal
  */
-pub fn check_areas_for_population(
-    mut area_query: Query<(Entity, &mut Population)>,
-    mut commands: Commands,
-) {
-    for (area, population) in area_query.iter_mut() {
+pub fn check_areas_for_population(populations: Populations, mut commands: Commands) {
+    for (area, population) in populations.iter() {
         if population.has_population() {
             commands.entity(area).insert(HasPopulation {});
         } else {
@@ -23,19 +22,23 @@ pub fn check_areas_for_population(
 }
 
 pub fn perform_census(
-    mut stock_query: Query<(Entity, &Name, &PlayerAreas, &mut Census)>,
+    mut stock_query: Query<(Entity, &Faction, &AstPosition, &mut Census)>,
+    populations: Populations,
     mut census_order: ResMut<GameInfoAndStuff>,
+    mut census_history: ResMut<CensusHistory>,
     mut next_state: ResMut<NextState<GameActivity>>,
 ) {
-    census_order.census_order.clear();
-    let mut hash_to_sort = HashMap::new();
-    for (player, _name, player_areas, mut census) in stock_query.iter_mut() {
-        census.population = player_areas.total_population();
-        hash_to_sort.insert(player, census.population);
+    let mut players = Vec::new();
+    for (player, faction, ast_position, mut census) in stock_query.iter_mut() {
+        census.population = populations.player_areas(player).total_population();
+        players.push((player, census.population, *ast_position, faction.faction));
     }
-    let mut ordered: Vec<(Entity, usize)> = hash_to_sort.into_iter().collect();
-    ordered.sort_by(|a, b| b.1.cmp(&a.1));
+    let ranked = rank_census(players);
 
-    census_order.census_order = ordered.into_iter().map(|(entity, _)| entity).collect();
+    census_order.census_order = ranked.iter().map(|(entity, _)| *entity).collect();
+    census_history.rounds.push(CensusRecord {
+        round: census_order.round,
+        populations: ranked,
+    });
     next_state.set(GameActivity::Movement);
 }
@@ -1,10 +1,12 @@
 pub mod census_components;
+pub mod census_functions;
 pub mod census_plugin;
 pub mod census_resources;
 pub mod census_systems;
 
 pub mod prelude {
     pub use super::census_components::*;
+    pub use super::census_functions::*;
     pub use super::census_plugin::*;
     pub use super::census_resources::*;
     pub use super::census_systems::*;
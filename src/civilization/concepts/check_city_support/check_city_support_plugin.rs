@@ -1,5 +1,6 @@
 use crate::civilization::concepts::check_city_support::check_city_support_events::*;
 use crate::civilization::concepts::check_city_support::check_city_support_systems::*;
+use crate::civilization::general_systems::reduce_cities;
 use crate::GameActivity;
 use bevy::app::{App, Plugin};
 use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter, Update};
@@ -18,9 +19,12 @@ impl Plugin for CitySupportPlugin {
             .add_systems(
                 Update,
                 (
+                    // The city is gone before the player's support is checked again
                     eliminate_city
+                        .before(reduce_cities)
                         .run_if(in_state(GameActivity::CheckCitySupport)),
                     check_player_city_support
+                        .after(reduce_cities)
                         .run_if(in_state(GameActivity::CheckCitySupport)),
                     check_status
                         .run_if(in_state(GameActivity::CheckCitySupport)),
@@ -1,44 +1,33 @@
 use crate::civilization::components::*;
 use crate::civilization::concepts::check_city_support::check_city_support_components::*;
 use crate::civilization::concepts::check_city_support::check_city_support_events::*;
-use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::civilization::events::ReduceCity;
 use crate::GameActivity;
-use bevy::prelude::{Commands, Entity, MessageReader, MessageWriter, NextState, Query, ResMut, With};
-use crate::civilization::components::population::Population;
+use bevy::prelude::{Commands, Entity, MessageReader, MessageWriter, NextState, Query, Res, ResMut, With};
+use crate::civilization::components::population::{MaxPopulation, Populations};
 
 pub fn eliminate_city(
     mut eliminate_city: MessageReader<EliminateCity>,
     mut commands: Commands,
-    mut city_token_stock: Query<(&mut CityTokenStock, &mut PlayerCities)>,
-    area_population: Query<&mut Population>,
-    city_token: Query<&CityToken>,
-    mut move_tokens: MessageWriter<MoveTokensFromStockToAreaCommand>,
+    area_population: Query<&MaxPopulation>,
+    mut reduce_city: MessageWriter<ReduceCity>,
 ) {
     for eliminate in eliminate_city.read() {
         //Remove TooManyCities
         commands
             .entity(eliminate.player)
             .remove::<HasTooManyCities>();
-        if let Ok(city_token) = city_token.get(eliminate.city) {
-            if let Ok((mut city_stock, mut player_cities)) =
-                city_token_stock.get_mut(city_token.player)
-            {
-                if let Ok(population) = area_population.get(eliminate.area_entity) {
-                    //debug!("Eliminating city, conflict: {}, max_pop: {}", eliminate.is_conflict, population.max_population);
-                    move_tokens.write(MoveTokensFromStockToAreaCommand {
-                        player_entity: city_token.player,
-                        area_entity: eliminate.area_entity,
-                        number_of_tokens: if eliminate.is_conflict {
-                            6
-                        } else {
-                            population.max_population
-                        },
-                    });
-                    commands.entity(eliminate.area_entity).remove::<BuiltCity>();
-                    player_cities.remove_city_from_area(eliminate.area_entity);
-                    city_stock.return_token_to_stock(eliminate.city);
-                }
-            }
+        if let Ok(max_population) = area_population.get(eliminate.area_entity) {
+            //debug!("Eliminating city, conflict: {}, max_pop: {}", eliminate.is_conflict, max_population.0);
+            reduce_city.write(ReduceCity::new(
+                eliminate.area_entity,
+                if eliminate.is_conflict {
+                    6
+                } else {
+                    max_population.0
+                },
+            ));
         }
         commands
             .entity(eliminate.player)
@@ -58,19 +47,22 @@ pub fn check_status(
 
 pub fn check_player_city_support(
     check_city_support_query: Query<
-        (Entity, &PlayerCities, &PlayerAreas),
+        (Entity, &PlayerCities),
         With<NeedsToCheckCitySupport>,
     >,
+    populations: Populations,
+    rules: Res<RuleSet>,
     mut commands: Commands,
 ) {
-    for (player, cities, areas) in check_city_support_query.iter() {
+    for (player, cities) in check_city_support_query.iter() {
+        let areas = populations.player_areas(player);
         let number_of_cities = cities.number_of_cities();
-        let required_population = number_of_cities * 2;
+        let required_population = rules.required_population(number_of_cities);
 
         if required_population > areas.total_population() {
             //debug!("A player has too many cities");
             commands.entity(player).insert(HasTooManyCities::new(
-                (required_population - areas.total_population()) / 2,
+                (required_population - areas.total_population()) / rules.city_support,
                 required_population - areas.total_population(),
             ));
         } else {
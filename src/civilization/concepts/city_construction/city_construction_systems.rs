@@ -2,15 +2,20 @@ use crate::civilization::components::*;
 use crate::civilization::concepts::city_construction::city_construction_components::*;
 use crate::civilization::concepts::city_construction::city_construction_events::*;
 use crate::civilization::concepts::map::map_plugin::AvailableFactions;
+use crate::civilization::concepts::token_animation::token_animation_components::{
+    MergeIntoCity, TokenTween, TOKEN_SCALE,
+};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
 use crate::civilization::functions::{build_city_in_area, return_all_tokens_from_area_to_players};
 use crate::civilization::game_moves::game_moves_components::AvailableMoves;
 use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
 use crate::player::Player;
 use crate::GameActivity;
 use bevy::prelude::{
-    Commands, Entity, MessageReader, MessageWriter, NextState, Query, Res, ResMut, Transform, With,
+    Commands, Entity, MessageReader, MessageWriter, NextState, Query, Res, ResMut, Transform, Vec3,
+    With,
 };
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::{MaxPopulation, Populations};
 
 pub fn city_building_gate(
     query: Query<&IsBuilding>,
@@ -24,33 +29,37 @@ pub fn city_building_gate(
 
 pub fn build_city(
     mut command: MessageReader<BuildCityCommand>,
-    mut city_population: Query<(&mut Population, &Transform)>,
-    mut player_query: Query<(
-        &mut CityTokenStock,
-        &mut PlayerAreas,
-        &mut PlayerCities,
-        &mut TokenStock,
-        &Faction,
-    )>,
+    (city_population, populations): (Query<&Transform, With<MaxPopulation>>, Populations),
+    mut player_query: Query<(&mut CityTokenStock, &mut PlayerCities, &Faction)>,
     mut commands: Commands,
     mut recalculate_player_moves: MessageWriter<RecalculatePlayerMoves>,
     game_factions: Res<AvailableFactions>,
+    animation_settings: Res<TokenAnimationSettings>,
 ) {
     for build_city in command.read() {
-        if let Ok((mut population, _)) = city_population.get_mut(build_city.area) {
-            return_all_tokens_from_area_to_players(&mut population, &mut commands);
+        if let (Some(population), Ok(area_transform)) = (
+            populations.get(build_city.area),
+            city_population.get(build_city.area),
+        ) {
+            // Must be in place before the tokens are sent back to the stock
+            for token in population.player_tokens().values().flatten() {
+                commands
+                    .entity(*token)
+                    .insert(MergeIntoCity::new(area_transform.translation));
+            }
+            return_all_tokens_from_area_to_players(&population, &mut commands);
         }
 
-        if let Ok((mut city_stock, _, mut player_cities, _, faction)) =
+        if let Ok((mut city_stock, mut player_cities, faction)) =
             player_query.get_mut(build_city.player)
         {
-            if let Ok((_, area_transform)) = city_population.get_mut(build_city.area) {
+            if let Ok(area_transform) = city_population.get(build_city.area) {
                 let texture = game_factions
                     .faction_city_icons
                     .get(&faction.faction)
                     .unwrap()
                     .clone();
-                build_city_in_area(
+                let city_token = build_city_in_area(
                     &mut commands,
                     texture,
                     build_city,
@@ -58,6 +67,23 @@ pub fn build_city(
                     &mut player_cities,
                     area_transform,
                 );
+                if let Some(city_token) = city_token {
+                    if !animation_settings.skip_animations {
+                        // The city grows once the tokens it replaces have gathered
+                        let start = Transform::from_translation(area_transform.translation)
+                            .with_scale(Vec3::ZERO);
+                        commands.entity(city_token).insert((
+                            start,
+                            TokenTween::new(
+                                &start,
+                                area_transform.translation,
+                                TOKEN_SCALE,
+                                animation_settings.duration(),
+                            )
+                            .with_delay(animation_settings.duration()),
+                        ));
+                    }
+                }
                 recalculate_player_moves.write(RecalculatePlayerMoves::new(build_city.player));
             }
         }
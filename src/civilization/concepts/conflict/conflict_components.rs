@@ -1,7 +1,20 @@
-use bevy::prelude::{Component, Reflect};
+use bevy::prelude::{Component, Entity, Reflect};
 
 #[derive(Component, Debug, Reflect)]
 pub struct UnresolvedConflict;
 
 #[derive(Component, Debug, Reflect)]
 pub struct UnresolvedCityConflict;
+
+/// A city owner attacked by several players picking who to fight first.
+#[derive(Component, Debug, Reflect)]
+pub struct ChoosingCityAttacker {
+    pub area: Entity,
+    pub attackers: Vec<Entity>,
+}
+
+impl ChoosingCityAttacker {
+    pub fn new(area: Entity, attackers: Vec<Entity>) -> Self {
+        ChoosingCityAttacker { area, attackers }
+    }
+}
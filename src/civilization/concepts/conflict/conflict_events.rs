@@ -0,0 +1,58 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{Entity, Message};
+
+/// A city owner's choice of the attacker to fight first.
+#[derive(Message, Debug)]
+pub struct FightAttackerFirst {
+    pub player: Entity,
+    pub area: Entity,
+    pub attacker: Entity,
+}
+
+impl FightAttackerFirst {
+    pub fn new(player: Entity, area: Entity, attacker: Entity) -> Self {
+        FightAttackerFirst {
+            player,
+            area,
+            attacker,
+        }
+    }
+}
+
+/// What happened in a step of the conflict phase, losses are counted per player.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum ConflictStep {
+    Battle {
+        area: Entity,
+        losses: HashMap<Entity, usize>,
+    },
+    /// None of the attackers had more than six tokens, they were all removed.
+    CityHeld {
+        area: Entity,
+        owner: Entity,
+        losses: HashMap<Entity, usize>,
+    },
+    /// The city was replaced by six of its owner's tokens, the attacker gets to pillage it.
+    CityFell {
+        area: Entity,
+        owner: Entity,
+        attacker: Entity,
+    },
+    AttackerChosen {
+        area: Entity,
+        owner: Entity,
+        attacker: Entity,
+    },
+}
+
+/// The attacker took treasury tokens and a trade card from the owner of a fallen city. An
+/// empty treasury or hand leaves nothing to take.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct CityPillaged {
+    pub area: Entity,
+    pub attacker: Entity,
+    pub owner: Entity,
+    pub tokens: usize,
+    pub card: Option<TradeCard>,
+}
@@ -1,13 +1,85 @@
 use crate::civilization::components::population::Population;
-use crate::civilization::components::ReturnTokenToStock;
-use crate::civilization::functions::{remove_n_tokens_from_each_player, return_all_but_n_tokens_to_stock_for_player, return_all_tokens_from_area_for_player, return_all_tokens_to_stock};
-use bevy::prelude::{Commands, Entity};
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::rules::rules_resources::ConflictTies;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::Entity;
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+
+/// Treasury tokens an attacker takes when it pillages a city.
+pub const PILLAGED_TOKENS: usize = 3;
+
+/*
+These only work on the population and hand back the tokens that were lost, so they can be used both
+by the conflict triggers and by AIs simulating the conflict phase.
+ */
+
+/// Conflicts without cities come first, so a city only has to face the attackers that are left.
+/// Within each group areas go by their map id.
+pub fn conflict_order(mut zones: Vec<(Entity, i32, bool)>) -> Vec<Entity> {
+    zones.sort_by_key(|(_, id, has_city)| (*has_city, *id));
+    zones.into_iter().map(|(area, _, _)| area).collect()
+}
+
+/// A regular conflict between everyone in the area. Players with the same number of tokens are
+/// taken in entity order, so the same board always loses the same tokens.
+pub fn resolve_conflict(population: &mut Population, ties: ConflictTies) -> HashSet<Entity> {
+    if population.total_population() <= population.max_population {
+        return HashSet::new();
+    }
+    let mut players = population.players().into_iter().collect::<Vec<_>>();
+    players.sort();
+    players.sort_by_key(|player| std::cmp::Reverse(population.population_for_player(*player)));
+    if population.max_population == 1 {
+        handle_max_pop_is_one_conflicts(&mut players, population)
+    } else if population.all_lengths_equal() {
+        match ties {
+            ConflictTies::EvenRounds => handle_all_lengths_equal(&players, population),
+            ConflictTies::Alternating => remove_tokens_in_turn(&players, population),
+        }
+    } else {
+        handle_unequal_lengths(&mut players, population)
+    }
+}
+
+/// A regular conflict between two players in the area, everyone else stays out of it.
+pub fn fight_between(
+    population: &Population,
+    player: Entity,
+    other: Entity,
+    ties: ConflictTies,
+) -> HashSet<Entity> {
+    let mut fight = Population::new(population.max_population);
+    for fighter in [player, other] {
+        if let Some(tokens) = population.tokens_for_player(&fighter) {
+            fight.add_tokens_to_area(fighter, tokens.clone());
+        }
+    }
+    resolve_conflict(&mut fight, ties)
+}
+
+/// How many of the removed tokens each player lost.
+pub fn losses(population: &Population, removed: &HashSet<Entity>) -> HashMap<Entity, usize> {
+    population
+        .player_tokens()
+        .iter()
+        .map(|(player, tokens)| (*player, tokens.intersection(removed).count()))
+        .filter(|(_, lost)| *lost > 0)
+        .collect()
+}
+
+/// A card drawn at random from a hand, every single card is as likely to come up.
+pub fn draw_random_card(cards: &HashMap<TradeCard, usize>, rng: &mut StdRng) -> Option<TradeCard> {
+    cards
+        .iter()
+        .flat_map(|(card, count)| std::iter::repeat_n(*card, *count))
+        .choose(rng)
+}
 
 pub fn handle_all_lengths_equal(
     players: &Vec<Entity>,
     population: &mut Population,
-    commands: &mut Commands,
-) {
+) -> HashSet<Entity> {
     let mut token_rounds = 1;
     let must_remove = population.total_population() - population.max_population;
     while token_rounds * population.number_of_players() < must_remove {
@@ -15,14 +87,30 @@ pub fn handle_all_lengths_equal(
     }
 
     //debug!("Removing {} tokens from each player", token_rounds);
-    remove_n_tokens_from_each_player(players, population, commands, token_rounds);
+    remove_n_tokens_from_each_player(players, population, token_rounds)
+}
+
+/// The players lose one token each in turn until the area is down to its population limit.
+pub fn remove_tokens_in_turn(players: &[Entity], population: &mut Population) -> HashSet<Entity> {
+    let mut removed_tokens = HashSet::new();
+    for player in players.iter().cycle() {
+        if population.total_population() <= population.max_population {
+            break;
+        }
+        removed_tokens.extend(
+            population
+                .remove_tokens_from_area(player, 1)
+                .unwrap_or_default(),
+        );
+    }
+    removed_tokens
 }
 
 pub fn handle_unequal_lengths(
     players: &mut Vec<Entity>,
     population: &mut Population,
-    commands: &mut Commands,
-) {
+) -> HashSet<Entity> {
+    let mut removed_tokens = HashSet::new();
     // Sort players by their token count (from most to least)
     players.sort_by(|a, b| {
         population
@@ -38,12 +126,11 @@ pub fn handle_unequal_lengths(
         //debug!("current player has {} tokens", population.population_for_player(current_player));
 
         // Remove 1 token from the current player
-        for token in population
-            .remove_tokens_from_area(&current_player, 1)
-            .unwrap_or_default()
-        {
-            commands.entity(token).insert(ReturnTokenToStock);
-        }
+        removed_tokens.extend(
+            population
+                .remove_tokens_from_area(&current_player, 1)
+                .unwrap_or_default(),
+        );
 
         // Check if the current player still has tokens, if so, put them back in the queue
         if population.population_for_player(current_player) > 0 {
@@ -63,13 +150,13 @@ pub fn handle_unequal_lengths(
             break;
         }
     }
+    removed_tokens
 }
 
 pub fn handle_max_pop_is_one_conflicts(
     players: &mut Vec<Entity>,
     population: &mut Population,
-    commands: &mut Commands,
-) {
+) -> HashSet<Entity> {
     // Sort players by their population size (from highest to lowest)
     players.sort_by(|a, b| {
         population
@@ -81,20 +168,151 @@ pub fn handle_max_pop_is_one_conflicts(
     if population.all_lengths_equal() {
         //debug!("All players have the same number of tokens - we remove all tokens!");
         // Remove all tokens from every player
-        return_all_tokens_to_stock(population, commands);
+        population.remove_all_tokens()
     } else {
         //debug!("All players do not have the same number of tokens");
         // Find the player with the highest population
         let largest_player = players[0];
 
         // Remove all but 2 tokens from the player with the largest population
-        return_all_but_n_tokens_to_stock_for_player(population, &largest_player, 2, commands);
+        let mut removed_tokens = population
+            .remove_all_but_n_tokens(&largest_player, 2)
+            .unwrap_or_default();
 
         // Remove all tokens from all other players
         for player in players.iter().skip(1) {
             // Skip the largest player
             //debug!("Removing all tokens from other players");
-            return_all_tokens_from_area_for_player(population, player, commands);
+            removed_tokens.extend(population.remove_all_tokens_for_player(player));
         }
+        removed_tokens
+    }
+}
+
+pub fn remove_n_tokens_from_each_player(
+    players: &Vec<Entity>,
+    population: &mut Population,
+    token_rounds: usize,
+) -> HashSet<Entity> {
+    let mut removed_tokens = HashSet::new();
+    for player in players {
+        removed_tokens.extend(
+            population
+                .remove_tokens_from_area(player, token_rounds)
+                .unwrap_or_default(),
+        );
+    }
+    removed_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn add_tokens(population: &mut Population, player: Entity, tokens: usize) {
+        for _ in 0..tokens {
+            population.add_token_to_area(player, create_entity());
+        }
+    }
+
+    #[test]
+    fn test_city_conflicts_come_last_in_map_order() {
+        let city = create_entity();
+        let later = create_entity();
+        let first = create_entity();
+
+        let order = conflict_order(vec![(city, 1, true), (later, 7, false), (first, 3, false)]);
+
+        assert_eq!(order, vec![first, later, city]);
+    }
+
+    #[test]
+    fn test_fight_between_leaves_others_alone() {
+        let owner = create_entity();
+        let attacker = create_entity();
+        let bystander = create_entity();
+        let mut population = Population::new(3);
+        add_tokens(&mut population, owner, 6);
+        add_tokens(&mut population, attacker, 7);
+        add_tokens(&mut population, bystander, 8);
+
+        let removed = fight_between(&population, owner, attacker, ConflictTies::EvenRounds);
+        let lost = losses(&population, &removed);
+
+        assert_eq!(lost.get(&owner), Some(&5));
+        assert_eq!(lost.get(&attacker), Some(&5));
+        assert_eq!(lost.get(&bystander), None);
+    }
+
+    #[test]
+    fn test_resolve_conflict_removes_the_smaller_player() {
+        let small = create_entity();
+        let large = create_entity();
+        let mut population = Population::new(2);
+        add_tokens(&mut population, small, 1);
+        add_tokens(&mut population, large, 4);
+
+        let removed = resolve_conflict(&mut population, ConflictTies::EvenRounds);
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(population.population_for_player(small), 0);
+        assert_eq!(population.population_for_player(large), 4);
+    }
+
+    fn three_tied_players() -> Population {
+        let mut population = Population::new(4);
+        for _ in 0..3 {
+            add_tokens(&mut population, create_entity(), 2);
+        }
+        population
+    }
+
+    #[test]
+    fn test_tied_players_lose_even_rounds() {
+        let mut population = three_tied_players();
+
+        let removed = resolve_conflict(&mut population, ConflictTies::EvenRounds);
+
+        assert_eq!(removed.len(), 3);
+        assert!(population.all_lengths_equal());
+    }
+
+    #[test]
+    fn test_tied_players_lose_tokens_in_turn() {
+        let mut population = three_tied_players();
+
+        let removed = resolve_conflict(&mut population, ConflictTies::Alternating);
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(population.total_population(), 4);
+    }
+
+    #[test]
+    fn test_drawing_from_an_empty_hand_gives_nothing() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_eq!(draw_random_card(&HashMap::default(), &mut rng), None);
+    }
+
+    #[test]
+    fn test_drawing_from_a_hand_of_one_kind() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let cards = HashMap::from_iter([(TradeCard::Ochre, 2)]);
+
+        assert_eq!(draw_random_card(&cards, &mut rng), Some(TradeCard::Ochre));
     }
 }
@@ -1,4 +1,11 @@
-use crate::civilization::concepts::conflict::conflict_systems::{conflict_gate, find_conflict_zones};
+use crate::civilization::concepts::conflict::conflict_events::{
+    CityPillaged, ConflictStep, FightAttackerFirst,
+};
+use crate::civilization::concepts::conflict::conflict_resources::ConflictQueue;
+use crate::civilization::concepts::conflict::conflict_systems::{
+    conflict_gate, fight_chosen_attacker, find_conflict_zones, pillage_fallen_cities,
+    report_conflict_steps, start_next_conflict,
+};
 use crate::civilization::concepts::conflict::conflict_triggers::{
     on_add_unresolved_city_conflict, on_add_unresolved_conflict,
 };
@@ -10,10 +17,20 @@ pub struct ConflictPlugin;
 
 impl Plugin for ConflictPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameActivity::Conflict), find_conflict_zones)
+        app.add_message::<FightAttackerFirst>()
+            .add_message::<ConflictStep>()
+            .add_message::<CityPillaged>()
+            .init_resource::<ConflictQueue>()
+            .add_systems(OnEnter(GameActivity::Conflict), find_conflict_zones)
             .add_systems(
                 Update,
-                (conflict_gate).run_if(in_state(GameActivity::Conflict)),
+                (
+                    (fight_chosen_attacker, conflict_gate, start_next_conflict)
+                        .chain()
+                        .run_if(in_state(GameActivity::Conflict)),
+                    pillage_fallen_cities.run_if(in_state(GameActivity::Conflict)),
+                    report_conflict_steps,
+                ),
             )
             .add_observer(on_add_unresolved_conflict)
             .add_observer(on_add_unresolved_city_conflict);
@@ -0,0 +1,8 @@
+use bevy::prelude::{Entity, Resource};
+use std::collections::VecDeque;
+
+/// The areas still to be resolved this conflict phase, one at a time and in order.
+#[derive(Resource, Debug, Default)]
+pub struct ConflictQueue {
+    pub areas: VecDeque<Entity>,
+}
@@ -1,36 +1,274 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::{
+    BuiltCity, CityTokenStock, Faction, GameArea, PlayerCities, ReturnTokenToStock, TokenStock,
+    Treasury,
+};
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_events::HumanPlayerTradeCardsUpdated;
+use crate::civilization::concepts::conflict::conflict_components::{
+    ChoosingCityAttacker, UnresolvedCityConflict, UnresolvedConflict,
+};
+use crate::civilization::concepts::conflict::conflict_events::{
+    CityPillaged, ConflictStep, FightAttackerFirst,
+};
+use crate::civilization::concepts::conflict::conflict_functions::{
+    conflict_order, draw_random_card, fight_between, losses, PILLAGED_TOKENS,
+};
+use crate::civilization::concepts::conflict::conflict_resources::ConflictQueue;
+use crate::civilization::concepts::map::map_plugin::AvailableFactions;
+use crate::civilization::concepts::rules::rules_resources::{GameRng, RuleSet};
+use crate::civilization::functions::reduce_city;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
 use crate::GameActivity;
-use bevy::prelude::{Commands, Entity, Has, Name, NextState, Query, ResMut};
-use crate::civilization::components::BuiltCity;
-use crate::civilization::components::population::Population;
-use crate::civilization::concepts::conflict::conflict_components::{UnresolvedCityConflict, UnresolvedConflict};
+use bevy::prelude::{
+    debug, Commands, Entity, Has, MessageReader, MessageWriter, Name, NextState, Query, Res,
+    ResMut, Transform,
+};
 
 pub fn conflict_gate(
+    queue: Res<ConflictQueue>,
     conflicts: Query<&UnresolvedConflict>,
     city_conflicts: Query<&UnresolvedCityConflict>,
+    choosing: Query<&ChoosingCityAttacker>,
     mut next_state: ResMut<NextState<GameActivity>>,
 ) {
-    if conflicts.is_empty() && city_conflicts.is_empty() {
+    if queue.areas.is_empty()
+        && conflicts.is_empty()
+        && city_conflicts.is_empty()
+        && choosing.is_empty()
+    {
         //debug!("No conflicts found, moving to next state");
         next_state.set(GameActivity::CityConstruction);
     }
 }
 
 pub fn find_conflict_zones(
-    pop_query: Query<(Entity, &Name, &Population, Has<BuiltCity>)>,
-    mut commands: Commands,
+    area_query: Query<(&GameArea, Has<BuiltCity>)>,
+    populations: Populations,
+    mut queue: ResMut<ConflictQueue>,
 ) {
-    pop_query
+    let zones = populations
         .iter()
-        .filter(|(_, _, pop, has_city)| pop.is_conflict_zone(*has_city))
-        .for_each(|(conflict_zone, _name, _, has_city)| {
-            if has_city {
-                //debug!("City Conflict Zone found: {:#?}", name);
-                commands
-                    .entity(conflict_zone)
-                    .insert(UnresolvedCityConflict);
-            } else {
-                //debug!("Conflict Zone found: {:#?}", name);
-                commands.entity(conflict_zone).insert(UnresolvedConflict);
+        .filter_map(|(area, pop)| {
+            let (game_area, has_city) = area_query.get(area).ok()?;
+            pop.is_conflict_zone(has_city)
+                .then_some((area, game_area.id, has_city))
+        })
+        .collect::<Vec<_>>();
+    queue.areas = conflict_order(zones).into();
+}
+
+/// Starts the next conflict in the queue once the one before it is over.
+pub fn start_next_conflict(
+    mut queue: ResMut<ConflictQueue>,
+    conflicts: Query<&UnresolvedConflict>,
+    city_conflicts: Query<&UnresolvedCityConflict>,
+    choosing: Query<&ChoosingCityAttacker>,
+    areas: Query<Has<BuiltCity>>,
+    populations: Populations,
+    mut commands: Commands,
+) {
+    if !conflicts.is_empty() || !city_conflicts.is_empty() || !choosing.is_empty() {
+        return;
+    }
+    while let Some(area) = queue.areas.pop_front() {
+        // An area is fought over again after a city owner's choice, it may be settled by now
+        if let (Some(population), Ok(has_city)) = (populations.get(area), areas.get(area)) {
+            if population.is_conflict_zone(has_city) {
+                if has_city {
+                    commands.entity(area).insert(UnresolvedCityConflict);
+                } else {
+                    commands.entity(area).insert(UnresolvedConflict);
+                }
+                return;
             }
+        }
+    }
+}
+
+/// The city falls and its owner fights the chosen attacker, the rest of the area is
+/// resolved as a regular conflict next.
+pub fn fight_chosen_attacker(
+    mut choices: MessageReader<FightAttackerFirst>,
+    (mut queue, rules, game_factions): (
+        ResMut<ConflictQueue>,
+        Res<RuleSet>,
+        Res<AvailableFactions>,
+    ),
+    choosing: Query<&ChoosingCityAttacker>,
+    (areas, populations): (Query<(&Transform, &BuiltCity)>, Populations),
+    mut players: Query<(
+        &mut CityTokenStock,
+        &mut TokenStock,
+        &mut PlayerCities,
+        &Faction,
+    )>,
+    mut steps: MessageWriter<ConflictStep>,
+    mut commands: Commands,
+) {
+    for choice in choices.read() {
+        let Ok(choosing) = choosing.get(choice.player) else {
+            continue;
+        };
+        if choosing.area != choice.area || !choosing.attackers.contains(&choice.attacker) {
+            continue;
+        }
+        commands
+            .entity(choice.player)
+            .remove::<(ChoosingCityAttacker, AvailableMoves)>();
+        let (
+            Ok((area_transform, built_city)),
+            Some(mut population),
+            Ok((mut city_stock, mut token_stock, mut player_cities, faction)),
+        ) = (
+            areas.get(choice.area),
+            populations.get(choice.area),
+            players.get_mut(choice.player),
+        )
+        else {
+            continue;
+        };
+        steps.write(ConflictStep::AttackerChosen {
+            area: choice.area,
+            owner: choice.player,
+            attacker: choice.attacker,
+        });
+        let city_tokens = reduce_city(
+            &mut commands,
+            (choice.area, area_transform),
+            built_city,
+            6,
+            game_factions.faction_icons[&faction.faction].clone(),
+            (&mut city_stock, &mut token_stock, &mut player_cities),
+        );
+        steps.write(ConflictStep::CityFell {
+            area: choice.area,
+            owner: choice.player,
+            attacker: choice.attacker,
         });
+        population.add_tokens_to_area(choice.player, city_tokens);
+        let removed_tokens = fight_between(
+            &population,
+            choice.player,
+            choice.attacker,
+            rules.conflict_ties,
+        );
+        steps.write(ConflictStep::Battle {
+            area: choice.area,
+            losses: losses(&population, &removed_tokens),
+        });
+        for token in removed_tokens {
+            commands.entity(token).insert(ReturnTokenToStock);
+        }
+        queue.areas.push_front(choice.area);
+    }
+}
+
+/// The attacker of a fallen city takes treasury tokens and a random trade card from its owner.
+/// The owner's treasury tokens go back to its stock and the attacker fills its own treasury
+/// with as many tokens from its stock.
+pub fn pillage_fallen_cities(
+    mut steps: MessageReader<ConflictStep>,
+    mut players: Query<(&mut Treasury, &mut TokenStock, &mut PlayerTradeCards)>,
+    mut pillaged: MessageWriter<CityPillaged>,
+    mut cards_updated: MessageWriter<HumanPlayerTradeCardsUpdated>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for step in steps.read() {
+        let ConflictStep::CityFell {
+            area,
+            owner,
+            attacker,
+        } = step
+        else {
+            continue;
+        };
+        let Ok(
+            [(mut owner_treasury, mut owner_stock, mut owner_cards), (mut attacker_treasury, mut attacker_stock, mut attacker_cards)],
+        ) = players.get_many_mut([*owner, *attacker])
+        else {
+            continue;
+        };
+        let tokens = PILLAGED_TOKENS
+            .min(owner_treasury.tokens_in_treasury())
+            .min(attacker_stock.tokens_in_stock());
+        for _ in 0..tokens {
+            if let (Some(owner_token), Some(attacker_token)) = (
+                owner_treasury.remove_token_from_treasury(),
+                attacker_stock.remove_token_from_stock(),
+            ) {
+                owner_stock.return_token_to_stock(owner_token);
+                attacker_treasury.add_token_to_treasury(attacker_token);
+            }
+        }
+        let card = draw_random_card(owner_cards.cards(), &mut game_rng.0);
+        if let Some(card) = card {
+            owner_cards.remove_n_trade_cards(1, card);
+            attacker_cards.add_trade_card(card);
+            cards_updated.write(HumanPlayerTradeCardsUpdated::new(*owner));
+            cards_updated.write(HumanPlayerTradeCardsUpdated::new(*attacker));
+        }
+        pillaged.write(CityPillaged {
+            area: *area,
+            attacker: *attacker,
+            owner: *owner,
+            tokens,
+            card,
+        });
+    }
+}
+
+pub fn report_conflict_steps(
+    mut steps: MessageReader<ConflictStep>,
+    mut pillages: MessageReader<CityPillaged>,
+    names: Query<&Name>,
+) {
+    let name_of = |entity: Entity| {
+        names
+            .get(entity)
+            .map_or_else(|_| format!("{}", entity), |name| name.to_string())
+    };
+    for step in steps.read() {
+        match step {
+            ConflictStep::Battle { area, losses } => {
+                let mut lost = losses
+                    .iter()
+                    .map(|(player, lost)| format!("{} lost {}", name_of(*player), lost))
+                    .collect::<Vec<_>>();
+                lost.sort();
+                debug!("Conflict in {}: {}", name_of(*area), lost.join(", "));
+            }
+            ConflictStep::CityHeld { area, owner, .. } => {
+                debug!("The city of {} in {} held", name_of(*owner), name_of(*area));
+            }
+            ConflictStep::CityFell { area, owner, .. } => {
+                debug!("The city of {} in {} fell", name_of(*owner), name_of(*area));
+            }
+            ConflictStep::AttackerChosen {
+                area,
+                owner,
+                attacker,
+            } => {
+                debug!(
+                    "{} fights {} first in {}",
+                    name_of(*owner),
+                    name_of(*attacker),
+                    name_of(*area)
+                );
+            }
+        }
+    }
+    for pillage in pillages.read() {
+        let card = pillage
+            .card
+            .map_or_else(|| "no card".to_string(), |card| format!("{:?}", card));
+        debug!(
+            "{} pillaged the city of {} in {}: {} treasury tokens and {}",
+            name_of(pillage.attacker),
+            name_of(pillage.owner),
+            name_of(pillage.area),
+            pillage.tokens,
+            card
+        );
+    }
 }
@@ -1,5 +1,7 @@
 pub mod conflict_components;
+pub mod conflict_events;
 pub mod conflict_functions;
 pub mod conflict_plugin;
+pub mod conflict_resources;
 pub mod conflict_systems;
-pub mod conflict_triggers;
\ No newline at end of file
+pub mod conflict_triggers;
@@ -0,0 +1,42 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use bevy::prelude::{Component, Entity, Reflect};
+
+/// A human player sharing the device with the other human players.
+#[derive(Component, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub struct HumanSeat {
+    pub seat: usize,
+}
+
+impl HumanSeat {
+    pub fn new(seat: usize) -> Self {
+        Self { seat }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct HotSeatPanel;
+
+#[derive(Component, Default)]
+pub struct HotSeatMoveList;
+
+/// Covers the whole screen while the device is handed over.
+#[derive(Component, Default)]
+pub struct PassDeviceScreen;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub enum HotSeatButton {
+    TakeSeat,
+    PlayMove { id: usize, tokens: usize },
+    PassTo(Entity),
+    DraftOffer(Entity),
+    Give(TradeCard),
+    Ask(TradeCard),
+    PublishOffer,
+    CancelOffer,
+    DeclineOffer(Entity),
+    WithdrawOffer(Entity),
+    Pay(TradeCard),
+    TakeBack(TradeCard),
+    HandOverCards,
+    BackOutOfTrade,
+}
@@ -0,0 +1,333 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::{
+    TradeCard, TradeCardTrait,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_components::HotSeatButton;
+use crate::civilization::concepts::hot_seat::hot_seat_resources::Settlement;
+use crate::civilization::concepts::trade::trade_components::TradeOffer;
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move, TradeMove};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::Entity;
+use std::collections::VecDeque;
+
+/// The next waiting human that still has moves to make. Humans that are done are dropped.
+pub fn next_seat(
+    queue: &mut VecDeque<Entity>,
+    has_moves: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    while let Some(player) = queue.pop_front() {
+        if has_moves(player) {
+            return Some(player);
+        }
+    }
+    None
+}
+
+/// The most tokens a move can take, zero for moves without tokens.
+pub fn max_tokens(game_move: &Move) -> usize {
+    match game_move {
+        Move::PopulationExpansion(pop_exp_move) => pop_exp_move.max_tokens,
+        Move::Movement(movement_move)
+        | Move::AttackArea(movement_move)
+        | Move::AttackCity(movement_move) => movement_move.max_tokens,
+        _ => 0,
+    }
+}
+
+pub fn describe_move(game_move: &Move, name_of: &impl Fn(Entity) -> String) -> String {
+    match game_move {
+        Move::PopulationExpansion(pop_exp_move) => {
+            format!("Expand in {}", name_of(pop_exp_move.area))
+        }
+        Move::Movement(movement_move) => format!(
+            "Move from {} to {}",
+            name_of(movement_move.source),
+            name_of(movement_move.target)
+        ),
+        Move::AttackArea(movement_move) => format!(
+            "Attack {} from {}",
+            name_of(movement_move.target),
+            name_of(movement_move.source)
+        ),
+        Move::AttackCity(movement_move) => format!(
+            "Attack the city in {} from {}",
+            name_of(movement_move.target),
+            name_of(movement_move.source)
+        ),
+        Move::EndMovement => "End movement".to_string(),
+        Move::ChooseAttacker(choose_move) => format!(
+            "Fight {} first ({} tokens)",
+            name_of(choose_move.attacker),
+            choose_move.attacker_tokens
+        ),
+        Move::CityConstruction(build_city_move) => {
+            format!("Build a city in {}", name_of(build_city_move.target))
+        }
+        Move::EndCityConstruction => "End city construction".to_string(),
+        Move::EliminateCity(el_move) => format!(
+            "Remove the city in {} ({} tokens back)",
+            name_of(el_move.area),
+            el_move.tokens_gained
+        ),
+        Move::Trade(TradeMove::ProposeTrade(receiver, _)) => {
+            format!("Offer a trade to {}", name_of(*receiver))
+        }
+        Move::Trade(TradeMove::AcceptOrDeclineTrade(_)) => "Accept the offer".to_string(),
+        Move::Trade(TradeMove::AutoDeclineTrade(_)) => "Decline the offer".to_string(),
+        Move::Trade(TradeMove::StopTrading) => "Stop trading".to_string(),
+        Move::Trade(TradeMove::SettleTrade(_)) => "Settle the trade".to_string(),
+    }
+}
+
+/// The buttons for a human's available moves, in move order. Moves with tokens can be played
+/// with all of them or just one, and offers made to the player can be accepted or declined.
+pub fn move_buttons(
+    me: Entity,
+    available_moves: &AvailableMoves,
+    name_of: &impl Fn(Entity) -> String,
+    offers: &HashMap<Entity, TradeOffer>,
+) -> Vec<(String, HotSeatButton)> {
+    let mut ids = available_moves.moves.keys().copied().collect::<Vec<_>>();
+    ids.sort();
+    let mut buttons = Vec::new();
+    for id in ids {
+        let game_move = &available_moves.moves[&id];
+        let label = describe_move(game_move, name_of);
+        match game_move {
+            Move::Trade(TradeMove::ProposeTrade(receiver, _)) => {
+                buttons.push((label, HotSeatButton::DraftOffer(*receiver)));
+            }
+            Move::Trade(TradeMove::AcceptOrDeclineTrade(trade)) => {
+                let Some(offer) = offers.get(trade) else {
+                    continue;
+                };
+                if offer.am_i_the_receiver(me) {
+                    buttons.push((
+                        format!("Accept the offer from {}", offer.initiator_name),
+                        HotSeatButton::PlayMove { id, tokens: 0 },
+                    ));
+                    buttons.push((
+                        format!("Decline the offer from {}", offer.initiator_name),
+                        HotSeatButton::DeclineOffer(*trade),
+                    ));
+                } else {
+                    buttons.push((
+                        format!("Withdraw the offer to {}", offer.receiver_name),
+                        HotSeatButton::WithdrawOffer(*trade),
+                    ));
+                }
+            }
+            _ => {
+                let tokens = max_tokens(game_move);
+                if tokens > 1 {
+                    buttons.push((
+                        format!("{} (all {})", label, tokens),
+                        HotSeatButton::PlayMove { id, tokens },
+                    ));
+                    buttons.push((
+                        format!("{} (1)", label),
+                        HotSeatButton::PlayMove { id, tokens: 1 },
+                    ));
+                } else {
+                    buttons.push((label, HotSeatButton::PlayMove { id, tokens }));
+                }
+            }
+        }
+    }
+    buttons
+}
+
+/// Whether another card of this kind can be put into an offer without promising cards we lack.
+pub fn can_give(offer: &TradeOffer, hand: &HashMap<TradeCard, usize>, card: TradeCard) -> bool {
+    let offered = offer.initiator_pays.get(&card).copied().unwrap_or_default()
+        + offer
+            .initiator_pays_guaranteed
+            .get(&card)
+            .copied()
+            .unwrap_or_default();
+    hand.get(&card).copied().unwrap_or_default() > offered
+}
+
+/// Whether one more of this card can go towards a settlement. Any tradeable card will do,
+/// calamities included.
+pub fn can_pay(settlement: &Settlement, hand: &HashMap<TradeCard, usize>, card: TradeCard) -> bool {
+    card.is_tradeable()
+        && !settlement.is_complete()
+        && hand.get(&card).copied().unwrap_or_default()
+            > settlement.cards.get(&card).copied().unwrap_or_default()
+}
+
+/// The buttons for picking the cards to settle a trade with.
+pub fn settlement_buttons(
+    settlement: &Settlement,
+    hand: &HashMap<TradeCard, usize>,
+) -> Vec<(String, HotSeatButton)> {
+    let mut buttons = Vec::new();
+    let mut cards = hand.keys().copied().collect::<Vec<_>>();
+    cards.sort_by_key(|card| (card.value(), card.to_string()));
+    for card in cards.iter() {
+        if can_pay(settlement, hand, *card) {
+            buttons.push((format!("Pay {}", card), HotSeatButton::Pay(*card)));
+        }
+    }
+    for card in cards.iter() {
+        if settlement.cards.get(card).copied().unwrap_or_default()
+            > settlement.guaranteed.get(card).copied().unwrap_or_default()
+        {
+            buttons.push((
+                format!("Take back {}", card),
+                HotSeatButton::TakeBack(*card),
+            ));
+        }
+    }
+    if settlement.is_complete() {
+        buttons.push((
+            "Hand over the cards".to_string(),
+            HotSeatButton::HandOverCards,
+        ));
+    }
+    buttons.push((
+        "Back out of the trade".to_string(),
+        HotSeatButton::BackOutOfTrade,
+    ));
+    buttons
+}
+
+pub fn describe_cards(cards: &HashMap<TradeCard, usize>) -> String {
+    let mut cards = cards
+        .iter()
+        .map(|(card, count)| format!("{} {}", count, card))
+        .collect::<Vec<_>>();
+    cards.sort();
+    cards.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::game_moves::game_moves_components::MovementMove;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn name_of(_: Entity) -> String {
+        "Thrace".to_string()
+    }
+
+    #[test]
+    fn test_next_seat_skips_humans_without_moves() {
+        let done = create_entity();
+        let waiting = create_entity();
+        let mut queue = VecDeque::from([done, waiting]);
+
+        let next = next_seat(&mut queue, |player| player == waiting);
+
+        assert_eq!(next, Some(waiting));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_moves_with_tokens_get_two_buttons() {
+        let me = create_entity();
+        let movement_move = MovementMove::new(create_entity(), create_entity(), me, 3);
+        let available_moves = AvailableMoves::new(HashMap::from([
+            (1, Move::Movement(movement_move)),
+            (2, Move::EndMovement),
+        ]));
+
+        let buttons = move_buttons(me, &available_moves, &name_of, &HashMap::default());
+
+        assert_eq!(buttons.len(), 3);
+        assert_eq!(buttons[0].1, HotSeatButton::PlayMove { id: 1, tokens: 3 });
+        assert_eq!(buttons[1].1, HotSeatButton::PlayMove { id: 1, tokens: 1 });
+        assert_eq!(buttons[2].0, "End movement");
+    }
+
+    #[test]
+    fn test_receiver_can_accept_or_decline_an_offer() {
+        let me = create_entity();
+        let other = create_entity();
+        let trade = create_entity();
+        let offer = TradeOffer::propose_trade(other, "Crete", me, "Thrace");
+        let available_moves = AvailableMoves::new(HashMap::from([(
+            1,
+            Move::Trade(TradeMove::AcceptOrDeclineTrade(trade)),
+        )]));
+
+        let buttons = move_buttons(
+            me,
+            &available_moves,
+            &name_of,
+            &HashMap::from([(trade, offer)]),
+        );
+
+        assert_eq!(buttons[0].0, "Accept the offer from Crete");
+        assert_eq!(buttons[1].1, HotSeatButton::DeclineOffer(trade));
+    }
+
+    #[test]
+    fn test_settlement_starts_with_the_promised_cards() {
+        let settlement =
+            Settlement::new(create_entity(), HashMap::from([(TradeCard::Ochre, 2)]), 3);
+        let hand = HashMap::from([(TradeCard::Ochre, 2), (TradeCard::Salt, 1)]);
+
+        let buttons = settlement_buttons(&settlement, &hand);
+
+        assert_eq!(buttons[0].1, HotSeatButton::Pay(TradeCard::Salt));
+        assert!(!buttons
+            .iter()
+            .any(|(_, button)| *button == HotSeatButton::TakeBack(TradeCard::Ochre)));
+        assert!(!buttons
+            .iter()
+            .any(|(_, button)| *button == HotSeatButton::HandOverCards));
+    }
+
+    #[test]
+    fn test_tradeable_calamities_can_be_handed_over() {
+        let mut settlement = Settlement::new(create_entity(), HashMap::default(), 1);
+        let hand = HashMap::from([
+            (TradeCard::Ochre, 1),
+            (TradeCard::Treachery, 1),
+            (TradeCard::VolcanoEarthquake, 1),
+        ]);
+
+        assert!(can_pay(&settlement, &hand, TradeCard::Treachery));
+        assert!(!can_pay(&settlement, &hand, TradeCard::VolcanoEarthquake));
+
+        settlement.pay(TradeCard::Treachery);
+
+        assert!(settlement.is_complete());
+        assert!(!can_pay(&settlement, &hand, TradeCard::Ochre));
+    }
+
+    #[test]
+    fn test_only_picked_cards_can_be_taken_back() {
+        let mut settlement =
+            Settlement::new(create_entity(), HashMap::from([(TradeCard::Ochre, 1)]), 2);
+        settlement.pay(TradeCard::Ochre);
+
+        settlement.take_back(TradeCard::Ochre);
+        settlement.take_back(TradeCard::Ochre);
+
+        assert_eq!(settlement.cards, HashMap::from([(TradeCard::Ochre, 1)]));
+    }
+
+    #[test]
+    fn test_cannot_give_more_cards_than_in_hand() {
+        let mut offer = TradeOffer::propose_trade(create_entity(), "Crete", create_entity(), "");
+        let hand = HashMap::from([(TradeCard::Ochre, 1)]);
+
+        assert!(can_give(&offer, &hand, TradeCard::Ochre));
+        offer.initiator_pays_more(TradeCard::Ochre);
+        assert!(!can_give(&offer, &hand, TradeCard::Ochre));
+    }
+}
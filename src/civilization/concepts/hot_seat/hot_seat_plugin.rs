@@ -0,0 +1,37 @@
+use crate::civilization::concepts::hot_seat::hot_seat_components::HumanSeat;
+use crate::civilization::concepts::hot_seat::hot_seat_resources::{ActiveSeat, HotSeatSetup};
+use crate::civilization::concepts::hot_seat::hot_seat_systems::*;
+use crate::civilization::concepts::hot_seat::hot_seat_triggers::on_add_available_moves_for_human_seat;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter, OnExit};
+
+pub struct HotSeatPlugin;
+
+impl Plugin for HotSeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HumanSeat>()
+            .register_type::<HotSeatSetup>()
+            .init_resource::<HotSeatSetup>()
+            .init_resource::<ActiveSeat>()
+            .add_systems(OnEnter(GameActivity::StartGame), setup_hot_seat_panel)
+            .add_systems(OnEnter(GameActivity::Trade), hide_seat_for_trade)
+            .add_systems(OnExit(GameActivity::Trade), clear_seats_after_trade)
+            .add_systems(
+                Update,
+                (
+                    hot_seat_button_action,
+                    advance_seat,
+                    refresh_pass_device_screen,
+                    refresh_seat_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                offer_trade_moves_to_active_seat.run_if(in_state(GameActivity::Trade)),
+            )
+            .add_observer(on_add_available_moves_for_human_seat);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::trade::trade_components::TradeOffer;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{Entity, Reflect, Resource};
+use std::collections::VecDeque;
+
+/// How many of the players are humans taking turns on this device.
+#[derive(Resource, Debug, Reflect, Default)]
+pub struct HotSeatSetup {
+    pub human_seats: usize,
+}
+
+/// The human player holding the device, and the ones waiting for it.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveSeat {
+    pub player: Option<Entity>,
+    pub revealed: bool,
+    pub waiting_for_moves: bool,
+    pub queue: VecDeque<Entity>,
+    pub offer_draft: Option<TradeOffer>,
+    pub settlement: Option<Settlement>,
+}
+
+impl ActiveSeat {
+    pub fn hand_to(&mut self, player: Entity) {
+        self.player = Some(player);
+        self.revealed = false;
+        self.waiting_for_moves = false;
+        self.offer_draft = None;
+        self.settlement = None;
+    }
+
+    /// Private things, like trade cards, may only be shown to the player holding the device.
+    pub fn may_see(&self, player: Entity) -> bool {
+        self.player.is_none() || (self.player == Some(player) && self.revealed)
+    }
+}
+
+/// The cards a human hands over for an accepted trade. The promised cards are in from the
+/// start, the player picks the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    pub trade: Entity,
+    pub guaranteed: HashMap<TradeCard, usize>,
+    pub total: usize,
+    pub cards: HashMap<TradeCard, usize>,
+}
+
+impl Settlement {
+    pub fn new(trade: Entity, guaranteed: HashMap<TradeCard, usize>, total: usize) -> Self {
+        Settlement {
+            trade,
+            cards: guaranteed.clone(),
+            guaranteed,
+            total,
+        }
+    }
+
+    pub fn number_of_cards(&self) -> usize {
+        self.cards.values().sum()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.number_of_cards() == self.total
+    }
+
+    pub fn pay(&mut self, card: TradeCard) {
+        *self.cards.entry(card).or_default() += 1;
+    }
+
+    /// Puts a picked card back in the hand, promised cards stay in.
+    pub fn take_back(&mut self, card: TradeCard) {
+        if self.cards.get(&card).copied().unwrap_or_default()
+            > self.guaranteed.get(&card).copied().unwrap_or_default()
+        {
+            if let Some(count) = self.cards.get_mut(&card) {
+                *count -= 1;
+            }
+            self.cards.retain(|_, count| *count > 0);
+        }
+    }
+}
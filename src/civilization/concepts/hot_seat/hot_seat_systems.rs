@@ -0,0 +1,540 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_events::HumanPlayerTradeCardsUpdated;
+use crate::civilization::concepts::hot_seat::hot_seat_components::{
+    HotSeatButton, HotSeatMoveList, HotSeatPanel, HumanSeat, PassDeviceScreen,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_functions::{
+    can_give, can_pay, describe_cards, move_buttons, next_seat, settlement_buttons,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_resources::{ActiveSeat, Settlement};
+use crate::civilization::concepts::trade::trade_components::{
+    CanTrade, NeedsTradeMove, PlayerTradeInterests, PublishedOffer, TradeOffer,
+};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move, TradeMove};
+use crate::civilization::game_moves::game_moves_systems::GameMoveWriters;
+use crate::civilization::ui::ui_builder::{ButtonPartial, UIBuilder, UiBuilderDefaults, BG_COLOR};
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{
+    debug, default, Added, Changed, Commands, DetectChanges, Entity, FlexWrap, GlobalZIndex, Has,
+    Interaction, JustifyContent, MessageWriter, Name, PositionType, Query, RemovedComponents, Res,
+    ResMut, State, Val, With,
+};
+use itertools::Itertools;
+
+pub fn setup_hot_seat_panel(
+    commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    seats: Query<(), With<HumanSeat>>,
+) {
+    if seats.is_empty() {
+        return;
+    }
+    let mut panel = UIBuilder::new(commands, Some(ui_defaults.clone()));
+
+    panel
+        .with_component::<HotSeatPanel>()
+        .as_flex_col(Val::Percent(50.), Val::Auto)
+        .with_position_type(PositionType::Absolute)
+        .with_left(Val::Percent(25.))
+        .with_bottom(Val::Px(80.))
+        .with_bg_color(BG_COLOR)
+        .child()
+        .as_flex_row()
+        .with_width(Val::Percent(100.))
+        .with_flex_wrap(FlexWrap::Wrap)
+        .with_component::<HotSeatMoveList>();
+
+    let (_root_entity, _commands) = panel.build();
+}
+
+/// Hands the device to the next human once the one holding it has nothing left to do. While
+/// trading, any human still at the table may take over.
+pub fn advance_seat(
+    mut active_seat: ResMut<ActiveSeat>,
+    seats: Query<(Entity, &HumanSeat, Has<AvailableMoves>, Has<CanTrade>)>,
+    phase: Res<State<GameActivity>>,
+) {
+    let trading = *phase.get() == GameActivity::Trade;
+    let busy = |player: Entity| {
+        seats
+            .get(player)
+            .is_ok_and(|(_, _, has_moves, can_trade)| has_moves || (trading && can_trade))
+    };
+    if active_seat.player.is_some_and(busy) {
+        return;
+    }
+    let mut next = None;
+    if !active_seat.queue.is_empty() {
+        next = next_seat(&mut active_seat.queue, busy);
+    }
+    if next.is_none() && trading {
+        next = seats
+            .iter()
+            .filter(|(player, ..)| Some(*player) != active_seat.player && busy(*player))
+            .min_by_key(|(_, seat, ..)| seat.seat)
+            .map(|(player, ..)| player);
+    }
+    if let Some(next) = next {
+        active_seat.hand_to(next);
+    }
+}
+
+/// Everyone has to take the device again when trading starts, the trade cards are on the table.
+pub fn hide_seat_for_trade(mut active_seat: ResMut<ActiveSeat>) {
+    active_seat.revealed = false;
+    active_seat.offer_draft = None;
+    active_seat.settlement = None;
+}
+
+/// Trade moves nobody made are of no use in the next phase.
+pub fn clear_seats_after_trade(
+    seats: Query<Entity, (With<HumanSeat>, With<AvailableMoves>)>,
+    mut active_seat: ResMut<ActiveSeat>,
+    mut commands: Commands,
+) {
+    for player in seats.iter() {
+        commands.entity(player).remove::<AvailableMoves>();
+    }
+    active_seat.queue.clear();
+    active_seat.offer_draft = None;
+    active_seat.settlement = None;
+}
+
+/// Covers the screen with a prompt to pass the device whenever a new human is up.
+pub fn refresh_pass_device_screen(
+    mut commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    active_seat: Res<ActiveSeat>,
+    screens: Query<Entity, With<PassDeviceScreen>>,
+    names: Query<&Name>,
+) {
+    if !active_seat.is_changed() {
+        return;
+    }
+    for screen in screens.iter() {
+        commands.entity(screen).despawn();
+    }
+    let Some(player) = active_seat.player.filter(|_| !active_seat.revealed) else {
+        return;
+    };
+    let name = names.get(player).map(|name| name.as_str()).unwrap_or("");
+
+    let mut screen = UIBuilder::new(commands, Some(ui_defaults.clone()));
+    screen
+        .with_component::<PassDeviceScreen>()
+        .as_flex_col(Val::Percent(100.), Val::Percent(100.))
+        .with_position_type(PositionType::Absolute)
+        .with_left(Val::Px(0.))
+        .with_top(Val::Px(0.))
+        .with_justify_content(JustifyContent::Center)
+        .with_bg_color(BG_COLOR)
+        .add_default_text_child(format!("Pass the device to {}", name))
+        .with_button(
+            Some(ButtonPartial {
+                text: Some(format!("I am {}", name)),
+                ..default()
+            }),
+            HotSeatButton::TakeSeat,
+        );
+    let (root, mut commands) = screen.build();
+    commands.entity(root).insert(GlobalZIndex(100));
+}
+
+/// Offers made to the human holding the device show up right away.
+pub fn offer_trade_moves_to_active_seat(
+    active_seat: Res<ActiveSeat>,
+    new_offers: Query<&TradeOffer, Added<TradeOffer>>,
+    mut commands: Commands,
+) {
+    let Some(player) = active_seat.player.filter(|_| active_seat.revealed) else {
+        return;
+    };
+    if new_offers
+        .iter()
+        .any(|offer| offer.am_i_the_receiver(player))
+    {
+        commands
+            .entity(player)
+            .remove::<NeedsTradeMove>()
+            .insert(NeedsTradeMove);
+    }
+}
+
+/// What the seat panel shows of the board.
+#[derive(SystemParam)]
+pub struct SeatBoard<'w, 's> {
+    names: Query<'w, 's, &'static Name>,
+    players: Query<
+        'w,
+        's,
+        (
+            Option<&'static AvailableMoves>,
+            &'static PlayerTradeCards,
+            Has<CanTrade>,
+        ),
+        With<HumanSeat>,
+    >,
+    seats: Query<'w, 's, (Entity, &'static HumanSeat), With<CanTrade>>,
+    interests: Query<'w, 's, &'static PlayerTradeInterests>,
+    offers: Query<'w, 's, (Entity, &'static TradeOffer)>,
+    phase: Res<'w, State<GameActivity>>,
+}
+
+impl SeatBoard<'_, '_> {
+    fn name_of(&self, entity: Entity) -> String {
+        self.names
+            .get(entity)
+            .map(|name| name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// The lines of text and the buttons for the human holding the device.
+    fn panel_for(&self, active_seat: &ActiveSeat) -> (Vec<String>, Vec<(String, HotSeatButton)>) {
+        let mut lines = Vec::new();
+        let mut buttons = Vec::new();
+        let Some(player) = active_seat.player.filter(|_| active_seat.revealed) else {
+            return (lines, buttons);
+        };
+        let Ok((available_moves, trade_cards, can_trade)) = self.players.get(player) else {
+            return (lines, buttons);
+        };
+        lines.push(format!("{} is playing", self.name_of(player)));
+
+        if let Some(draft) = active_seat.offer_draft.as_ref() {
+            lines.push(format!("Offer to {}", draft.receiver_name));
+            lines.push(format!(
+                "You give: {} and {} hidden",
+                describe_cards(&draft.initiator_pays_guaranteed),
+                draft.initiator_pays.values().sum::<usize>()
+            ));
+            lines.push(format!(
+                "You get: {} and {} hidden",
+                describe_cards(&draft.initiator_gets_guaranteed),
+                draft.initiator_gets.values().sum::<usize>()
+            ));
+            for card in trade_cards.commodity_cards().keys() {
+                if can_give(draft, trade_cards.cards(), *card) {
+                    buttons.push((format!("Give {}", card), HotSeatButton::Give(*card)));
+                }
+            }
+            if let Ok(interests) = self.interests.get(draft.receiver) {
+                for card in interests.wants.iter() {
+                    buttons.push((format!("Ask for {}", card), HotSeatButton::Ask(*card)));
+                }
+            }
+            if draft.can_be_accepted() {
+                buttons.push(("Make the offer".to_string(), HotSeatButton::PublishOffer));
+            }
+            buttons.push(("Cancel the offer".to_string(), HotSeatButton::CancelOffer));
+            return (lines, buttons);
+        }
+
+        if let Some(settlement) = active_seat.settlement.as_ref() {
+            if let Ok((_, offer)) = self.offers.get(settlement.trade) {
+                let partner = if offer.am_i_the_initiator(player) {
+                    &offer.receiver_name
+                } else {
+                    &offer.initiator_name
+                };
+                lines.push(format!("Settling the trade with {}", partner));
+            }
+            lines.push(format!(
+                "You hand over: {} ({} of {})",
+                describe_cards(&settlement.cards),
+                settlement.number_of_cards(),
+                settlement.total
+            ));
+            buttons.extend(settlement_buttons(settlement, trade_cards.cards()));
+            return (lines, buttons);
+        }
+
+        if active_seat.waiting_for_moves {
+            lines.push("...".to_string());
+        } else if let Some(available_moves) = available_moves {
+            let offers = self
+                .offers
+                .iter()
+                .map(|(entity, offer)| (entity, offer.clone()))
+                .collect::<HashMap<_, _>>();
+            buttons.extend(move_buttons(
+                player,
+                available_moves,
+                &|entity| self.name_of(entity),
+                &offers,
+            ));
+        }
+
+        if *self.phase.get() == GameActivity::Trade && can_trade {
+            for (other, _) in self
+                .seats
+                .iter()
+                .filter(|(other, _)| *other != player)
+                .sorted_by_key(|(_, seat)| seat.seat)
+            {
+                buttons.push((
+                    format!("Pass the device to {}", self.name_of(other)),
+                    HotSeatButton::PassTo(other),
+                ));
+            }
+        }
+        (lines, buttons)
+    }
+}
+
+/// Rebuilds the seat panel whenever the human holding the device, their moves or the trade
+/// offers have changed.
+pub fn refresh_seat_panel(
+    commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    active_seat: Res<ActiveSeat>,
+    move_list: Query<Entity, With<HotSeatMoveList>>,
+    board: SeatBoard,
+    mut changes: (
+        Query<(), (With<HumanSeat>, Changed<AvailableMoves>)>,
+        Query<(), Changed<TradeOffer>>,
+        RemovedComponents<AvailableMoves>,
+    ),
+) {
+    let (changed_moves, changed_offers, removed_moves) = &mut changes;
+    let moves_removed = removed_moves.read().count() > 0;
+    if !active_seat.is_changed()
+        && changed_moves.is_empty()
+        && changed_offers.is_empty()
+        && !moves_removed
+    {
+        return;
+    }
+    let Ok(move_list) = move_list.single() else {
+        return;
+    };
+    let (lines, buttons) = board.panel_for(&active_seat);
+
+    let mut ui_builder =
+        UIBuilder::start_from_entity(commands, move_list, true, Some(ui_defaults.clone()));
+    let _b = ui_builder.with_children(|mut b| {
+        for line in lines {
+            b = b.add_default_text_child(line);
+        }
+        for (label, action) in buttons {
+            b = b.with_button(
+                Some(ButtonPartial {
+                    text: Some(label),
+                    ..default()
+                }),
+                action,
+            );
+        }
+    });
+    let _ = ui_builder.build();
+}
+
+/// The trade side of the hot seat buttons.
+#[derive(SystemParam)]
+pub struct SeatTrading<'w, 's> {
+    names: Query<'w, 's, &'static Name>,
+    offers: Query<'w, 's, &'static mut TradeOffer>,
+    card_updates: MessageWriter<'w, HumanPlayerTradeCardsUpdated>,
+}
+
+impl SeatTrading<'_, '_> {
+    fn name_of(&self, entity: Entity) -> String {
+        self.names
+            .get(entity)
+            .map(|name| name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// The cards the player owes for the trade, with the promised ones already picked. None if
+    /// the promised cards are no longer in the hand.
+    fn settlement_for(
+        &self,
+        player: Entity,
+        trade: Entity,
+        hand: &PlayerTradeCards,
+    ) -> Option<Settlement> {
+        let offer = self.offers.get(trade).ok()?;
+        let settlement = if offer.am_i_the_initiator(player) {
+            Settlement::new(
+                trade,
+                offer.initiator_pays_guaranteed.clone(),
+                offer.pays_number_of_cards(),
+            )
+        } else {
+            Settlement::new(
+                trade,
+                offer.initiator_gets_guaranteed.clone(),
+                offer.gets_number_of_cards(),
+            )
+        };
+        settlement
+            .guaranteed
+            .iter()
+            .all(|(card, count)| hand.number_of_cards_for_trade_card(*card) >= *count)
+            .then_some(settlement)
+    }
+
+    fn back_out(&mut self, player: Entity, trade: Entity) {
+        if let Ok(mut offer) = self.offers.get_mut(trade) {
+            offer.reject(player);
+            offer.withdraw(player);
+        }
+    }
+
+    fn play(&mut self, player: Entity, trade_move: &TradeMove) {
+        match trade_move {
+            TradeMove::AcceptOrDeclineTrade(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.accept(&player);
+                }
+            }
+            TradeMove::AutoDeclineTrade(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.reject(player);
+                }
+            }
+            TradeMove::ProposeTrade(..) | TradeMove::SettleTrade(_) | TradeMove::StopTrading => {}
+        }
+    }
+}
+
+/// Plays what the human holding the device clicked on.
+pub fn hot_seat_button_action(
+    interactions: Query<(&Interaction, &HotSeatButton), Changed<Interaction>>,
+    mut active_seat: ResMut<ActiveSeat>,
+    players: Query<(&AvailableMoves, &PlayerTradeCards)>,
+    mut writers: GameMoveWriters,
+    mut trading: SeatTrading,
+    phase: Res<State<GameActivity>>,
+    mut commands: Commands,
+) {
+    for (interaction, action) in interactions.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(player) = active_seat.player else {
+            continue;
+        };
+        // Trade moves are recalculated after every click, there is no countdown for humans
+        let mut needs_trade_move = false;
+        match action {
+            HotSeatButton::TakeSeat => {
+                active_seat.revealed = true;
+                trading
+                    .card_updates
+                    .write(HumanPlayerTradeCardsUpdated::new(player));
+                needs_trade_move = *phase.get() == GameActivity::Trade;
+            }
+            HotSeatButton::PlayMove { id, tokens } => {
+                let Ok((available_moves, hand)) = players.get(player) else {
+                    continue;
+                };
+                let Some(game_move) = available_moves.moves.get(id) else {
+                    continue;
+                };
+                debug!("A human plays {}", game_move);
+                match game_move {
+                    Move::Trade(TradeMove::StopTrading) => {
+                        commands.entity(player).remove::<CanTrade>();
+                    }
+                    Move::Trade(TradeMove::SettleTrade(trade)) => {
+                        active_seat.settlement = trading.settlement_for(player, *trade, hand);
+                        if active_seat.settlement.is_none() {
+                            trading.back_out(player, *trade);
+                            needs_trade_move = true;
+                        }
+                    }
+                    Move::Trade(trade_move) => {
+                        trading.play(player, trade_move);
+                        needs_trade_move = true;
+                    }
+                    _ => {
+                        writers.write_move(player, game_move, *tokens);
+                        active_seat.waiting_for_moves = true;
+                    }
+                }
+            }
+            HotSeatButton::PassTo(other) => {
+                active_seat.hand_to(*other);
+            }
+            HotSeatButton::DraftOffer(receiver) => {
+                active_seat.offer_draft = Some(TradeOffer::propose_trade(
+                    player,
+                    trading.name_of(player),
+                    *receiver,
+                    trading.name_of(*receiver),
+                ));
+            }
+            HotSeatButton::Give(card) => {
+                if let Some(draft) = active_seat.offer_draft.as_mut() {
+                    draft.initiator_pays_more(*card);
+                }
+            }
+            HotSeatButton::Ask(card) => {
+                if let Some(draft) = active_seat.offer_draft.as_mut() {
+                    draft.initiator_gets_more(*card);
+                }
+            }
+            HotSeatButton::PublishOffer => {
+                if let Some(draft) = active_seat.offer_draft.take() {
+                    commands.spawn((draft, PublishedOffer));
+                    needs_trade_move = true;
+                }
+            }
+            HotSeatButton::CancelOffer => {
+                active_seat.offer_draft = None;
+            }
+            HotSeatButton::DeclineOffer(trade) => {
+                if let Ok(mut offer) = trading.offers.get_mut(*trade) {
+                    offer.reject(player);
+                }
+                needs_trade_move = true;
+            }
+            HotSeatButton::WithdrawOffer(trade) => {
+                if let Ok(mut offer) = trading.offers.get_mut(*trade) {
+                    offer.withdraw(player);
+                }
+                needs_trade_move = true;
+            }
+            HotSeatButton::Pay(card) => {
+                let Ok((_, hand)) = players.get(player) else {
+                    continue;
+                };
+                if let Some(settlement) = active_seat.settlement.as_mut() {
+                    if can_pay(settlement, hand.cards(), *card) {
+                        settlement.pay(*card);
+                    }
+                }
+            }
+            HotSeatButton::TakeBack(card) => {
+                if let Some(settlement) = active_seat.settlement.as_mut() {
+                    settlement.take_back(*card);
+                }
+            }
+            HotSeatButton::HandOverCards => {
+                if let Some(settlement) = active_seat
+                    .settlement
+                    .take_if(|settlement| settlement.is_complete())
+                {
+                    if let Ok(mut offer) = trading.offers.get_mut(settlement.trade) {
+                        offer.settle(player, settlement.cards);
+                    }
+                    needs_trade_move = true;
+                }
+            }
+            HotSeatButton::BackOutOfTrade => {
+                if let Some(settlement) = active_seat.settlement.take() {
+                    trading.back_out(player, settlement.trade);
+                    needs_trade_move = true;
+                }
+            }
+        }
+        if needs_trade_move {
+            commands
+                .entity(player)
+                .remove::<NeedsTradeMove>()
+                .insert(NeedsTradeMove);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::civilization::concepts::hot_seat::hot_seat_components::HumanSeat;
+use crate::civilization::concepts::hot_seat::hot_seat_resources::ActiveSeat;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use bevy::prelude::{Add, On, Query, ResMut};
+
+/// Humans get in line for the device when they have something to do.
+pub fn on_add_available_moves_for_human_seat(
+    trigger: On<Add, AvailableMoves>,
+    seats: Query<&HumanSeat>,
+    mut active_seat: ResMut<ActiveSeat>,
+) {
+    let player = trigger.event().entity;
+    if !seats.contains(player) {
+        return;
+    }
+    if active_seat.player == Some(player) {
+        active_seat.waiting_for_moves = false;
+    } else if !active_seat.queue.contains(&player) {
+        active_seat.queue.push_back(player);
+    }
+}
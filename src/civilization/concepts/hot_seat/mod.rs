@@ -0,0 +1,6 @@
+pub mod hot_seat_components;
+pub mod hot_seat_functions;
+pub mod hot_seat_plugin;
+pub mod hot_seat_resources;
+pub mod hot_seat_systems;
+pub mod hot_seat_triggers;
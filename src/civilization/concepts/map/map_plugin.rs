@@ -1,14 +1,18 @@
-use crate::civilization::components::population::Population;
-use crate::civilization::components::{CityFlood, CitySite, FloodPlain, GameArea, GameCamera, LandPassage, NeedsConnections, StartArea, Volcano};
+use crate::civilization::components::population::MaxPopulation;
+use crate::civilization::components::{CityFlood, CitySite, FloodPlain, GameArea, GameCamera, LandPassage, NeedsConnections, SeaPassage, StartArea, Volcano};
+use crate::civilization::concepts::remote_seat::remote_seat_systems::playing_locally;
 use crate::civilization::enums::GameFaction;
 use crate::civilization::general_systems::setup_players;
 use crate::loading::TextureAssets;
 use crate::GameState;
 use bevy::platform::collections::{HashMap, HashSet};
-use bevy::prelude::{App, AssetServer, Assets, Camera, Commands, Handle, Image, IntoScheduleConfigs, Name, OnEnter, Plugin, Query, Res, ResMut, Resource, Sprite, Startup, Transform, Vec3, With};
+use bevy::prelude::{App, AssetServer, Assets, Camera, Commands, Entity, Handle, Image, IntoScheduleConfigs, Name, OnEnter, Plugin, Query, Res, ResMut, Resource, Sprite, Startup, Transform, Vec3, With};
 use bevy_common_assets::ron::RonAssetPlugin;
 use rand::seq::IteratorRandom;
 
+pub const MAP_WIDTH: f32 = 2500.0;
+pub const MAP_HEIGHT: f32 = 1325.0;
+
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
@@ -18,7 +22,7 @@ impl Plugin for MapPlugin {
             .add_systems(Startup, setup)
             .add_systems(
                 OnEnter(GameState::Playing),
-                (load_map,setup_players).chain(),
+                (load_map,setup_players.run_if(playing_locally)).chain(),
             );
     }
 }
@@ -285,7 +289,7 @@ fn load_map(
         .collect();
 
         let (_, mut transform) = camera.single_mut().unwrap();
-        transform.translation = Vec3::new(1250.0, 662.5, 0.0);
+        transform.translation = Vec3::new(MAP_WIDTH / 2.0, MAP_HEIGHT / 2.0, 0.0);
 
         commands.spawn((
             Sprite {
@@ -293,44 +297,13 @@ fn load_map(
 
                 ..Default::default()
             },
-            Transform::from_xyz(1250.0, 662.5, -1.0),
+            Transform::from_xyz(MAP_WIDTH / 2.0, MAP_HEIGHT / 2.0, -1.0),
         ));
 
         for area in level.areas.clone() {
             let n = remove_random_place(&mut ancient_places).unwrap_or("STANDARD_NAME".to_string());
 
-            let entity = commands
-                .spawn((
-                    Name::new(format!("{}:{}", area.id, n)),
-                    GameArea::new(area.id),
-                    LandPassage::default(),
-                    NeedsConnections {
-                        land_connections: area.land_connections,
-                        sea_connections: area.sea_connections,
-                    },
-                    Population::new(area.max_population),
-                    Transform::from_xyz(area.x, area.y, 1.),
-                    // SpriteBundle {
-                    //     texture: textures.dot.clone(),
-                    //     transform: Transform::from_xyz(area.x, area.y, 1.),
-                    //     ..Default::default()
-                    // }
-                ))
-                .id();
-            if area.city_site {
-                commands.entity(entity).insert(CitySite);
-            }
-
-            if area.city_flood {
-                commands.entity(entity).insert(CityFlood);
-            }
-
-            if area.flood_plain {
-                commands.entity(entity).insert(FloodPlain);
-            }
-            if area.volcano {
-                commands.entity(entity).insert(Volcano);
-            }
+            let entity = spawn_area(&mut commands, &area, n);
 
             if let Some(faction) = area.start_area {
                 available_factions.factions.insert(faction);
@@ -414,3 +387,34 @@ fn load_map(
         }
     }
 }
+
+/// Spawns a map area with everything but its start area.
+pub fn spawn_area(commands: &mut Commands, area: &Area, place: String) -> Entity {
+    let entity = commands
+        .spawn((
+            Name::new(format!("{}:{}", area.id, place)),
+            GameArea::new(area.id),
+            LandPassage::default(),
+            SeaPassage::default(),
+            NeedsConnections {
+                land_connections: area.land_connections.clone(),
+                sea_connections: area.sea_connections.clone(),
+            },
+            MaxPopulation(area.max_population),
+            Transform::from_xyz(area.x, area.y, 1.),
+        ))
+        .id();
+    if area.city_site {
+        commands.entity(entity).insert(CitySite);
+    }
+    if area.city_flood {
+        commands.entity(entity).insert(CityFlood);
+    }
+    if area.flood_plain {
+        commands.entity(entity).insert(FloodPlain);
+    }
+    if area.volcano {
+        commands.entity(entity).insert(Volcano);
+    }
+    entity
+}
@@ -1,10 +1,23 @@
+pub mod area_info;
+pub mod board_audit;
+pub mod camera;
 pub mod census;
 pub mod map;
 pub mod population_expansion;
 pub mod trade;
 pub mod acquire_trade_cards;
+pub mod ai_personality;
 pub mod movement;
+pub mod phase_tracker;
+pub mod player_dashboard;
 pub mod check_city_support;
+pub mod hot_seat;
+pub mod spectator;
 pub mod city_construction;
 pub mod conflict;
 pub mod remove_surplus_population;
+pub mod token_animation;
+pub mod undo;
+pub mod scenario;
+pub mod rules;
+pub mod remote_seat;
@@ -1,21 +1,25 @@
-use crate::civilization::components::population::Population;
-use crate::civilization::components::{FixTokenPositions, PlayerAreas, Token};
-use crate::civilization::concepts::census::prelude::GameInfoAndStuff;
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::{FixTokenPositions, InArea, Token};
+use crate::civilization::concepts::census::prelude::{movement_order, GameInfoAndStuff};
 use crate::civilization::concepts::movement::movement_components::*;
 use crate::civilization::concepts::movement::movement_events::*;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
 use crate::civilization::game_moves::game_moves_components::AvailableMoves;
 use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
 use crate::player::Player;
 use crate::GameActivity;
 use bevy::prelude::{
-    Commands, Entity, MessageReader, MessageWriter, NextState, Query, ResMut, Transform, With, Without,
+    Commands, Entity, MessageReader, MessageWriter, NextState, Query, Res, ResMut, With, Without,
 };
+use bevy::platform::collections::HashSet;
 
 pub fn start_movement_activity(
     mut game_info: ResMut<GameInfoAndStuff>,
+    rules: Res<RuleSet>,
     mut next_player: MessageWriter<NextPlayerStarted>,
 ) {
-    game_info.left_to_move = game_info.census_order.clone();
+    // Players are popped off the back, the first to move goes last
+    game_info.left_to_move = movement_order(&game_info.census_order, rules.movement_order);
     game_info.left_to_move.reverse();
     next_player.write(NextPlayerStarted);
 }
@@ -71,46 +75,33 @@ pub fn player_end_movement(
 
 pub fn move_tokens_from_area_to_area(
     mut move_events: MessageReader<MoveTokenFromAreaToAreaCommand>,
-    mut pop_query: Query<(&mut Population, &Transform), Without<Token>>,
+    populations: Populations,
     mut commands: Commands,
-    mut player_areas: Query<&mut PlayerAreas>,
     tokens_that_can_move: Query<&Token, Without<TokenHasMoved>>,
-    mut token_transform: Query<&mut Transform, With<Token>>,
     mut recalculate_player_moves: MessageWriter<RecalculatePlayerMoves>,
 ) {
+    // InArea is only updated when the commands are applied, so keep track of tokens sent off already
+    let mut moved_tokens = HashSet::new();
     for ev in move_events.read() {
-        if let Ok((mut from_pop, _)) = pop_query.get_mut(ev.source_area) {
-            let cloned = from_pop.player_tokens().clone();
-            if let Some(player_tokens) = cloned.get(&ev.player) {
+        if let (Some(from_pop), Some(_)) = (
+            populations.get(ev.source_area),
+            populations.get(ev.target_area),
+        ) {
+            if let Some(player_tokens) = from_pop.tokens_for_player(&ev.player) {
                 let tokens_that_can_move = player_tokens
                     .iter()
-                    .filter(|t| tokens_that_can_move.get(**t).is_ok())
+                    .filter(|t| tokens_that_can_move.get(**t).is_ok() && !moved_tokens.contains(*t))
                     .copied()
                     .collect::<Vec<_>>();
                 if tokens_that_can_move.len() < ev.number_of_tokens {
                     recalculate_player_moves.write(RecalculatePlayerMoves::new(ev.player));
                 } else {
-                    let tokens_to_move = tokens_that_can_move
-                        .iter()
-                        .take(ev.number_of_tokens)
-                        .copied()
-                        .collect::<Vec<_>>();
-                    for token in tokens_to_move.iter() {
-                        from_pop.remove_token_from_area(ev.player, *token);
-                    }
-
-                    if let Ok((mut to_pop, target_transform)) = pop_query.get_mut(ev.target_area) {
-                        if let Ok(mut player_area) = player_areas.get_mut(ev.player) {
-                            tokens_to_move.iter().for_each(|token| {
-                                commands.entity(*token).insert(TokenHasMoved);
-                                if let Ok(mut token_transform) = token_transform.get_mut(*token) {
-                                    token_transform.translation = target_transform.translation;
-                                }
-                                player_area.remove_token_from_area(&ev.source_area, *token);
-                                to_pop.add_token_to_area(ev.player, *token);
-                                player_area.add_token_to_area(ev.target_area, *token);
-                            });
-                        }
+                    // The tokens stay where they are, FixTokenPositions on the target area moves them over
+                    for token in tokens_that_can_move.into_iter().take(ev.number_of_tokens) {
+                        moved_tokens.insert(token);
+                        commands
+                            .entity(token)
+                            .insert((TokenHasMoved, InArea(ev.target_area)));
                     }
                 }
             }
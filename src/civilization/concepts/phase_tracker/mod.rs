@@ -0,0 +1,3 @@
+pub mod phase_tracker_components;
+pub mod phase_tracker_plugin;
+pub mod phase_tracker_systems;
@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component, Default)]
+pub struct PhaseTrackerRoot;
+
+#[derive(Component, Default)]
+pub struct PhaseTrackerStatus;
@@ -0,0 +1,21 @@
+use crate::civilization::concepts::phase_tracker::phase_tracker_systems::*;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, state_changed, IntoScheduleConfigs, OnEnter};
+
+pub struct PhaseTrackerPlugin;
+
+impl Plugin for PhaseTrackerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameActivity::StartGame), setup_phase_tracker)
+            .add_systems(
+                Update,
+                (
+                    rebuild_phase_tracker.run_if(state_changed::<GameActivity>),
+                    update_phase_tracker_status,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
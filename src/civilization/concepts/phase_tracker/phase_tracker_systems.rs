@@ -0,0 +1,127 @@
+use crate::civilization::concepts::census::prelude::GameInfoAndStuff;
+use crate::civilization::concepts::check_city_support::check_city_support_components::HasTooManyCities;
+use crate::civilization::concepts::city_construction::city_construction_components::IsBuilding;
+use crate::civilization::concepts::movement::movement_components::PerformingMovement;
+use crate::civilization::concepts::phase_tracker::phase_tracker_components::{
+    PhaseTrackerRoot, PhaseTrackerStatus,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_components::NeedsExpansion;
+use crate::civilization::ui::ui_builder::{UIBuilder, UiBuilderDefaults, BG_COLOR, CARD_COLOR};
+use crate::player::Player;
+use crate::GameActivity;
+use bevy::prelude::{Commands, Entity, Name, Or, PositionType, Query, Res, State, Text, Val, With};
+
+/// The phases of a round, in the order they are played.
+pub const ROUND_PHASES: [GameActivity; 9] = [
+    GameActivity::PopulationExpansion,
+    GameActivity::Census,
+    GameActivity::Movement,
+    GameActivity::Conflict,
+    GameActivity::CityConstruction,
+    GameActivity::RemoveSurplusPopulation,
+    GameActivity::CheckCitySupport,
+    GameActivity::AcquireTradeCards,
+    GameActivity::Trade,
+];
+
+pub fn setup_phase_tracker(commands: Commands, ui_defaults: Res<UiBuilderDefaults>) {
+    let mut phase_tracker = UIBuilder::new(commands, Some(ui_defaults.clone()));
+
+    phase_tracker
+        .with_component::<PhaseTrackerRoot>()
+        .as_flex_col(Val::Percent(50.), Val::Auto)
+        .with_position_type(PositionType::Absolute)
+        .with_left(Val::Percent(25.))
+        .with_bottom(Val::Px(0.))
+        .with_bg_color(BG_COLOR);
+
+    let (_root_entity, _commands) = phase_tracker.build();
+}
+
+/// Redraws the timeline every time the game moves on to a new phase.
+pub fn rebuild_phase_tracker(
+    commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    game_info: Res<GameInfoAndStuff>,
+    current_activity: Res<State<GameActivity>>,
+    root_query: Query<Entity, With<PhaseTrackerRoot>>,
+) {
+    let Ok(root) = root_query.single() else {
+        return;
+    };
+    let mut ui_builder =
+        UIBuilder::start_from_entity(commands, root, true, Some(ui_defaults.clone()));
+    let _b = ui_builder.with_children(|mut b| {
+        b = b
+            .add_default_text_child(format!(
+                "Round {} - {:?}",
+                game_info.round,
+                current_activity.get()
+            ))
+            .child()
+            .as_flex_row();
+        for phase in ROUND_PHASES.iter() {
+            let color = if phase == current_activity.get() {
+                CARD_COLOR
+            } else {
+                BG_COLOR
+            };
+            b = b
+                .child()
+                .with_bg_color(color)
+                .with_text(format!("{:?}", phase), None, Some(12.), None)
+                .parent();
+        }
+        b.parent()
+            .child()
+            .with_default_text("")
+            .with_component::<PhaseTrackerStatus>()
+            .parent();
+    });
+    let _ = ui_builder.build();
+}
+
+/// Shows who is acting right now and who is still waiting for their turn.
+pub fn update_phase_tracker_status(
+    game_info: Res<GameInfoAndStuff>,
+    player_query: Query<(Entity, &Name), With<Player>>,
+    acting_query: Query<
+        Entity,
+        (
+            With<Player>,
+            Or<(
+                With<PerformingMovement>,
+                With<IsBuilding>,
+                With<NeedsExpansion>,
+                With<HasTooManyCities>,
+            )>,
+        ),
+    >,
+    mut status_query: Query<&mut Text, With<PhaseTrackerStatus>>,
+) {
+    let Ok(mut status) = status_query.single_mut() else {
+        return;
+    };
+    let names = |entities: &mut dyn Iterator<Item = Entity>| {
+        entities
+            .filter_map(|entity| player_query.get(entity).ok())
+            .map(|(_, name)| name.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let acting = names(&mut acting_query.iter());
+    // Movement is the only phase where players take turns, the others are played simultaneously
+    let pending = names(&mut game_info.left_to_move.iter().rev().copied());
+
+    let mut lines = Vec::new();
+    if !acting.is_empty() {
+        lines.push(format!("Acting: {}", acting));
+    }
+    if !pending.is_empty() {
+        lines.push(format!("Pending: {}", pending));
+    }
+    let new_status = lines.join("\n");
+    if status.0 != new_status {
+        status.0 = new_status;
+    }
+}
@@ -0,0 +1,3 @@
+pub mod player_dashboard_components;
+pub mod player_dashboard_plugin;
+pub mod player_dashboard_systems;
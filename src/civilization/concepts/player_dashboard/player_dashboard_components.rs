@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component, Default)]
+pub struct PlayerDashboardRoot;
+
+#[derive(Component, Default)]
+pub struct PlayerDashboardList;
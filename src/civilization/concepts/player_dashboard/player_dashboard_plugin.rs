@@ -0,0 +1,16 @@
+use crate::civilization::concepts::player_dashboard::player_dashboard_systems::*;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
+
+pub struct PlayerDashboardPlugin;
+
+impl Plugin for PlayerDashboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameActivity::StartGame), setup_player_dashboard)
+            .add_systems(
+                Update,
+                refresh_player_dashboard.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
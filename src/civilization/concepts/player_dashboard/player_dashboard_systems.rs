@@ -0,0 +1,145 @@
+use crate::civilization::components::{CityTokenStock, PlayerCities, TokenStock, Treasury};
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::census::prelude::{Census, CensusHistory, GameInfoAndStuff};
+use crate::civilization::concepts::player_dashboard::player_dashboard_components::{
+    PlayerDashboardList, PlayerDashboardRoot,
+};
+use crate::civilization::concepts::remote_seat::remote_seat_components::HiddenTradeCards;
+use crate::civilization::ui::ui_builder::{UIBuilder, UiBuilderDefaults, BG_COLOR};
+use crate::player::Player;
+use crate::stupid_ai::prelude::IsHuman;
+use bevy::prelude::{
+    Changed, Commands, DetectChanges, Entity, Has, Name, Or, PositionType, Query, Res, Val, With,
+};
+use itertools::Itertools;
+
+pub fn setup_player_dashboard(commands: Commands, ui_defaults: Res<UiBuilderDefaults>) {
+    let mut dashboard = UIBuilder::new(commands, Some(ui_defaults.clone()));
+
+    dashboard
+        .with_component::<PlayerDashboardRoot>()
+        .as_flex_col(Val::Percent(20.), Val::Auto)
+        .with_position_type(PositionType::Absolute)
+        .with_right(Val::Px(0.))
+        .with_top(Val::Px(0.))
+        .with_bg_color(BG_COLOR)
+        .child()
+        .as_flex_col(Val::Percent(100.), Val::Auto)
+        .with_component::<PlayerDashboardList>();
+
+    let (_root_entity, _commands) = dashboard.build();
+}
+
+/// Rebuilds the dashboard whenever anything it shows has changed for any player, or the turn
+/// order has been recalculated.
+pub fn refresh_player_dashboard(
+    commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    game_info: Res<GameInfoAndStuff>,
+    census_history: Res<CensusHistory>,
+    dashboard_list: Query<Entity, With<PlayerDashboardList>>,
+    changed_players: Query<
+        (),
+        (
+            With<Player>,
+            Or<(
+                Changed<Census>,
+                Changed<TokenStock>,
+                Changed<Treasury>,
+                Changed<CityTokenStock>,
+                Changed<PlayerCities>,
+                Changed<PlayerTradeCards>,
+                Changed<HiddenTradeCards>,
+            )>,
+        ),
+    >,
+    player_query: Query<
+        (
+            Entity,
+            &Name,
+            &Census,
+            &TokenStock,
+            &Treasury,
+            &CityTokenStock,
+            &PlayerCities,
+            &PlayerTradeCards,
+            Option<&HiddenTradeCards>,
+            Has<IsHuman>,
+        ),
+        With<Player>,
+    >,
+) {
+    if changed_players.is_empty() && !game_info.is_changed() && !census_history.is_changed() {
+        return;
+    }
+    let Ok(dashboard_list) = dashboard_list.single() else {
+        return;
+    };
+
+    // Players without a place in the census order yet end up last
+    let players = player_query
+        .iter()
+        .sorted_by_key(|(entity, name, ..)| {
+            (
+                game_info
+                    .census_order
+                    .iter()
+                    .position(|p| p == entity)
+                    .unwrap_or(usize::MAX),
+                name.to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut ui_builder =
+        UIBuilder::start_from_entity(commands, dashboard_list, true, Some(ui_defaults.clone()));
+    let _b = ui_builder.with_children(|mut b| {
+        b = b
+            .add_default_text_child(format!("Round {}", game_info.round))
+            .parent();
+        for (
+            turn,
+            (
+                player,
+                name,
+                census,
+                token_stock,
+                treasury,
+                city_stock,
+                cities,
+                trade_cards,
+                hidden_trade_cards,
+                is_human,
+            ),
+        ) in players.iter().enumerate()
+        {
+            let title = if *is_human {
+                format!("{}. {} (you)", turn + 1, name)
+            } else {
+                format!("{}. {}", turn + 1, name)
+            };
+            b = b
+                .child()
+                .as_flex_col(Val::Percent(100.), Val::Auto)
+                .add_default_text_child(title)
+                .add_default_text_child(match census_history.population_change(*player) {
+                    Some(change) => format!("Census: {} ({:+})", census.population, change),
+                    None => format!("Census: {}", census.population),
+                })
+                .add_default_text_child(format!("Stock: {}", token_stock.tokens_in_stock()))
+                .add_default_text_child(format!("Treasury: {}", treasury.tokens_in_treasury()))
+                .add_default_text_child(format!(
+                    "Cities: {} ({} left)",
+                    cities.number_of_cities(),
+                    city_stock.tokens_in_stock()
+                ))
+                .add_default_text_child(format!(
+                    "Trade cards: {}",
+                    hidden_trade_cards
+                        .map_or(trade_cards.number_of_trade_cards(), |hidden| hidden.0)
+                ))
+                .parent();
+        }
+    });
+    let _ = ui_builder.build();
+}
@@ -1,4 +1,4 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::Populations;
 use crate::civilization::components::*;
 use crate::civilization::concepts::census::prelude::GameInfoAndStuff;
 use crate::civilization::concepts::population_expansion::population_expansion_components::{
@@ -8,16 +8,19 @@ use crate::civilization::concepts::population_expansion::population_expansion_ev
     CheckGate, CheckPlayerExpansionEligibility, ExpandPopulationManuallyCommand,
 };
 use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::player::Player;
 use crate::GameActivity;
 use bevy::prelude::{Commands, Entity, MessageReader, MessageWriter, NextState, Query, ResMut, With};
 
 pub fn check_area_population_expansion_eligibility(
     mut expansion_check_event: MessageReader<CheckPlayerExpansionEligibility>,
-    stock_query: Query<(&TokenStock, &PlayerAreas, &NeedsExpansion)>,
+    stock_query: Query<(&TokenStock, &NeedsExpansion)>,
+    populations: Populations,
     mut commands: Commands,
 ) {
     for event in expansion_check_event.read() {
-        if let Ok((stock, player_areas, needs_expansion)) = stock_query.get(event.player) {
+        if let Ok((stock, needs_expansion)) = stock_query.get(event.player) {
+            let player_areas = populations.player_areas(event.player);
             if needs_expansion.areas_that_need_expansion.is_empty() || stock.is_empty() {
                 commands.entity(event.player).remove::<NeedsExpansion>();
                 commands.entity(event.player).remove::<ExpandManually>();
@@ -36,15 +39,15 @@ pub fn check_area_population_expansion_eligibility(
 }
 
 pub fn enter_population_expansion(
-    player_query: Query<(Entity, &PlayerAreas)>,
-    area: Query<(Entity, &Population)>,
+    player_query: Query<Entity, With<Player>>,
+    populations: Populations,
     mut game_info: ResMut<GameInfoAndStuff>,
     mut commands: Commands,
     mut checker: MessageWriter<CheckPlayerExpansionEligibility>,
 ) {
     game_info.round += 1;
     //debug!("Entering population expansion round {}", game_info.round);
-    for (area_entity, pop) in area.iter() {
+    for (area_entity, pop) in populations.iter() {
         if pop.has_population() {
             commands
                 .entity(area_entity)
@@ -52,22 +55,24 @@ pub fn enter_population_expansion(
         }
     }
 
-    for (player, player_areas) in player_query.iter() {
+    for player in player_query.iter() {
         commands
             .entity(player)
-            .insert(NeedsExpansion::new(player_areas.areas()));
+            .insert(NeedsExpansion::new(populations.player_areas(player).areas()));
         checker.write(CheckPlayerExpansionEligibility::new(player));
     }
 }
 
 pub fn auto_expand_population(
     mut area_query: Query<&mut AreaIsExpanding>,
-    mut player_query: Query<(Entity, &PlayerAreas, &mut NeedsExpansion), With<ExpandAutomatically>>,
+    mut player_query: Query<(Entity, &mut NeedsExpansion), With<ExpandAutomatically>>,
+    populations: Populations,
     mut event_writer: MessageWriter<MoveTokensFromStockToAreaCommand>,
     mut commands: Commands,
     mut checker: MessageWriter<CheckPlayerExpansionEligibility>,
 ) {
-    for (player_entity, player_areas, mut needs_expansion) in player_query.iter_mut() {
+    for (player_entity, mut needs_expansion) in player_query.iter_mut() {
+        let player_areas = populations.player_areas(player_entity);
         for area in needs_expansion.areas_that_need_expansion.iter() {
             let needed_tokens = player_areas.required_tokens_for_expansion_for_area(*area);
             if needed_tokens > 0 {
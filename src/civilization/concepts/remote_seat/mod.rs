@@ -0,0 +1,5 @@
+pub mod remote_seat_components;
+pub mod remote_seat_functions;
+pub mod remote_seat_plugin;
+pub mod remote_seat_resources;
+pub mod remote_seat_systems;
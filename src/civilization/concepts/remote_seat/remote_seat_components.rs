@@ -0,0 +1,31 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use bevy::prelude::Component;
+
+#[derive(Component, Default)]
+pub struct RemoteSeatPanel;
+
+#[derive(Component, Default)]
+pub struct RemoteSeatMoveList;
+
+/// How many trade cards a player holds on the server. The server only shows a seat its own
+/// cards, everyone else's are just counted.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HiddenTradeCards(pub usize);
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub enum RemoteSeatButton {
+    /// Tokens left out play the move with as many as it takes
+    PlayMove {
+        id: usize,
+        tokens: Option<usize>,
+    },
+    DraftOffer(usize),
+    Give(TradeCard),
+    Ask(TradeCard),
+    PublishOffer,
+    Settle(usize),
+    Pay(TradeCard),
+    TakeBack(TradeCard),
+    HandOverCards,
+    CancelDraft,
+}
@@ -0,0 +1,292 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::{
+    TradeCard, TradeCardTrait,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_functions::describe_cards;
+use crate::civilization::concepts::remote_seat::remote_seat_components::RemoteSeatButton;
+use crate::civilization::concepts::remote_seat::remote_seat_resources::{RemoteDraft, ServerLink};
+use crate::civilization::enums::GameFaction;
+use crate::network::network_components::{
+    ActionView, ClientMessage, GameView, MoveView, PROTOCOL_VERSION,
+};
+use crate::network::network_functions::describe_action;
+use bevy::platform::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::sync::{mpsc, Mutex};
+
+/// Connects to the server and says hello, the lines the server sends are read on a thread of
+/// their own.
+pub fn connect_to_server(address: &str, name: &str) -> Result<ServerLink, String> {
+    let stream = TcpStream::connect(address)
+        .map_err(|err| format!("could not connect to {address}: {err}"))?;
+    let reader = BufReader::new(stream.try_clone().map_err(|err| err.to_string())?);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let mut link = ServerLink {
+        stream,
+        messages: Mutex::new(receiver),
+    };
+    link.send(&ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+        name: name.to_string(),
+    })
+    .map_err(|err| format!("could not say hello to {address}: {err}"))?;
+    Ok(link)
+}
+
+/// Factions are sent by the name they have in the game.
+pub fn parse_faction(name: &str) -> Result<GameFaction, String> {
+    ron::from_str(name).map_err(|_| format!("unknown faction {name}"))
+}
+
+/// The move as a line on a button, starting with a capital.
+fn label(action: &ActionView, view: Option<&GameView>) -> String {
+    let description = describe_action(action, view);
+    let mut chars = description.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// The buttons for the moves the server asked for, in move order. Moves with tokens can be
+/// played with all of them or just one, trade offers and settlements pick their cards first.
+pub fn move_buttons(
+    moves: &[MoveView],
+    view: Option<&GameView>,
+) -> Vec<(String, RemoteSeatButton)> {
+    let mut buttons = Vec::new();
+    for game_move in moves {
+        let label = label(&game_move.action, view);
+        match &game_move.action {
+            ActionView::Expand { max_tokens, .. }
+            | ActionView::Move { max_tokens, .. }
+            | ActionView::AttackArea { max_tokens, .. }
+            | ActionView::AttackCity { max_tokens, .. }
+                if *max_tokens > 1 =>
+            {
+                buttons.push((
+                    format!("{} (all {})", label, max_tokens),
+                    RemoteSeatButton::PlayMove {
+                        id: game_move.id,
+                        tokens: Some(*max_tokens),
+                    },
+                ));
+                buttons.push((
+                    format!("{} (1)", label),
+                    RemoteSeatButton::PlayMove {
+                        id: game_move.id,
+                        tokens: Some(1),
+                    },
+                ));
+            }
+            ActionView::ProposeTrade { .. } => {
+                buttons.push((label, RemoteSeatButton::DraftOffer(game_move.id)));
+            }
+            ActionView::SettleTrade { .. } => {
+                buttons.push((label, RemoteSeatButton::Settle(game_move.id)));
+            }
+            _ => buttons.push((
+                label,
+                RemoteSeatButton::PlayMove {
+                    id: game_move.id,
+                    tokens: None,
+                },
+            )),
+        }
+    }
+    buttons
+}
+
+/// What has been picked for the trade move so far.
+pub fn draft_lines(draft: &RemoteDraft) -> Vec<String> {
+    match draft {
+        RemoteDraft::Offer { gives, asks, .. } => vec![
+            format!("You give: {}", describe_cards(gives)),
+            format!("You ask for: {}", describe_cards(asks)),
+            "The first two cards of each, by name, are promised".to_string(),
+        ],
+        RemoteDraft::Settlement { cards, .. } => vec![format!(
+            "You hand over: {} ({} of {})",
+            describe_cards(draft.gives()),
+            draft.number_of_cards(),
+            cards
+        )],
+    }
+}
+
+/// The buttons for picking the cards of a trade move. Offers give commodities from the hand
+/// and may ask for any commodity, settlements pay with any tradeable card.
+pub fn draft_buttons(
+    draft: &RemoteDraft,
+    hand: &HashMap<TradeCard, usize>,
+) -> Vec<(String, RemoteSeatButton)> {
+    let mut buttons = Vec::new();
+    let in_hand = |card: &TradeCard| hand.get(card).copied().unwrap_or_default();
+    let picked = |card: &TradeCard| draft.gives().get(card).copied().unwrap_or_default();
+    let mut cards = hand.keys().copied().collect::<Vec<_>>();
+    cards.sort_by_key(|card| (card.value(), card.to_string()));
+    match draft {
+        RemoteDraft::Offer { .. } => {
+            for card in cards.iter().filter(|card| card.is_commodity()) {
+                if in_hand(card) > picked(card) {
+                    buttons.push((format!("Give {}", card), RemoteSeatButton::Give(*card)));
+                }
+            }
+            let mut commodities = TradeCard::iter()
+                .filter(|card| card.is_commodity())
+                .collect::<Vec<_>>();
+            commodities.sort_by_key(|card| (card.value(), card.to_string()));
+            for card in commodities {
+                buttons.push((format!("Ask for {}", card), RemoteSeatButton::Ask(card)));
+            }
+            if draft.is_complete() {
+                buttons.push(("Make the offer".to_string(), RemoteSeatButton::PublishOffer));
+            }
+        }
+        RemoteDraft::Settlement { promised, .. } => {
+            for card in cards.iter().filter(|card| card.is_tradeable()) {
+                if !draft.is_complete() && in_hand(card) > picked(card) {
+                    buttons.push((format!("Pay {}", card), RemoteSeatButton::Pay(*card)));
+                }
+            }
+            for card in cards.iter() {
+                if picked(card) > promised.get(card).copied().unwrap_or_default() {
+                    buttons.push((
+                        format!("Take back {}", card),
+                        RemoteSeatButton::TakeBack(*card),
+                    ));
+                }
+            }
+            if draft.is_complete() {
+                buttons.push((
+                    "Hand over the cards".to_string(),
+                    RemoteSeatButton::HandOverCards,
+                ));
+            }
+        }
+    }
+    buttons.push(("Cancel".to_string(), RemoteSeatButton::CancelDraft));
+    buttons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn move_view(id: usize, action: ActionView) -> MoveView {
+        MoveView { id, action }
+    }
+
+    #[test]
+    fn test_moves_with_tokens_get_two_buttons() {
+        let moves = vec![
+            move_view(
+                0,
+                ActionView::Move {
+                    source: 1,
+                    target: 2,
+                    max_tokens: 3,
+                },
+            ),
+            move_view(1, ActionView::EndMovement),
+        ];
+
+        let buttons = move_buttons(&moves, None);
+
+        assert_eq!(buttons.len(), 3);
+        assert_eq!(
+            buttons[0].1,
+            RemoteSeatButton::PlayMove {
+                id: 0,
+                tokens: Some(3)
+            }
+        );
+        assert_eq!(
+            buttons[1].1,
+            RemoteSeatButton::PlayMove {
+                id: 0,
+                tokens: Some(1)
+            }
+        );
+        assert_eq!(buttons[2].0, "End movement");
+    }
+
+    #[test]
+    fn test_trade_moves_pick_their_cards_first() {
+        let moves = vec![
+            move_view(0, ActionView::ProposeTrade { receiver: 4 }),
+            move_view(
+                1,
+                ActionView::SettleTrade {
+                    offer: 9,
+                    cards: 3,
+                    promised: BTreeMap::new(),
+                },
+            ),
+        ];
+
+        let buttons = move_buttons(&moves, None);
+
+        assert_eq!(buttons[0].1, RemoteSeatButton::DraftOffer(0));
+        assert_eq!(buttons[1].1, RemoteSeatButton::Settle(1));
+    }
+
+    #[test]
+    fn test_offer_needs_three_cards_each_way() {
+        let mut draft = RemoteDraft::offer(2);
+        let hand = HashMap::from([(TradeCard::Ochre, 3)]);
+        for _ in 0..3 {
+            draft.give(TradeCard::Ochre);
+            draft.ask(TradeCard::Salt);
+        }
+
+        let buttons = draft_buttons(&draft, &hand);
+
+        assert!(!buttons
+            .iter()
+            .any(|(_, button)| *button == RemoteSeatButton::Give(TradeCard::Ochre)));
+        assert!(buttons
+            .iter()
+            .any(|(_, button)| *button == RemoteSeatButton::PublishOffer));
+        let choice = draft.choice();
+        assert_eq!(choice.move_id, 2);
+        assert_eq!(choice.gives.get("Ochre"), Some(&3));
+        assert_eq!(choice.asks.get("Salt"), Some(&3));
+    }
+
+    #[test]
+    fn test_settlement_keeps_the_promised_cards() {
+        let mut draft = RemoteDraft::settlement(1, 3, HashMap::from([(TradeCard::Ochre, 2)]));
+        let hand = HashMap::from([(TradeCard::Ochre, 2), (TradeCard::Salt, 2)]);
+
+        draft.take_back(TradeCard::Ochre);
+        draft.give(TradeCard::Salt);
+
+        assert!(draft.is_complete());
+        let buttons = draft_buttons(&draft, &hand);
+        assert!(!buttons
+            .iter()
+            .any(|(_, button)| matches!(button, RemoteSeatButton::Pay(_))));
+        assert!(buttons
+            .iter()
+            .any(|(_, button)| *button == RemoteSeatButton::TakeBack(TradeCard::Salt)));
+        assert_eq!(draft.choice().gives.get("Ochre"), Some(&2));
+    }
+
+    #[test]
+    fn test_factions_are_read_by_name() {
+        assert_eq!(parse_faction("Thrace"), Ok(GameFaction::Thrace));
+        assert!(parse_faction("Atlantis").is_err());
+    }
+}
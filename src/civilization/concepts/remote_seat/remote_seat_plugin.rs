@@ -0,0 +1,40 @@
+use crate::civilization::concepts::remote_seat::remote_seat_resources::{
+    RemoteBoard, RemotePlayers, RemoteSeat, RemoteSeatSetup, ServerLink,
+};
+use crate::civilization::concepts::remote_seat::remote_seat_systems::*;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Startup, Update};
+use bevy::prelude::{in_state, resource_changed, resource_exists, IntoScheduleConfigs, OnEnter};
+
+/// Plays one seat of a game on a server: the board it sends is laid out on the map and the
+/// moves it asks for show up as buttons.
+pub struct RemoteSeatPlugin;
+
+impl Plugin for RemoteSeatPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<RemoteSeatSetup>() {
+            app.insert_resource(RemoteSeatSetup::from_args(std::env::args().skip(1)));
+        }
+        app.init_resource::<RemoteSeat>()
+            .init_resource::<RemoteBoard>()
+            .init_resource::<RemotePlayers>()
+            .add_systems(Startup, join_server)
+            .add_systems(
+                OnEnter(GameActivity::StartGame),
+                setup_remote_seat_panel.run_if(resource_exists::<ServerLink>),
+            )
+            .add_systems(
+                Update,
+                (
+                    receive_server_messages.run_if(resource_exists::<ServerLink>),
+                    (mirror_remote_players, mirror_remote_board)
+                        .chain()
+                        .run_if(resource_changed::<RemoteBoard>),
+                    remote_seat_button_action,
+                    refresh_remote_seat_panel,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
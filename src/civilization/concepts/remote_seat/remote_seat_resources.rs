@@ -0,0 +1,233 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::network::network_components::{
+    ClientMessage, GameView, MoveChoice, MoveView, SeatScore,
+};
+use crate::network::network_functions::card_names;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{Entity, Resource};
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+
+/// The least number of cards on each side of an offer.
+pub const MIN_OFFER_CARDS: usize = 3;
+
+/// The server to play a seat on instead of playing the game on this device. Asked for with
+/// `--connect <host:port>` on the command line, `--name <name>` is the name to join with.
+#[derive(Resource, Debug, Clone)]
+pub struct RemoteSeatSetup {
+    pub address: Option<String>,
+    pub name: String,
+}
+
+impl RemoteSeatSetup {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut setup = RemoteSeatSetup::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--connect" => setup.address = args.next(),
+                "--name" => setup.name = args.next().unwrap_or(setup.name),
+                _ => {}
+            }
+        }
+        setup
+    }
+}
+
+impl Default for RemoteSeatSetup {
+    fn default() -> Self {
+        RemoteSeatSetup {
+            address: None,
+            name: "player".to_string(),
+        }
+    }
+}
+
+/// The connection to the server. A reader thread hands over the lines it sends.
+#[derive(Resource)]
+pub struct ServerLink {
+    pub stream: TcpStream,
+    pub messages: Mutex<Receiver<String>>,
+}
+
+impl ServerLink {
+    pub fn send(&mut self, message: &ClientMessage) -> std::io::Result<()> {
+        let line = serde_json::to_string(message)?;
+        writeln!(self.stream, "{line}")?;
+        self.stream.flush()
+    }
+
+    /// The next line the server sent, if any. Err once the server has hung up.
+    pub fn next_message(&self) -> Result<Option<String>, String> {
+        let messages = self.messages.lock().map_err(|err| err.to_string())?;
+        match messages.try_recv() {
+            Ok(line) => Ok(Some(line)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err("the server hung up".to_string()),
+        }
+    }
+}
+
+/// The board as the server last sent it.
+#[derive(Resource, Debug, Default)]
+pub struct RemoteBoard {
+    pub round: usize,
+    pub phase: String,
+    pub view: Option<GameView>,
+}
+
+/// The players on the server and the players standing in for them on this device.
+#[derive(Resource, Debug, Default)]
+pub struct RemotePlayers {
+    pub players: HashMap<u64, Entity>,
+}
+
+/// The seat this device plays: what the server asked it to do and the move being put
+/// together.
+#[derive(Resource, Debug, Default)]
+pub struct RemoteSeat {
+    /// The seat's player on the server
+    pub player: Option<u64>,
+    pub request: Option<RemoteRequest>,
+    pub draft: Option<RemoteDraft>,
+    /// The last thing the server had to say, like what was wrong with a move
+    pub notice: Option<String>,
+    pub scores: Vec<SeatScore>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteRequest {
+    pub request_id: u64,
+    pub moves: Vec<MoveView>,
+}
+
+/// The cards picked for a trade move before it is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteDraft {
+    Offer {
+        move_id: usize,
+        gives: HashMap<TradeCard, usize>,
+        asks: HashMap<TradeCard, usize>,
+    },
+    /// The promised cards are in from the start, the player picks the rest
+    Settlement {
+        move_id: usize,
+        cards: usize,
+        promised: HashMap<TradeCard, usize>,
+        gives: HashMap<TradeCard, usize>,
+    },
+}
+
+impl RemoteDraft {
+    pub fn offer(move_id: usize) -> Self {
+        RemoteDraft::Offer {
+            move_id,
+            gives: HashMap::default(),
+            asks: HashMap::default(),
+        }
+    }
+
+    pub fn settlement(move_id: usize, cards: usize, promised: HashMap<TradeCard, usize>) -> Self {
+        RemoteDraft::Settlement {
+            move_id,
+            cards,
+            gives: promised.clone(),
+            promised,
+        }
+    }
+
+    pub fn gives(&self) -> &HashMap<TradeCard, usize> {
+        match self {
+            RemoteDraft::Offer { gives, .. } | RemoteDraft::Settlement { gives, .. } => gives,
+        }
+    }
+
+    pub fn number_of_cards(&self) -> usize {
+        self.gives().values().sum()
+    }
+
+    pub fn give(&mut self, card: TradeCard) {
+        match self {
+            RemoteDraft::Offer { gives, .. } | RemoteDraft::Settlement { gives, .. } => {
+                *gives.entry(card).or_default() += 1;
+            }
+        }
+    }
+
+    pub fn ask(&mut self, card: TradeCard) {
+        if let RemoteDraft::Offer { asks, .. } = self {
+            *asks.entry(card).or_default() += 1;
+        }
+    }
+
+    /// Puts a picked card back in the hand, promised cards stay in.
+    pub fn take_back(&mut self, card: TradeCard) {
+        if let RemoteDraft::Settlement {
+            promised, gives, ..
+        } = self
+        {
+            if gives.get(&card).copied().unwrap_or_default()
+                > promised.get(&card).copied().unwrap_or_default()
+            {
+                if let Some(count) = gives.get_mut(&card) {
+                    *count -= 1;
+                }
+                gives.retain(|_, count| *count > 0);
+            }
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        match self {
+            RemoteDraft::Offer { gives, asks, .. } => {
+                gives.values().sum::<usize>() >= MIN_OFFER_CARDS
+                    && asks.values().sum::<usize>() >= MIN_OFFER_CARDS
+            }
+            RemoteDraft::Settlement { cards, .. } => self.number_of_cards() == *cards,
+        }
+    }
+
+    pub fn choice(&self) -> MoveChoice {
+        match self {
+            RemoteDraft::Offer {
+                move_id,
+                gives,
+                asks,
+            } => MoveChoice {
+                gives: card_names(gives),
+                asks: card_names(asks),
+                ..MoveChoice::new(*move_id)
+            },
+            RemoteDraft::Settlement { move_id, gives, .. } => MoveChoice {
+                gives: card_names(gives),
+                ..MoveChoice::new(*move_id)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_game_is_played_here_unless_a_server_is_named() {
+        let setup = RemoteSeatSetup::from_args(args("--scenario late_game"));
+
+        assert_eq!(setup.address, None);
+    }
+
+    #[test]
+    fn test_server_and_name_are_read_from_the_arguments() {
+        let setup = RemoteSeatSetup::from_args(args("--connect 127.0.0.1:7878 --name Ada"));
+
+        assert_eq!(setup.address.as_deref(), Some("127.0.0.1:7878"));
+        assert_eq!(setup.name, "Ada");
+    }
+}
@@ -0,0 +1,532 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::{
+    BuiltCity, CityTokenStock, Faction, FixTokenPositions, GameArea, InArea, PlayerCities,
+    ReturnTokenToStock, TokenStock,
+};
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::census::census_components::Census;
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::city_construction::city_construction_events::BuildCityCommand;
+use crate::civilization::concepts::map::map_plugin::AvailableFactions;
+use crate::civilization::concepts::remote_seat::remote_seat_components::{
+    HiddenTradeCards, RemoteSeatButton, RemoteSeatMoveList, RemoteSeatPanel,
+};
+use crate::civilization::concepts::remote_seat::remote_seat_functions::{
+    connect_to_server, draft_buttons, draft_lines, move_buttons, parse_faction,
+};
+use crate::civilization::concepts::remote_seat::remote_seat_resources::{
+    RemoteBoard, RemoteDraft, RemotePlayers, RemoteRequest, RemoteSeat, RemoteSeatSetup, ServerLink,
+};
+use crate::civilization::functions::{build_city_in_area, reduce_city, token_on_board};
+use crate::civilization::general_systems::spawn_player;
+use crate::civilization::ui::ui_builder::{ButtonPartial, UIBuilder, UiBuilderDefaults, BG_COLOR};
+use crate::network::network_components::{
+    ActionView, ClientMessage, GameView, MoveChoice, ServerMessage,
+};
+use crate::network::network_functions::parse_cards;
+use crate::stupid_ai::prelude::IsHuman;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{
+    default, warn, Changed, Commands, DetectChanges, Entity, FlexWrap, Interaction, PositionType,
+    Query, Res, ResMut, Transform, Val, With,
+};
+
+/// The game is played on this device unless a server was asked for.
+pub fn playing_locally(setup: Res<RemoteSeatSetup>) -> bool {
+    setup.address.is_none()
+}
+
+pub fn join_server(mut commands: Commands, mut setup: ResMut<RemoteSeatSetup>) {
+    let Some(address) = setup.address.clone() else {
+        return;
+    };
+    match connect_to_server(&address, &setup.name) {
+        Ok(link) => commands.insert_resource(link),
+        Err(err) => {
+            warn!("{}, playing on this device instead", err);
+            setup.address = None;
+        }
+    }
+}
+
+/// Reads everything the server has sent since the last frame.
+pub fn receive_server_messages(
+    mut commands: Commands,
+    link: Res<ServerLink>,
+    mut board: ResMut<RemoteBoard>,
+    mut seat: ResMut<RemoteSeat>,
+) {
+    loop {
+        let line = match link.next_message() {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(err) => {
+                seat.notice = Some(err);
+                commands.remove_resource::<ServerLink>();
+                break;
+            }
+        };
+        let message = match serde_json::from_str::<ServerMessage>(&line) {
+            Ok(message) => message,
+            Err(err) => {
+                warn!("Could not read what the server sent: {}", err);
+                continue;
+            }
+        };
+        match message {
+            ServerMessage::Welcome {
+                seat: seat_number,
+                player,
+                faction,
+                ..
+            } => {
+                seat.player = Some(player);
+                seat.notice = Some(format!("You play {} in seat {}", faction, seat_number));
+            }
+            ServerMessage::State { round, phase, view } => {
+                board.round = round;
+                board.phase = phase;
+                board.view = Some(view);
+            }
+            ServerMessage::MoveRequest { request_id, moves } => {
+                seat.request = Some(RemoteRequest { request_id, moves });
+                seat.draft = None;
+            }
+            ServerMessage::Error { message, .. } => {
+                seat.notice = Some(message);
+            }
+            ServerMessage::GameOver { scores } => {
+                seat.request = None;
+                seat.draft = None;
+                seat.scores = scores;
+            }
+        }
+    }
+}
+
+/// Every player on the server gets a player on this device to stand in for it on the map and
+/// the dashboard. The one this device plays is the human.
+pub fn mirror_remote_players(
+    mut commands: Commands,
+    board: Res<RemoteBoard>,
+    seat: Res<RemoteSeat>,
+    mut remote_players: ResMut<RemotePlayers>,
+    mut available_factions: ResMut<AvailableFactions>,
+) {
+    let Some(view) = board.view.as_ref() else {
+        return;
+    };
+    for player_view in view.players.iter() {
+        if remote_players.players.contains_key(&player_view.id) {
+            continue;
+        }
+        let faction = match parse_faction(&player_view.faction) {
+            Ok(faction) => faction,
+            Err(err) => {
+                warn!("{}", err);
+                continue;
+            }
+        };
+        available_factions.remaining_factions.remove(&faction);
+        let n = remote_players.players.len() + 1;
+        let player = spawn_player(&mut commands, faction, n, 0);
+        if seat.player == Some(player_view.id) {
+            commands.entity(player).insert(IsHuman);
+        }
+        remote_players.players.insert(player_view.id, player);
+    }
+}
+
+/// What it takes to make the board on this device look like the one on the server.
+#[derive(SystemParam)]
+pub struct RemoteBoardMirror<'w, 's> {
+    commands: Commands<'w, 's>,
+    areas: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GameArea,
+            &'static Transform,
+            Option<&'static BuiltCity>,
+        ),
+    >,
+    populations: Populations<'w, 's>,
+    players: Query<
+        'w,
+        's,
+        (
+            &'static Faction,
+            &'static mut CityTokenStock,
+            &'static mut TokenStock,
+            &'static mut PlayerCities,
+            &'static mut Census,
+            &'static mut PlayerTradeCards,
+        ),
+    >,
+    game_factions: Res<'w, AvailableFactions>,
+    game_info: ResMut<'w, GameInfoAndStuff>,
+    remote_players: Res<'w, RemotePlayers>,
+}
+
+impl RemoteBoardMirror<'_, '_> {
+    fn local_player(&self, remote: u64) -> Option<Entity> {
+        self.remote_players.players.get(&remote).copied()
+    }
+
+    pub fn mirror(&mut self, round: usize, view: &GameView) {
+        if self.game_info.round != round {
+            self.game_info.round = round;
+        }
+        let areas = self
+            .areas
+            .iter()
+            .map(|(entity, area, transform, built_city)| {
+                (
+                    area.id,
+                    (
+                        entity,
+                        *transform,
+                        built_city.map(|built| BuiltCity::new(built.city, built.player)),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let mut census = HashMap::<Entity, usize>::default();
+        for area_view in view.areas.iter() {
+            let Some((area, transform, built_city)) = areas.get(&area_view.map_id) else {
+                warn!("Area {} is not on the map", area_view.map_id);
+                continue;
+            };
+            let mut wanted = self
+                .remote_players
+                .players
+                .values()
+                .map(|player| (*player, 0))
+                .collect::<HashMap<_, _>>();
+            for player_tokens in area_view.population.iter() {
+                if let Some(player) = self.local_player(player_tokens.player) {
+                    wanted.insert(player, player_tokens.tokens);
+                    *census.entry(player).or_default() += player_tokens.tokens;
+                }
+            }
+            for (player, tokens) in wanted {
+                self.set_tokens(*area, transform, player, tokens);
+            }
+            let owner = area_view
+                .city_owner
+                .and_then(|owner| self.local_player(owner));
+            self.set_city(*area, transform, built_city.as_ref(), owner);
+        }
+        for (remote, player) in self.remote_players.players.iter() {
+            let Ok((_, _, _, _, mut player_census, mut trade_cards)) =
+                self.players.get_mut(*player)
+            else {
+                continue;
+            };
+            let population = census.get(player).copied().unwrap_or_default();
+            if player_census.population != population {
+                player_census.population = population;
+            }
+            let Some(player_view) = view.players.iter().find(|p| p.id == *remote) else {
+                continue;
+            };
+            match player_view.hand.as_ref().map(parse_cards) {
+                Some(Ok(hand)) => {
+                    if *trade_cards.cards() != hand {
+                        let mut mirrored = PlayerTradeCards::default();
+                        for (card, count) in hand {
+                            mirrored.add_trade_cards(card, count);
+                        }
+                        *trade_cards = mirrored;
+                    }
+                }
+                Some(Err(err)) => warn!("{}", err),
+                None => {
+                    self.commands
+                        .entity(*player)
+                        .insert(HiddenTradeCards(player_view.trade_cards));
+                }
+            }
+        }
+    }
+
+    /// Puts tokens from stock on the area or sends the ones too many back to it.
+    fn set_tokens(&mut self, area: Entity, transform: &Transform, player: Entity, tokens: usize) {
+        let standing = self
+            .populations
+            .get(area)
+            .and_then(|population| population.tokens_for_player(&player).cloned())
+            .unwrap_or_default();
+        if standing.len() == tokens {
+            return;
+        }
+        if standing.len() > tokens {
+            for token in standing.iter().take(standing.len() - tokens) {
+                self.commands.entity(*token).insert(ReturnTokenToStock);
+            }
+        } else {
+            let Ok((faction, _, mut token_stock, ..)) = self.players.get_mut(player) else {
+                return;
+            };
+            let Some(texture) = self
+                .game_factions
+                .faction_icons
+                .get(&faction.faction)
+                .cloned()
+            else {
+                return;
+            };
+            let placed = token_stock
+                .remove_at_most_n_tokens_from_stock(tokens - standing.len())
+                .unwrap_or_default();
+            for token in placed {
+                self.commands
+                    .entity(token)
+                    .insert((InArea(area), token_on_board(texture.clone(), transform)));
+            }
+        }
+        self.commands.entity(area).insert(FixTokenPositions);
+    }
+
+    /// Takes down the city on the area if someone else owns it now and builds the owner's.
+    fn set_city(
+        &mut self,
+        area: Entity,
+        transform: &Transform,
+        built_city: Option<&BuiltCity>,
+        owner: Option<Entity>,
+    ) {
+        if built_city.map(|built| built.player) == owner {
+            return;
+        }
+        if let Some(built_city) = built_city {
+            if let Ok((faction, mut city_stock, mut token_stock, mut player_cities, ..)) =
+                self.players.get_mut(built_city.player)
+            {
+                let texture = self
+                    .game_factions
+                    .faction_icons
+                    .get(&faction.faction)
+                    .cloned()
+                    .unwrap_or_default();
+                reduce_city(
+                    &mut self.commands,
+                    (area, transform),
+                    built_city,
+                    0,
+                    texture,
+                    (&mut city_stock, &mut token_stock, &mut player_cities),
+                );
+            }
+        }
+        let Some(owner) = owner else {
+            return;
+        };
+        let Ok((faction, mut city_stock, _, mut player_cities, ..)) = self.players.get_mut(owner)
+        else {
+            return;
+        };
+        let Some(texture) = self
+            .game_factions
+            .faction_city_icons
+            .get(&faction.faction)
+            .cloned()
+        else {
+            return;
+        };
+        build_city_in_area(
+            &mut self.commands,
+            texture,
+            &BuildCityCommand::new(owner, area),
+            &mut city_stock,
+            &mut player_cities,
+            transform,
+        );
+    }
+}
+
+/// Lays the board the server sent out on the map.
+pub fn mirror_remote_board(board: Res<RemoteBoard>, mut mirror: RemoteBoardMirror) {
+    if let Some(view) = board.view.as_ref() {
+        mirror.mirror(board.round, view);
+    }
+}
+
+pub fn setup_remote_seat_panel(commands: Commands, ui_defaults: Res<UiBuilderDefaults>) {
+    let mut panel = UIBuilder::new(commands, Some(ui_defaults.clone()));
+
+    panel
+        .with_component::<RemoteSeatPanel>()
+        .as_flex_col(Val::Percent(50.), Val::Auto)
+        .with_position_type(PositionType::Absolute)
+        .with_left(Val::Percent(25.))
+        .with_bottom(Val::Px(80.))
+        .with_bg_color(BG_COLOR)
+        .child()
+        .as_flex_row()
+        .with_width(Val::Percent(100.))
+        .with_flex_wrap(FlexWrap::Wrap)
+        .with_component::<RemoteSeatMoveList>();
+
+    let (_root_entity, _commands) = panel.build();
+}
+
+/// The lines of text and the buttons for the seat.
+fn panel_for(
+    seat: &RemoteSeat,
+    board: &RemoteBoard,
+) -> (Vec<String>, Vec<(String, RemoteSeatButton)>) {
+    let mut lines = vec![format!("Round {}, {}", board.round, board.phase)];
+    let mut buttons = Vec::new();
+    if let Some(notice) = seat.notice.as_ref() {
+        lines.push(notice.clone());
+    }
+    if !seat.scores.is_empty() {
+        lines.push("The game is over".to_string());
+        for score in seat.scores.iter() {
+            lines.push(format!("{}: {}", score.name, score.score));
+        }
+        return (lines, buttons);
+    }
+    if let Some(draft) = seat.draft.as_ref() {
+        let hand = board
+            .view
+            .as_ref()
+            .and_then(|view| view.players.iter().find_map(|player| player.hand.as_ref()))
+            .and_then(|hand| parse_cards(hand).ok())
+            .unwrap_or_default();
+        lines.extend(draft_lines(draft));
+        buttons.extend(draft_buttons(draft, &hand));
+    } else if let Some(request) = seat.request.as_ref() {
+        buttons.extend(move_buttons(&request.moves, board.view.as_ref()));
+    } else {
+        lines.push("Waiting for the other players".to_string());
+    }
+    (lines, buttons)
+}
+
+/// Rebuilds the seat panel whenever the server has sent something or a card was picked.
+pub fn refresh_remote_seat_panel(
+    commands: Commands,
+    ui_defaults: Res<UiBuilderDefaults>,
+    seat: Res<RemoteSeat>,
+    board: Res<RemoteBoard>,
+    move_list: Query<Entity, With<RemoteSeatMoveList>>,
+) {
+    if !seat.is_changed() && !board.is_changed() {
+        return;
+    }
+    let Ok(move_list) = move_list.single() else {
+        return;
+    };
+    let (lines, buttons) = panel_for(&seat, &board);
+
+    let mut ui_builder =
+        UIBuilder::start_from_entity(commands, move_list, true, Some(ui_defaults.clone()));
+    let _b = ui_builder.with_children(|mut b| {
+        for line in lines {
+            b = b.add_default_text_child(line);
+        }
+        for (label, action) in buttons {
+            b = b.with_button(
+                Some(ButtonPartial {
+                    text: Some(label),
+                    ..default()
+                }),
+                action,
+            );
+        }
+    });
+    let _ = ui_builder.build();
+}
+
+/// Answers the open request with the move, the seat waits for the next one after that.
+fn play(seat: &mut RemoteSeat, link: Option<&mut ServerLink>, choice: MoveChoice) {
+    let (Some(request), Some(link)) = (seat.request.take(), link) else {
+        return;
+    };
+    seat.draft = None;
+    if let Err(err) = link.send(&ClientMessage::PlayMove {
+        request_id: request.request_id,
+        choice,
+    }) {
+        seat.notice = Some(format!("Could not send the move: {}", err));
+    }
+}
+
+/// Plays what the seat clicked on.
+pub fn remote_seat_button_action(
+    interactions: Query<(&Interaction, &RemoteSeatButton), Changed<Interaction>>,
+    mut seat: ResMut<RemoteSeat>,
+    mut link: Option<ResMut<ServerLink>>,
+) {
+    for (interaction, action) in interactions.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let link = link.as_deref_mut();
+        match action {
+            RemoteSeatButton::PlayMove { id, tokens } => {
+                let choice = MoveChoice {
+                    tokens: *tokens,
+                    ..MoveChoice::new(*id)
+                };
+                play(&mut seat, link, choice);
+            }
+            RemoteSeatButton::DraftOffer(id) => {
+                seat.draft = Some(RemoteDraft::offer(*id));
+            }
+            RemoteSeatButton::Settle(id) => {
+                let settle = seat.request.as_ref().and_then(|request| {
+                    request
+                        .moves
+                        .iter()
+                        .find(|game_move| game_move.id == *id)
+                        .and_then(|game_move| match &game_move.action {
+                            ActionView::SettleTrade {
+                                cards, promised, ..
+                            } => Some((*cards, parse_cards(promised))),
+                            _ => None,
+                        })
+                });
+                match settle {
+                    Some((cards, Ok(promised))) => {
+                        seat.draft = Some(RemoteDraft::settlement(*id, cards, promised));
+                    }
+                    Some((_, Err(err))) => seat.notice = Some(err),
+                    None => {}
+                }
+            }
+            RemoteSeatButton::Give(card) | RemoteSeatButton::Pay(card) => {
+                if let Some(draft) = seat.draft.as_mut() {
+                    draft.give(*card);
+                }
+            }
+            RemoteSeatButton::Ask(card) => {
+                if let Some(draft) = seat.draft.as_mut() {
+                    draft.ask(*card);
+                }
+            }
+            RemoteSeatButton::TakeBack(card) => {
+                if let Some(draft) = seat.draft.as_mut() {
+                    draft.take_back(*card);
+                }
+            }
+            RemoteSeatButton::PublishOffer | RemoteSeatButton::HandOverCards => {
+                if let Some(choice) = seat
+                    .draft
+                    .as_ref()
+                    .filter(|draft| draft.is_complete())
+                    .map(|draft| draft.choice())
+                {
+                    play(&mut seat, link, choice);
+                }
+            }
+            RemoteSeatButton::CancelDraft => {
+                seat.draft = None;
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::Populations;
 use crate::civilization::components::BuiltCity;
 use crate::civilization::components::ReturnTokenToStock;
 use crate::GameActivity;
@@ -6,12 +6,15 @@ use bevy::prelude::{Commands, Has, Name, NextState, Query, ResMut};
 
 pub fn remove_surplus_population(
     mut next_state: ResMut<NextState<GameActivity>>,
-    mut areas: Query<(&mut Population, Has<BuiltCity>)>,
+    areas: Query<Has<BuiltCity>>,
+    populations: Populations,
     _name_query: Query<&Name>,
     mut commands: Commands,
 ) {
-    for (mut area, has_city) in areas.iter_mut() {
+    for (area_entity, mut area) in populations.iter() {
+        let has_city = areas.get(area_entity).unwrap_or_default();
         if area.has_surplus(has_city) {
+            // The tokens leave the board through ReturnTokenToStock
             if has_city {
                 //debug!("Area has a city, so we remove all tokens");
                 for token in area.remove_all_tokens() {
@@ -0,0 +1,3 @@
+pub mod rules_plugin;
+pub mod rules_resources;
+pub mod rules_systems;
@@ -0,0 +1,31 @@
+use crate::civilization::concepts::rules::rules_resources::{
+    ConflictTies, GameRng, MovementOrder, RuleSet, RuleVariant, ShipRules,
+};
+use crate::civilization::concepts::rules::rules_systems::{apply_house_rules, load_house_rules};
+use bevy::app::{App, Plugin, Startup, Update};
+use bevy_common_assets::ron::RonAssetPlugin;
+
+pub struct RulesPlugin;
+
+impl Plugin for RulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RuleSet>()
+            .init_resource::<GameRng>()
+            .register_type::<RuleSet>()
+            .register_type::<RuleVariant>()
+            .register_type::<ConflictTies>()
+            .register_type::<MovementOrder>()
+            .register_type::<ShipRules>();
+    }
+}
+
+/// Reads the house rules from `definitions/house.rules.ron`, so a group can play by its own.
+pub struct HouseRulesPlugin;
+
+impl Plugin for HouseRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<RuleSet>::new(&["rules.ron"]))
+            .add_systems(Startup, load_house_rules)
+            .add_systems(Update, apply_house_rules);
+    }
+}
@@ -0,0 +1,225 @@
+use bevy::prelude::{Handle, Reflect, Resource};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// The rulebook a game is played by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, serde::Deserialize, serde::Serialize)]
+pub enum RuleVariant {
+    /// Avalon Hill's Civilization.
+    Civilization,
+    AdvancedCivilization,
+    /// The rules this game has been played by so far, where the books were guessed at.
+    HouseRules,
+}
+
+/// How players with the same number of tokens in a conflict lose them.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, serde::Deserialize, serde::Serialize,
+)]
+pub enum ConflictTies {
+    /// Everyone loses the same number of tokens, as many rounds as it takes to get down to
+    /// the population limit.
+    #[default]
+    EvenRounds,
+    /// Everyone loses one token in turn until the population limit is met, so some may lose one
+    /// token more than others.
+    Alternating,
+}
+
+/// Who moves first once the census is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, serde::Deserialize, serde::Serialize)]
+pub enum MovementOrder {
+    /// The smallest population moves first, the largest gets to see everyone else's moves.
+    SmallestFirst,
+    LargestFirst,
+}
+
+/// The rules the phases of the round play by. Every phase reads the numbers it needs from here
+/// instead of keeping its own. A rules file only has to name the rules it changes, the rest
+/// are the house rules.
+#[derive(
+    Resource,
+    Debug,
+    Clone,
+    PartialEq,
+    Reflect,
+    serde::Deserialize,
+    serde::Serialize,
+    bevy::asset::Asset,
+)]
+#[serde(default)]
+pub struct RuleSet {
+    pub variant: RuleVariant,
+    /// Tradeable cards a player has to hold to take part in trading.
+    pub min_cards_to_trade: usize,
+    /// The highest pile a player draws from, one pile per city up to this one. Without a limit
+    /// a player draws from one pile per city, whatever piles there are.
+    pub pile_draw_limit: Option<usize>,
+    /// Tokens on the board each city needs to be supported.
+    pub city_support: usize,
+    pub conflict_ties: ConflictTies,
+    pub movement_order: MovementOrder,
+    pub ships: ShipRules,
+}
+
+/// What ships cost and carry. There is no ship construction phase yet, it is to read these.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ShipRules {
+    /// Tokens a ship costs to build, paid from the treasury or the board.
+    pub cost: usize,
+    /// Tokens a ship costs every round to keep, ships not paid for are taken off the board.
+    pub upkeep: usize,
+    /// Ships a player may have on the board.
+    pub max_ships: usize,
+    /// Tokens a ship carries.
+    pub capacity: usize,
+    /// Sea areas a ship moves through in one movement phase.
+    pub moves: usize,
+}
+
+impl Default for ShipRules {
+    fn default() -> Self {
+        ShipRules {
+            cost: 2,
+            upkeep: 0,
+            max_ships: 4,
+            capacity: 5,
+            moves: 4,
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn new(variant: RuleVariant) -> Self {
+        match variant {
+            // Both books deal the trade cards out in nine stacks and have two tokens support
+            // each city
+            RuleVariant::Civilization => RuleSet {
+                variant,
+                min_cards_to_trade: 2,
+                pile_draw_limit: Some(9),
+                city_support: 2,
+                conflict_ties: ConflictTies::EvenRounds,
+                movement_order: MovementOrder::SmallestFirst,
+                ships: ShipRules::default(),
+            },
+            RuleVariant::AdvancedCivilization => RuleSet {
+                variant,
+                min_cards_to_trade: 3,
+                pile_draw_limit: Some(9),
+                city_support: 2,
+                conflict_ties: ConflictTies::Alternating,
+                movement_order: MovementOrder::SmallestFirst,
+                // Ships that are kept have to be paid for every round
+                ships: ShipRules {
+                    upkeep: 1,
+                    ..ShipRules::default()
+                },
+            },
+            RuleVariant::HouseRules => RuleSet {
+                variant,
+                min_cards_to_trade: 5,
+                pile_draw_limit: None,
+                city_support: 2,
+                conflict_ties: ConflictTies::EvenRounds,
+                movement_order: MovementOrder::LargestFirst,
+                ships: ShipRules::default(),
+            },
+        }
+    }
+
+    /// Rules read from a rules file, they are house rules whatever variant the file names.
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        let rule_set: RuleSet = ron::from_str(text).map_err(|err| err.to_string())?;
+        Ok(RuleSet {
+            variant: RuleVariant::HouseRules,
+            ..rule_set
+        })
+    }
+
+    /// The piles a player with this many cities draws a card from.
+    pub fn piles_to_draw(&self, number_of_cities: usize) -> std::ops::RangeInclusive<usize> {
+        1..=self
+            .pile_draw_limit
+            .map_or(number_of_cities, |limit| number_of_cities.min(limit))
+    }
+
+    /// Tokens a player needs on the board to support this many cities.
+    pub fn required_population(&self, number_of_cities: usize) -> usize {
+        number_of_cities * self.city_support
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::new(RuleVariant::HouseRules)
+    }
+}
+
+/// The house rules file, it takes the place of the house rules preset once it has loaded.
+#[derive(Resource)]
+pub struct HouseRulesHandle(pub Handle<RuleSet>);
+
+/// The dice of the game. Everything left to chance draws from here, the AIs' picks, conflicts,
+/// pillage and the trade cards, so a game started from the same seed plays out the same.
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(rand::random())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_file_only_needs_the_rules_it_changes() {
+        let rule_set = RuleSet::from_ron("(city_support: 3, ships: (capacity: 6))").unwrap();
+
+        assert_eq!(rule_set.city_support, 3);
+        assert_eq!(rule_set.ships.capacity, 6);
+        assert_eq!(rule_set.ships.cost, ShipRules::default().cost);
+        assert_eq!(
+            rule_set.min_cards_to_trade,
+            RuleSet::new(RuleVariant::HouseRules).min_cards_to_trade
+        );
+    }
+
+    #[test]
+    fn test_rules_file_is_always_house_rules() {
+        let rule_set =
+            RuleSet::from_ron("(variant: Civilization, movement_order: SmallestFirst)").unwrap();
+
+        assert_eq!(rule_set.variant, RuleVariant::HouseRules);
+        assert_eq!(rule_set.movement_order, MovementOrder::SmallestFirst);
+    }
+
+    #[test]
+    fn test_rules_file_reports_mistakes() {
+        assert!(RuleSet::from_ron("(city_support: \"two\")").is_err());
+    }
+
+    #[test]
+    fn test_shipped_house_rules_match_the_preset() {
+        let text = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/definitions/house.rules.ron"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            RuleSet::from_ron(&text),
+            Ok(RuleSet::new(RuleVariant::HouseRules))
+        );
+    }
+}
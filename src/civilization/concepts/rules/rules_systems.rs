@@ -0,0 +1,50 @@
+use crate::civilization::concepts::rules::rules_resources::{
+    HouseRulesHandle, RuleSet, RuleVariant,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{info, AssetEvent, AssetServer, Assets, Commands, MessageReader, Res, ResMut};
+
+pub fn load_house_rules(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(HouseRulesHandle(
+        asset_server.load("definitions/house.rules.ron"),
+    ));
+}
+
+/// The house rules out of the rules file once it has loaded, the preset until then.
+#[derive(SystemParam)]
+pub struct LoadedHouseRules<'w> {
+    handle: Option<Res<'w, HouseRulesHandle>>,
+    rule_sets: Res<'w, Assets<RuleSet>>,
+}
+
+impl LoadedHouseRules<'_> {
+    pub fn rule_set(&self, variant: RuleVariant) -> RuleSet {
+        if variant != RuleVariant::HouseRules {
+            return RuleSet::new(variant);
+        }
+        self.handle
+            .as_ref()
+            .and_then(|handle| self.rule_sets.get(handle.0.id()))
+            .map_or_else(|| RuleSet::new(variant), |rule_set| rule_set.clone())
+    }
+}
+
+/// Games played by the house rules pick up the rules file when it loads or is edited.
+pub fn apply_house_rules(
+    mut asset_events: MessageReader<AssetEvent<RuleSet>>,
+    house_rules: LoadedHouseRules,
+    mut rule_set: ResMut<RuleSet>,
+) {
+    let Some(handle) = house_rules.handle.as_ref() else {
+        return;
+    };
+    let changed = asset_events.read().fold(false, |changed, event| {
+        changed
+            || event.is_loaded_with_dependencies(handle.0.id())
+            || event.is_modified(handle.0.id())
+    });
+    if changed && rule_set.variant == RuleVariant::HouseRules {
+        *rule_set = house_rules.rule_set(RuleVariant::HouseRules);
+        info!("Playing by the house rules file: {:?}", *rule_set);
+    }
+}
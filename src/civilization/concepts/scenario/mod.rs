@@ -0,0 +1,4 @@
+pub mod scenario_functions;
+pub mod scenario_plugin;
+pub mod scenario_resources;
+pub mod scenario_systems;
@@ -0,0 +1,190 @@
+use crate::civilization::concepts::scenario::scenario_resources::Scenario;
+use crate::GameActivity;
+use std::collections::HashSet;
+
+pub const TOKENS_PER_PLAYER: usize = 47;
+pub const CITIES_PER_PLAYER: usize = 9;
+pub const SCENARIO_EXTENSION: &str = ".scenario.ron";
+
+/// Everything that keeps a scenario from being set up, one line per problem.
+pub fn scenario_problems(scenario: &Scenario) -> Vec<String> {
+    let mut problems = Vec::new();
+    if scenario.players.is_empty() {
+        problems.push("The scenario has no players".to_string());
+    }
+    if scenario.activity == GameActivity::StartGame {
+        problems.push("The scenario can not start in StartGame".to_string());
+    }
+    let mut factions = HashSet::new();
+    let mut cities = HashSet::new();
+    for player in scenario.players.iter() {
+        if !factions.insert(player.faction) {
+            problems.push(format!("{} is in the scenario twice", player.faction));
+        }
+        let tokens = player.tokens_on_board() + player.treasury;
+        if tokens > TOKENS_PER_PLAYER {
+            problems.push(format!(
+                "{} needs {} tokens but only has {}",
+                player.faction, tokens, TOKENS_PER_PLAYER
+            ));
+        }
+        if player.cities.len() > CITIES_PER_PLAYER {
+            problems.push(format!(
+                "{} has {} cities but only {} city tokens",
+                player.faction,
+                player.cities.len(),
+                CITIES_PER_PLAYER
+            ));
+        }
+        for area in player.cities.iter() {
+            if !cities.insert(*area) {
+                problems.push(format!("Area {} has more than one city", area));
+            }
+        }
+    }
+    for player in scenario.players.iter() {
+        for placed in player.tokens.iter() {
+            if cities.contains(&placed.area) {
+                problems.push(format!(
+                    "{} has tokens in area {} which has a city",
+                    player.faction, placed.area
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// The round counter to start from. Population expansion counts the round up when it starts.
+pub fn starting_round(scenario: &Scenario) -> usize {
+    if scenario.activity == GameActivity::PopulationExpansion {
+        scenario.round.saturating_sub(1)
+    } else {
+        scenario.round
+    }
+}
+
+/// The scenario file following `--scenario` in the arguments. A bare name like `late_game` is
+/// looked for in the scenarios folder.
+pub fn scenario_from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    args.find(|arg| arg == "--scenario")?;
+    args.next().map(|name| {
+        if name.ends_with(SCENARIO_EXTENSION) {
+            name
+        } else {
+            format!("scenarios/{name}{SCENARIO_EXTENSION}")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+    use crate::civilization::enums::GameFaction;
+
+    fn parse(text: &str) -> Scenario {
+        ron::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_scenario_defaults_to_population_expansion_in_the_first_round() {
+        let scenario = parse("(players: [(faction: Egypt)])");
+
+        assert_eq!(scenario.round, 1);
+        assert_eq!(scenario.activity, GameActivity::PopulationExpansion);
+        assert_eq!(starting_round(&scenario), 0);
+        assert!(scenario_problems(&scenario).is_empty());
+    }
+
+    #[test]
+    fn test_scenario_reads_board_trade_cards_and_treasury() {
+        let scenario = parse(
+            "(
+                round: 6,
+                activity: CheckCitySupport,
+                players: [(
+                    faction: Crete,
+                    tokens: [(area: 109, tokens: 3)],
+                    cities: [108],
+                    trade_cards: {Ochre: 2, Salt: 1},
+                    treasury: 4,
+                )],
+            )",
+        );
+        let crete = scenario.player(GameFaction::Crete).unwrap();
+
+        assert_eq!(starting_round(&scenario), 6);
+        assert_eq!(crete.tokens_on_board(), 3);
+        assert_eq!(crete.cities, vec![108]);
+        assert_eq!(crete.trade_cards.get(&TradeCard::Ochre), Some(&2));
+        assert_eq!(crete.treasury, 4);
+        assert!(scenario_problems(&scenario).is_empty());
+    }
+
+    #[test]
+    fn test_scenario_problems_finds_overdrawn_stocks() {
+        let scenario = parse(
+            "(players: [(
+                faction: Asia,
+                tokens: [(area: 41, tokens: 40)],
+                cities: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+                treasury: 8,
+            )])",
+        );
+
+        assert_eq!(scenario_problems(&scenario).len(), 2);
+    }
+
+    #[test]
+    fn test_scenario_problems_finds_clashing_players() {
+        let scenario = parse(
+            "(
+                activity: StartGame,
+                players: [
+                    (faction: Egypt, cities: [133]),
+                    (faction: Egypt, tokens: [(area: 133, tokens: 1)]),
+                ],
+            )",
+        );
+
+        assert_eq!(
+            scenario_problems(&scenario),
+            vec![
+                "The scenario can not start in StartGame".to_string(),
+                "Egypt is in the scenario twice".to_string(),
+                "Egypt has tokens in area 133 which has a city".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shipped_late_game_scenario_can_be_set_up() {
+        let text = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/scenarios/late_game.scenario.ron"
+        ))
+        .unwrap();
+
+        let scenario = parse(&text);
+
+        assert_eq!(scenario_problems(&scenario), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_scenario_from_args_finds_the_file() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            scenario_from_args(args(&["--scenario", "late_game"])),
+            Some("scenarios/late_game.scenario.ron".to_string())
+        );
+        assert_eq!(
+            scenario_from_args(args(&["--scenario", "mine/test.scenario.ron"])),
+            Some("mine/test.scenario.ron".to_string())
+        );
+        assert_eq!(scenario_from_args(args(&["--scenario"])), None);
+        assert_eq!(scenario_from_args(args(&[])), None);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::civilization::concepts::scenario::scenario_resources::{Scenario, ScenarioSetup};
+use crate::civilization::concepts::scenario::scenario_systems::load_scenario;
+use bevy::app::{App, Plugin, Startup};
+use bevy_common_assets::ron::RonAssetPlugin;
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<ScenarioSetup>() {
+            app.insert_resource(ScenarioSetup::from_args(std::env::args().skip(1)));
+        }
+        app.add_plugins(RonAssetPlugin::<Scenario>::new(&["scenario.ron"]))
+            .add_systems(Startup, load_scenario);
+    }
+}
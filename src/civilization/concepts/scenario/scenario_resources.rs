@@ -0,0 +1,79 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::scenario::scenario_functions::scenario_from_args;
+use crate::civilization::enums::GameFaction;
+use crate::GameActivity;
+use bevy::prelude::{Handle, Resource};
+use std::collections::HashMap;
+
+/// A board position to start a game from instead of one token in each start area.
+#[derive(
+    serde::Deserialize, serde::Serialize, bevy::asset::Asset, bevy::reflect::TypePath, Clone, Debug,
+)]
+pub struct Scenario {
+    /// The round being played when the game starts.
+    #[serde(default = "first_round")]
+    pub round: usize,
+    #[serde(default = "population_expansion")]
+    pub activity: GameActivity,
+    pub players: Vec<ScenarioPlayer>,
+}
+
+impl Scenario {
+    pub fn player(&self, faction: GameFaction) -> Option<&ScenarioPlayer> {
+        self.players.iter().find(|player| player.faction == faction)
+    }
+}
+
+fn first_round() -> usize {
+    1
+}
+
+fn population_expansion() -> GameActivity {
+    GameActivity::PopulationExpansion
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, Default)]
+pub struct ScenarioPlayer {
+    pub faction: GameFaction,
+    #[serde(default)]
+    pub tokens: Vec<ScenarioTokens>,
+    /// The ids of the areas the player has cities in.
+    #[serde(default)]
+    pub cities: Vec<i32>,
+    #[serde(default)]
+    pub trade_cards: HashMap<TradeCard, usize>,
+    #[serde(default)]
+    pub treasury: usize,
+}
+
+impl ScenarioPlayer {
+    pub fn tokens_on_board(&self) -> usize {
+        self.tokens.iter().map(|placed| placed.tokens).sum()
+    }
+}
+
+/// Tokens put in the area with the given id.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct ScenarioTokens {
+    pub area: i32,
+    pub tokens: usize,
+}
+
+#[derive(Resource)]
+pub struct ScenarioHandle(pub Handle<Scenario>);
+
+/// The scenario file to start the game from, relative to the assets folder. Games start from
+/// the usual single token per player when it is not set.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ScenarioSetup {
+    pub path: Option<String>,
+}
+
+impl ScenarioSetup {
+    /// The scenario asked for with `--scenario <file>` on the command line.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        ScenarioSetup {
+            path: scenario_from_args(args),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::civilization::components::{CityTokenStock, GameArea, PlayerCities};
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::city_construction::city_construction_events::BuildCityCommand;
+use crate::civilization::concepts::map::map_plugin::AvailableFactions;
+use crate::civilization::concepts::scenario::scenario_functions::{
+    scenario_problems, starting_round,
+};
+use crate::civilization::concepts::scenario::scenario_resources::{
+    Scenario, ScenarioHandle, ScenarioSetup,
+};
+use crate::civilization::enums::GameFaction;
+use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::civilization::functions::build_city_in_area;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    warn, AssetServer, Assets, Commands, Entity, MessageWriter, Query, Res, ResMut, Transform,
+};
+
+pub fn load_scenario(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    setup: Res<ScenarioSetup>,
+) {
+    if let Some(path) = setup.path.clone() {
+        commands.insert_resource(ScenarioHandle(asset_server.load(path)));
+    }
+}
+
+/// The scenario the game starts from, if one was asked for and has loaded.
+#[derive(SystemParam)]
+pub struct LoadedScenario<'w> {
+    handle: Option<Res<'w, ScenarioHandle>>,
+    scenarios: Res<'w, Assets<Scenario>>,
+}
+
+impl LoadedScenario<'_> {
+    pub fn get(&self) -> Option<&Scenario> {
+        self.handle
+            .as_ref()
+            .and_then(|handle| self.scenarios.get(handle.0.id()))
+    }
+
+    pub fn was_asked_for(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /// The scenario if it can be set up, warning about whatever is wrong with it otherwise.
+    pub fn validated(&self) -> Option<&Scenario> {
+        if !self.was_asked_for() {
+            return None;
+        }
+        let Some(scenario) = self.get() else {
+            warn!("The scenario has not loaded, starting a regular game");
+            return None;
+        };
+        let problems = scenario_problems(scenario);
+        for problem in problems.iter() {
+            warn!("Scenario: {}", problem);
+        }
+        problems.is_empty().then_some(scenario)
+    }
+}
+
+/// What it takes to lay a scenario out on the map.
+#[derive(SystemParam)]
+pub struct ScenarioBoard<'w, 's> {
+    commands: Commands<'w, 's>,
+    area_query: Query<'w, 's, (Entity, &'static GameArea, &'static Transform)>,
+    city_query: Query<'w, 's, (&'static mut CityTokenStock, &'static mut PlayerCities)>,
+    game_factions: Res<'w, AvailableFactions>,
+    game_info: ResMut<'w, GameInfoAndStuff>,
+}
+
+impl ScenarioBoard<'_, '_> {
+    fn area(&self, id: i32) -> Option<(Entity, Transform)> {
+        let found = self
+            .area_query
+            .iter()
+            .find(|(_, area, _)| area.id == id)
+            .map(|(entity, _, transform)| (entity, *transform));
+        if found.is_none() {
+            warn!("Scenario area {} is not on the map", id);
+        }
+        found
+    }
+
+    pub fn lay_out(
+        &mut self,
+        scenario: &Scenario,
+        players: &[(Entity, GameFaction)],
+        writer: &mut MessageWriter<MoveTokensFromStockToAreaCommand>,
+    ) {
+        self.game_info.round = starting_round(scenario);
+        for (player_entity, faction) in players.iter() {
+            let Some(scenario_player) = scenario.player(*faction) else {
+                continue;
+            };
+            for placed in scenario_player.tokens.iter() {
+                if let Some((area_entity, _)) = self.area(placed.area) {
+                    writer.write(MoveTokensFromStockToAreaCommand {
+                        area_entity,
+                        player_entity: *player_entity,
+                        number_of_tokens: placed.tokens,
+                    });
+                }
+            }
+            for city_area in scenario_player.cities.iter() {
+                let Some((area_entity, area_transform)) = self.area(*city_area) else {
+                    continue;
+                };
+                let Some(texture) = self.game_factions.faction_city_icons.get(faction).cloned()
+                else {
+                    continue;
+                };
+                if let Ok((mut city_stock, mut player_cities)) =
+                    self.city_query.get_mut(*player_entity)
+                {
+                    build_city_in_area(
+                        &mut self.commands,
+                        texture,
+                        &BuildCityCommand::new(*player_entity, area_entity),
+                        &mut city_stock,
+                        &mut player_cities,
+                        &area_transform,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod spectator_components;
+pub mod spectator_functions;
+pub mod spectator_plugin;
+pub mod spectator_resources;
+pub mod spectator_systems;
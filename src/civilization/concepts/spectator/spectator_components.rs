@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+/// An AI player whose next move waits for the spectator to let it through.
+#[derive(Component, Debug, Default)]
+pub struct HeldMove;
+
+#[derive(Component, Default)]
+pub struct SpectatorPanel;
+
+#[derive(Component, Default)]
+pub struct SpectatorStatus;
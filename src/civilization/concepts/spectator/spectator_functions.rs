@@ -0,0 +1,137 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::hot_seat::hot_seat_functions::describe_cards;
+use crate::civilization::concepts::spectator::spectator_resources::{
+    SpectatorControl, SpectatorView,
+};
+use bevy::prelude::Entity;
+
+/// Cycles from seeing everything through each player's view and back again.
+pub fn next_view(view: SpectatorView, players: &[Entity]) -> SpectatorView {
+    let next_index = match view {
+        SpectatorView::Omniscient => 0,
+        SpectatorView::Player(player) => players
+            .iter()
+            .position(|p| *p == player)
+            .map_or(players.len(), |index| index + 1),
+    };
+    players
+        .get(next_index)
+        .map_or(SpectatorView::Omniscient, |player| {
+            SpectatorView::Player(*player)
+        })
+}
+
+/// One line per player. Hands are only shown to an omniscient spectator or to the player
+/// being watched, everyone else's cards are just counted.
+pub fn hand_lines(
+    view: SpectatorView,
+    players: &[(Entity, String, &PlayerTradeCards)],
+) -> Vec<String> {
+    players
+        .iter()
+        .map(|(player, name, trade_cards)| {
+            let visible = match view {
+                SpectatorView::Omniscient => true,
+                SpectatorView::Player(watched) => watched == *player,
+            };
+            if visible && trade_cards.has_trade_cards() {
+                format!("{}: {}", name, describe_cards(trade_cards.cards()))
+            } else {
+                format!("{}: {} cards", name, trade_cards.number_of_trade_cards())
+            }
+        })
+        .collect()
+}
+
+pub fn status_line(control: &SpectatorControl, watched: Option<&str>) -> String {
+    let pace = if control.paused {
+        "Paused".to_string()
+    } else {
+        format!("{} moves/s", control.speed())
+    };
+    let view = watched.map_or("Everything".to_string(), |name| name.to_string());
+    format!(
+        "Spectating - {} - Watching: {} (Space pause, N step, +/- speed, V view, F2 leave)",
+        pace, view
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_views_cycle_through_players_and_back() {
+        let players = vec![create_entity(), create_entity()];
+
+        let view = next_view(SpectatorView::Omniscient, &players);
+        assert_eq!(view, SpectatorView::Player(players[0]));
+        let view = next_view(view, &players);
+        assert_eq!(view, SpectatorView::Player(players[1]));
+        assert_eq!(next_view(view, &players), SpectatorView::Omniscient);
+    }
+
+    #[test]
+    fn test_player_view_only_counts_other_hands() {
+        let me = create_entity();
+        let other = create_entity();
+        let mut my_cards = PlayerTradeCards::default();
+        my_cards.add_trade_cards(TradeCard::Ochre, 2);
+        let mut other_cards = PlayerTradeCards::default();
+        other_cards.add_trade_cards(TradeCard::Salt, 3);
+        let players = vec![
+            (me, "Crete".to_string(), &my_cards),
+            (other, "Thrace".to_string(), &other_cards),
+        ];
+
+        let lines = hand_lines(SpectatorView::Player(me), &players);
+        assert_eq!(lines[0], format!("Crete: 2 {}", TradeCard::Ochre));
+        assert_eq!(lines[1], "Thrace: 3 cards");
+
+        let lines = hand_lines(SpectatorView::Omniscient, &players);
+        assert_eq!(lines[1], format!("Thrace: 3 {}", TradeCard::Salt));
+    }
+
+    #[test]
+    fn test_moves_wait_for_steps_while_spectating() {
+        let mut control = SpectatorControl::default();
+        assert!(control.take_action());
+
+        control.active = true;
+        assert!(!control.take_action());
+        control.step();
+        assert!(control.take_action());
+        assert!(!control.take_action());
+    }
+
+    #[test]
+    fn test_steps_are_handed_out_at_the_chosen_speed() {
+        let mut control = SpectatorControl {
+            active: true,
+            ..Default::default()
+        };
+        control.advance(0.1);
+        assert_eq!(control.steps, 0);
+        control.advance(0.2);
+        assert_eq!(control.steps, 1);
+
+        control.steps = 0;
+        control.paused = true;
+        control.advance(10.);
+        assert_eq!(control.steps, 0);
+    }
+}
@@ -0,0 +1,20 @@
+use crate::civilization::concepts::spectator::spectator_resources::SpectatorControl;
+use crate::civilization::concepts::spectator::spectator_systems::*;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
+
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpectatorControl>()
+            .add_systems(OnEnter(GameActivity::StartGame), setup_spectator_panel)
+            .add_systems(
+                Update,
+                (spectator_keys, release_held_moves, refresh_spectator_panel)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
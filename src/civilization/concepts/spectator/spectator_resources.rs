@@ -0,0 +1,81 @@
+use bevy::prelude::{Entity, Resource};
+
+/// The speeds, in AI moves per second, the spectator can pick from.
+pub const SPECTATOR_SPEEDS: [f32; 7] = [0.5, 1., 2., 4., 8., 16., 32.];
+
+/// Whose cards the spectator gets to see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectatorView {
+    #[default]
+    Omniscient,
+    Player(Entity),
+}
+
+/// Pacing for the AI players while a spectator is watching. Every AI move uses up one step,
+/// steps are handed out at the chosen speed, or one at a time while paused.
+#[derive(Resource, Debug)]
+pub struct SpectatorControl {
+    pub active: bool,
+    pub paused: bool,
+    pub speed_index: usize,
+    pub steps: usize,
+    pub progress: f32,
+    pub view: SpectatorView,
+}
+
+impl Default for SpectatorControl {
+    fn default() -> Self {
+        Self {
+            active: false,
+            paused: false,
+            speed_index: 3,
+            steps: 0,
+            progress: 0.,
+            view: SpectatorView::Omniscient,
+        }
+    }
+}
+
+impl SpectatorControl {
+    pub fn speed(&self) -> f32 {
+        SPECTATOR_SPEEDS[self.speed_index]
+    }
+
+    /// Whether an AI may make a move right now. Without a spectator they always may.
+    pub fn take_action(&mut self) -> bool {
+        if !self.active {
+            return true;
+        }
+        if self.steps == 0 {
+            return false;
+        }
+        self.steps -= 1;
+        true
+    }
+
+    /// Lets the time pass and hands out a step when the next move is due.
+    pub fn advance(&mut self, seconds: f32) {
+        if !self.active || self.paused {
+            return;
+        }
+        self.progress += seconds * self.speed();
+        if self.progress >= 1. {
+            self.progress = 0.;
+            self.steps = 1;
+        }
+    }
+
+    pub fn step(&mut self) {
+        if self.active {
+            self.steps += 1;
+        }
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPECTATOR_SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+}
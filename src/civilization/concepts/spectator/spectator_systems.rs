@@ -0,0 +1,177 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::spectator::spectator_components::{
+    HeldMove, SpectatorPanel, SpectatorStatus,
+};
+use crate::civilization::concepts::spectator::spectator_functions::{
+    hand_lines, next_view, status_line,
+};
+use crate::civilization::concepts::spectator::spectator_resources::{
+    SpectatorControl, SpectatorView,
+};
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
+use crate::civilization::ui::ui_builder::{UIBuilder, UiBuilderDefaults, BG_COLOR};
+use crate::player::Player;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    ButtonInput, Commands, Display, Entity, KeyCode, MessageWriter, Name, Node, PositionType,
+    Query, Real, Res, ResMut, Text, Time, Val, Virtual, With, Without,
+};
+
+/// Lets the AI players' observers ask the spectator before they pick a move.
+#[derive(SystemParam)]
+pub struct SpectatorGate<'w, 's> {
+    control: ResMut<'w, SpectatorControl>,
+    commands: Commands<'w, 's>,
+}
+
+impl SpectatorGate<'_, '_> {
+    /// Holds the player's move back when the spectator has no step to spare. The move is
+    /// offered again once it is this player's turn to go.
+    pub fn hold_back(&mut self, player: Entity) -> bool {
+        if self.control.take_action() {
+            return false;
+        }
+        self.commands.entity(player).insert(HeldMove);
+        true
+    }
+}
+
+pub fn setup_spectator_panel(commands: Commands, ui_defaults: Res<UiBuilderDefaults>) {
+    let mut panel = UIBuilder::new(commands, Some(ui_defaults.clone()));
+
+    panel
+        .with_component::<SpectatorPanel>()
+        .as_flex_col(Val::Percent(50.), Val::Auto)
+        .with_position_type(PositionType::Absolute)
+        .with_left(Val::Percent(25.))
+        .with_top(Val::Px(0.))
+        .with_bg_color(BG_COLOR)
+        .with_display(Display::None)
+        .child()
+        .with_default_text("")
+        .with_component::<SpectatorStatus>()
+        .parent();
+
+    let (_root_entity, _commands) = panel.build();
+}
+
+/// F2 attaches to or leaves the game, Space pauses, N steps one move, +/- change the speed and
+/// V changes whose cards are shown.
+pub fn spectator_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut control: ResMut<SpectatorControl>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    players: Query<Entity, With<Player>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F2) {
+        control.active = !control.active;
+        control.paused = false;
+        control.steps = 0;
+        control.progress = 0.;
+    }
+    if !control.active {
+        if virtual_time.is_paused() {
+            virtual_time.unpause();
+        }
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        control.paused = !control.paused;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyN) {
+        control.step();
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        control.faster();
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        control.slower();
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyV) {
+        let mut players = players.iter().collect::<Vec<_>>();
+        players.sort();
+        control.view = next_view(control.view, &players);
+    }
+    if control.paused != virtual_time.is_paused() {
+        if control.paused {
+            virtual_time.pause();
+        } else {
+            virtual_time.unpause();
+        }
+    }
+}
+
+/// Offers held moves again, one at a time as steps come in, or all at once when the spectator
+/// leaves.
+pub fn release_held_moves(
+    mut control: ResMut<SpectatorControl>,
+    time: Res<Time<Real>>,
+    held: Query<Entity, (With<HeldMove>, With<AvailableMoves>)>,
+    stale: Query<Entity, (With<HeldMove>, Without<AvailableMoves>)>,
+    mut recalculate: MessageWriter<RecalculatePlayerMoves>,
+    mut commands: Commands,
+) {
+    for player in stale.iter() {
+        commands.entity(player).remove::<HeldMove>();
+    }
+    if !control.active {
+        for player in held.iter() {
+            commands.entity(player).remove::<HeldMove>();
+            recalculate.write(RecalculatePlayerMoves::new(player));
+        }
+        return;
+    }
+    if held.is_empty() {
+        return;
+    }
+    control.advance(time.delta_secs());
+    if control.steps == 0 {
+        return;
+    }
+    if let Some(player) = held.iter().min() {
+        commands.entity(player).remove::<HeldMove>();
+        recalculate.write(RecalculatePlayerMoves::new(player));
+    }
+}
+
+pub fn refresh_spectator_panel(
+    control: Res<SpectatorControl>,
+    players: Query<(Entity, &Name, &PlayerTradeCards), With<Player>>,
+    mut panel: Query<&mut Node, With<SpectatorPanel>>,
+    mut status: Query<&mut Text, With<SpectatorStatus>>,
+) {
+    let (Ok(mut node), Ok(mut text)) = (panel.single_mut(), status.single_mut()) else {
+        return;
+    };
+    let display = if control.active {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    if node.display != display {
+        node.display = display;
+    }
+    if !control.active {
+        return;
+    }
+
+    let mut players = players
+        .iter()
+        .map(|(player, name, trade_cards)| (player, name.to_string(), trade_cards))
+        .collect::<Vec<_>>();
+    players.sort_by_key(|(player, _, _)| *player);
+    let watched = match control.view {
+        SpectatorView::Omniscient => None,
+        SpectatorView::Player(watched) => players
+            .iter()
+            .find(|(player, _, _)| *player == watched)
+            .map(|(_, name, _)| name.as_str()),
+    };
+    let mut lines = vec![status_line(&control, watched)];
+    lines.extend(hand_lines(control.view, &players));
+    let content = lines.join("\n");
+    if text.0 != content {
+        text.0 = content;
+    }
+}
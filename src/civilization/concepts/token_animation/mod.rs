@@ -0,0 +1,6 @@
+pub mod token_animation_components;
+pub mod token_animation_functions;
+pub mod token_animation_plugin;
+pub mod token_animation_resources;
+pub mod token_animation_systems;
+pub mod token_animation_triggers;
@@ -0,0 +1,57 @@
+use bevy::prelude::{Component, Reflect, Transform, Vec3};
+
+/// The scale token and city sprites are drawn at when they sit on the map.
+pub const TOKEN_SCALE: Vec3 = Vec3::splat(0.25);
+
+/// Slides and scales a sprite from one place on the map to another. Removed when it is done,
+/// or, for sprites that only exist to be animated, the whole entity is despawned.
+#[derive(Component, Debug, Reflect)]
+pub struct TokenTween {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub from_scale: Vec3,
+    pub to_scale: Vec3,
+    /// Seconds to hold still at the start before moving
+    pub delay: f32,
+    pub duration: f32,
+    pub elapsed: f32,
+    pub despawn_when_done: bool,
+}
+
+impl TokenTween {
+    pub fn new(from: &Transform, to: Vec3, to_scale: Vec3, duration: f32) -> Self {
+        TokenTween {
+            from: from.translation,
+            to,
+            from_scale: from.scale,
+            to_scale,
+            delay: 0.0,
+            duration,
+            elapsed: 0.0,
+            despawn_when_done: false,
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn despawn_when_done(mut self) -> Self {
+        self.despawn_when_done = true;
+        self
+    }
+}
+
+/// Put on tokens that are about to be replaced by a city, so they gather in the middle of the
+/// area on their way back to the stock instead of just shrinking where they stand.
+#[derive(Component, Debug, Reflect)]
+pub struct MergeIntoCity {
+    pub target: Vec3,
+}
+
+impl MergeIntoCity {
+    pub fn new(target: Vec3) -> Self {
+        MergeIntoCity { target }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::civilization::concepts::token_animation::token_animation_components::TokenTween;
+use bevy::math::Vec3;
+
+/// Starts slow, speeds up in the middle and slows down again when arriving.
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// Translation and scale of a tweened sprite after `elapsed` seconds.
+pub fn sample_tween(tween: &TokenTween) -> (Vec3, Vec3) {
+    let progress = if tween.duration > 0.0 {
+        (tween.elapsed - tween.delay) / tween.duration
+    } else {
+        1.0
+    };
+    let t = ease_in_out(progress);
+    (
+        tween.from.lerp(tween.to, t),
+        tween.from_scale.lerp(tween.to_scale, t),
+    )
+}
+
+pub fn tween_is_finished(tween: &TokenTween) -> bool {
+    tween.elapsed >= tween.delay + tween.duration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::prelude::Transform;
+
+    fn tween() -> TokenTween {
+        TokenTween::new(
+            &Transform::from_translation(Vec3::ZERO).with_scale(Vec3::ZERO),
+            Vec3::new(100.0, 50.0, 0.0),
+            Vec3::ONE,
+            1.0,
+        )
+    }
+
+    #[test]
+    fn test_ease_in_out_keeps_end_points() {
+        assert_eq!(ease_in_out(0.0), 0.0);
+        assert_eq!(ease_in_out(0.5), 0.5);
+        assert_eq!(ease_in_out(1.0), 1.0);
+        assert_eq!(ease_in_out(2.0), 1.0);
+    }
+
+    #[test]
+    fn test_tween_is_held_during_delay() {
+        let mut tween = tween().with_delay(0.5);
+        tween.elapsed = 0.4;
+        assert_eq!(sample_tween(&tween), (Vec3::ZERO, Vec3::ZERO));
+        assert!(!tween_is_finished(&tween));
+    }
+
+    #[test]
+    fn test_tween_ends_at_target() {
+        let mut tween = tween();
+        tween.elapsed = 1.5;
+        assert_eq!(
+            sample_tween(&tween),
+            (Vec3::new(100.0, 50.0, 0.0), Vec3::ONE)
+        );
+        assert!(tween_is_finished(&tween));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::civilization::concepts::token_animation::token_animation_components::{
+    MergeIntoCity, TokenTween,
+};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use crate::civilization::concepts::token_animation::token_animation_systems::*;
+use crate::civilization::concepts::token_animation::token_animation_triggers::on_add_return_token_to_stock_leave_ghost;
+use crate::civilization::general_systems::fix_token_positions;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
+
+pub struct TokenAnimationPlugin;
+
+impl Plugin for TokenAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TokenTween>()
+            .register_type::<MergeIntoCity>()
+            .register_type::<TokenAnimationSettings>()
+            .init_resource::<TokenAnimationSettings>()
+            .add_systems(
+                OnEnter(GameActivity::StartGame),
+                skip_animations_in_ai_only_games,
+            )
+            .add_systems(
+                Update,
+                // A tween that finishes must not remove the new one fix_token_positions just added
+                animate_tokens
+                    .before(fix_token_positions)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_observer(on_add_return_token_to_stock_leave_ghost);
+    }
+}
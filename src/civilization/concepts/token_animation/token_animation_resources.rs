@@ -0,0 +1,30 @@
+use bevy::prelude::{Reflect, Resource};
+
+#[derive(Resource, Debug, Reflect)]
+pub struct TokenAnimationSettings {
+    /// Playback speed, 2.0 plays every animation in half the time
+    pub speed: f32,
+    /// Seconds a token needs to travel, appear or disappear at speed 1.0
+    pub token_duration: f32,
+    /// Place tokens and cities instantly instead of animating them
+    pub skip_animations: bool,
+    /// Turn on skip_animations when a game starts without a human player
+    pub skip_in_ai_only_games: bool,
+}
+
+impl TokenAnimationSettings {
+    pub fn duration(&self) -> f32 {
+        self.token_duration / self.speed.max(0.01)
+    }
+}
+
+impl Default for TokenAnimationSettings {
+    fn default() -> Self {
+        TokenAnimationSettings {
+            speed: 1.0,
+            token_duration: 0.4,
+            skip_animations: false,
+            skip_in_ai_only_games: true,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::civilization::concepts::token_animation::token_animation_components::TokenTween;
+use crate::civilization::concepts::token_animation::token_animation_functions::{
+    sample_tween, tween_is_finished,
+};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use crate::player::Player;
+use crate::stupid_ai::prelude::IsHuman;
+use bevy::prelude::{Commands, Entity, Query, Res, ResMut, Time, Transform, With};
+
+pub fn animate_tokens(
+    time: Res<Time>,
+    mut tween_query: Query<(Entity, &mut Transform, &mut TokenTween)>,
+    mut commands: Commands,
+) {
+    for (entity, mut transform, mut tween) in tween_query.iter_mut() {
+        tween.elapsed += time.delta_secs();
+        let (translation, scale) = sample_tween(&tween);
+        transform.translation = translation;
+        transform.scale = scale;
+        if tween_is_finished(&tween) {
+            if tween.despawn_when_done {
+                commands.entity(entity).despawn();
+            } else {
+                commands.entity(entity).remove::<TokenTween>();
+            }
+        }
+    }
+}
+
+/// Nobody is watching a game played by the AI alone, so there is no point in waiting for tokens to slide around.
+pub fn skip_animations_in_ai_only_games(
+    human_query: Query<(), (With<Player>, With<IsHuman>)>,
+    mut settings: ResMut<TokenAnimationSettings>,
+) {
+    if settings.skip_in_ai_only_games && human_query.is_empty() {
+        settings.skip_animations = true;
+    }
+}
@@ -0,0 +1,34 @@
+use crate::civilization::components::{ReturnTokenToStock, Token};
+use crate::civilization::concepts::token_animation::token_animation_components::{
+    MergeIntoCity, TokenTween,
+};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use bevy::prelude::{Add, Commands, Name, On, Query, Res, Sprite, Transform, Vec3, With};
+
+/// The token itself loses its sprite the moment it goes back to the stock, so a stand-in
+/// sprite is left behind on the map that shrinks away, or slides into the city it became part of.
+pub fn on_add_return_token_to_stock_leave_ghost(
+    trigger: On<Add, ReturnTokenToStock>,
+    token_query: Query<(&Sprite, &Transform, Option<&MergeIntoCity>), With<Token>>,
+    settings: Res<TokenAnimationSettings>,
+    mut commands: Commands,
+) {
+    let token = trigger.event().entity;
+    if let Ok((sprite, transform, merge_into_city)) = token_query.get(token) {
+        if !settings.skip_animations {
+            let target = merge_into_city
+                .map(|merge| merge.target)
+                .unwrap_or(transform.translation);
+            commands.spawn((
+                Name::new("Token ghost"),
+                sprite.clone(),
+                *transform,
+                TokenTween::new(transform, target, Vec3::ZERO, settings.duration())
+                    .despawn_when_done(),
+            ));
+        }
+    }
+    commands
+        .entity(token)
+        .remove::<(MergeIntoCity, TokenTween)>();
+}
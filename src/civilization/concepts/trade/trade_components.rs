@@ -3,7 +3,6 @@ use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeC
 use crate::civilization::game_moves::game_moves_components::TradeMove;
 use bevy::platform::collections::{HashMap, HashSet};
 use bevy::prelude::{Component, Entity, Reflect};
-use rand::prelude::{IndexedRandom, ThreadRng};
 use std::collections::VecDeque;
 
 #[derive(Component, Default, Clone, PartialEq, Eq, Debug, Reflect)]
@@ -57,10 +56,6 @@ impl PlayerTradeInterests {
             wants: player_trade_cards.wants(),
         }
     }
-    
-    pub fn get_trade_thingie(&self, mut rng: &mut ThreadRng) -> HashMap<TradeCard, usize> {
-        HashMap::from([(*self.wants.choose(&mut rng).unwrap(), 2), (*self.wants.choose(&mut rng).unwrap(), 1)])
-    }
 }
 /*
 Step one, make trade infinitely simpler than what we have right now.
@@ -195,6 +190,14 @@ impl TradeOffer {
         }
     }
 
+    /// The initiator takes the offer back, it is then removed like a rejected one.
+    pub fn withdraw(&mut self, entity: Entity) {
+        if self.am_i_the_initiator(entity) {
+            self.rejects = Some(entity);
+            self.accepts = None;
+        }
+    }
+
     pub fn receiver_accepts(&self) -> bool {
         self.accepts == Some(self.receiver) 
     }
@@ -1,9 +1,11 @@
 use crate::civilization::concepts::trade::trade_events::SendTradingCardsCommand;
 use crate::civilization::concepts::trade::trade_resources::{TradeCountdown, TradeUiState};
-use crate::civilization::concepts::trade::trade_triggers::{can_trade_removed, offer_published};
+use crate::civilization::concepts::trade::trade_triggers::{
+    can_trade_removed, offer_published, trade_offer_removed,
+};
 use crate::GameActivity;
 use bevy::app::App;
-use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter, OnExit, Plugin, Update};
 use crate::civilization::concepts::trade::trade_systems::*;
 
 pub struct TradePlugin;
@@ -14,6 +16,7 @@ impl Plugin for TradePlugin {
             .init_resource::<TradeCountdown>()
             .add_message::<SendTradingCardsCommand>()
             .add_systems(OnEnter(GameActivity::Trade), setup_trade)
+            .add_systems(OnExit(GameActivity::Trade), on_exit_trade)
             .add_systems(
                 Update,
                 (
@@ -30,6 +33,7 @@ impl Plugin for TradePlugin {
             )
             .add_systems(Update, button_action)
             .add_observer(offer_published)
-            .add_observer(can_trade_removed);
+            .add_observer(can_trade_removed)
+            .add_observer(trade_offer_removed);
     }
 }
@@ -1,7 +1,7 @@
 use bevy::prelude::{Entity, Resource, Timer, TimerMode};
 #[derive(Default, Resource)]
 pub struct TradeUiState {
-    pub human_player: Option<Entity>,
+    pub human_players: Vec<Entity>,
 }
 
 #[derive(Resource)]
@@ -1,5 +1,6 @@
 use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
 use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
 use crate::civilization::concepts::trade::trade_components::{
     AvailableTradeOfferActions, CanTrade, InSettlement, NeedsTradeMove, PlayerSettlements,
     PlayerTradeInterests, PublishedOffer, TradeButtonAction, TradeOffer,
@@ -9,6 +10,7 @@ use crate::civilization::concepts::trade::trade_resources::{TradeCountdown, Trad
 use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move, TradeMove};
 use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
 use crate::civilization::ui::ui_builder::UiBuilderDefaults;
+use crate::player::Player;
 use crate::stupid_ai::prelude::IsHuman;
 use crate::GameActivity;
 use bevy::platform::collections::HashMap;
@@ -41,6 +43,16 @@ pub fn check_trade_gate(
     }
 }
 
+/// Moves that were still coming and trades left unsettled when the trading ended would keep the
+/// players waiting in the next trade phase.
+pub fn on_exit_trade(player_query: Query<Entity, With<Player>>, mut commands: Commands) {
+    for player in player_query.iter() {
+        commands
+            .entity(player)
+            .remove::<(NeedsTradeMove, PlayerSettlements)>();
+    }
+}
+
 pub fn settle_trades(
     mut commands: Commands,
     mut trades_query: Query<(Entity, &mut TradeOffer)>,
@@ -103,14 +115,16 @@ pub fn setup_trade(
     trading_players_query: Query<(&PlayerTradeCards, Entity, Has<IsHuman>)>,
     _ui_builder_defaults: Res<UiBuilderDefaults>,
     mut trade_ui_state: ResMut<TradeUiState>,
+    rules: Res<RuleSet>,
 ) {
     let mut _has_any_human = false;
     let mut _players_that_can_trade_count: usize = 0;
+    trade_ui_state.human_players.clear();
     for (trade_cards, player, is_human) in trading_players_query.iter() {
-        if trade_cards.can_trade() {
+        if trade_cards.can_trade(rules.min_cards_to_trade) {
             if is_human {
                 _has_any_human = true;
-                trade_ui_state.human_player = Some(player);
+                trade_ui_state.human_players.push(player);
             }
             commands.entity(player).insert(CanTrade);
             commands
@@ -160,6 +174,7 @@ pub fn begin_trade_settlement(
     mut player_settlement_query: Query<&mut PlayerSettlements>,
     mut commands: Commands,
 ) {
+    let mut new_settlements: HashMap<Entity, PlayerSettlements> = HashMap::default();
     for (trade_entity, offer) in trade_offers.iter() {
         if offer.trade_accepted() {
             /* So much needs to happen here! */
@@ -182,28 +197,31 @@ pub fn begin_trade_settlement(
             So the trade itself needs to be marked as "in settlement" and then added to a queue for the
             players involved with it.
              */
-            let initiator = offer.initiator;
-            let receiver = offer.receiver;
-
-            if !player_settlement_query.contains(initiator) {
-                commands
-                    .entity(initiator)
-                    .insert(PlayerSettlements::default());
-            }
-            if !player_settlement_query.contains(receiver) {
-                commands
-                    .entity(receiver)
-                    .insert(PlayerSettlements::default());
+            for player in [offer.initiator, offer.receiver] {
+                if let Ok(mut settlements) = player_settlement_query.get_mut(player) {
+                    settlements.trades.push_back(trade_entity);
+                } else {
+                    new_settlements
+                        .entry(player)
+                        .or_default()
+                        .trades
+                        .push_back(trade_entity);
+                }
             }
-            commands.entity(trade_entity).insert(InSettlement); //Makes sure we don't end up here again!
-            let mut initiator_settlements = player_settlement_query
-                .get_mut(initiator)
-                .expect("Player should have settlements");
-            initiator_settlements.trades.push_back(trade_entity);
-            let mut receiver_settlements = player_settlement_query
-                .get_mut(receiver)
-                .expect("Player should have settlements");
-            receiver_settlements.trades.push_back(trade_entity);
+            // The offer may be withdrawn in the same frame
+            commands.entity(trade_entity).try_insert(InSettlement); //Makes sure we don't end up here again!
+        }
+    }
+    for (player, settlements) in new_settlements {
+        commands.entity(player).insert(settlements);
+    }
+    // Both players have to settle right away, so they get a move without waiting for the countdown
+    for (_, offer) in trade_offers.iter().filter(|(_, offer)| offer.trade_accepted()) {
+        for player in [offer.initiator, offer.receiver] {
+            commands
+                .entity(player)
+                .remove::<NeedsTradeMove>()
+                .insert(NeedsTradeMove);
         }
     }
 }
@@ -274,9 +292,22 @@ pub fn recalculate_trade_moves_for_player(
         let mut is_not_settling_trade = true;
         // we cannot create new trades or stop trading while we need to settle a trade.
         if let Ok(mut player_settlement) = player_settlements_query.get_mut(event.player) {
-            if !player_settlement.trades.is_empty() && player_settlement.current_trade.is_none() {
-                player_settlement.current_trade =
-                    Some(player_settlement.trades.pop_front().unwrap());
+            // Trades we have settled, or that were called off, are done with
+            let needs_settling = |trade: Entity| {
+                trade_offer_query.get(trade).is_ok_and(|(_, offer)| {
+                    !offer.settled_players.contains(&event.player) && !offer.trade_rejected()
+                })
+            };
+            player_settlement.current_trade =
+                player_settlement.current_trade.filter(|trade| needs_settling(*trade));
+            while player_settlement.current_trade.is_none() {
+                match player_settlement.trades.pop_front() {
+                    Some(trade) if needs_settling(trade) => {
+                        player_settlement.current_trade = Some(trade);
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
             }
             if let Some(current_trade) = player_settlement.current_trade {
                 debug!("Player needs to settle a trade");
@@ -406,41 +437,40 @@ pub fn recalculate_trade_moves_for_player(
                     //         _ => {}
                     //     }
                     // }
-
-                    for (trade_offer_entity, trade_offer) in trade_offer_query.iter() {
-                        if let Some(offer_actions) =
-                            trade_offer.get_trade_offer_actions(event.player)
-                        {
-                            for action in offer_actions {
-                                match action {
-                                    AvailableTradeOfferActions::CanAccept => {
-                                        command_index += 1;
-                                        moves.insert(
-                                            command_index,
-                                            Move::Trade(TradeMove::AcceptOrDeclineTrade(
-                                                trade_offer_entity,
-                                            )),
-                                        );
-                                    }
-                                    AvailableTradeOfferActions::Decline => {
-                                        command_index += 1;
-                                        moves.insert(
-                                            command_index,
-                                            Move::Trade(TradeMove::AutoDeclineTrade(
-                                                trade_offer_entity,
-                                            )),
-                                        );
-                                    }
+                }
+                for (trade_offer_entity, trade_offer) in trade_offer_query.iter() {
+                    if let Some(offer_actions) =
+                        trade_offer.get_trade_offer_actions(event.player)
+                    {
+                        for action in offer_actions {
+                            match action {
+                                AvailableTradeOfferActions::CanAccept => {
+                                    command_index += 1;
+                                    moves.insert(
+                                        command_index,
+                                        Move::Trade(TradeMove::AcceptOrDeclineTrade(
+                                            trade_offer_entity,
+                                        )),
+                                    );
+                                }
+                                AvailableTradeOfferActions::Decline => {
+                                    command_index += 1;
+                                    moves.insert(
+                                        command_index,
+                                        Move::Trade(TradeMove::AutoDeclineTrade(
+                                            trade_offer_entity,
+                                        )),
+                                    );
                                 }
                             }
                         }
                     }
-                    command_index += 1;
-                    moves.insert(command_index, Move::Trade(TradeMove::StopTrading));
                 }
+                command_index += 1;
+                moves.insert(command_index, Move::Trade(TradeMove::StopTrading));
             }
-            commands.entity(event.player).remove::<NeedsTradeMove>();
         }
+        commands.entity(event.player).remove::<NeedsTradeMove>();
         if moves.is_empty() {
             commands.entity(event.player).remove::<CanTrade>();
         } else {
@@ -1,6 +1,6 @@
 use crate::civilization::concepts::trade::trade_components::{
-    CanTrade, PlayerTradeInterests, PublishedOffer, PublishedOffersList, TradeButtonAction,
-    TradeOffer,
+    CanTrade, NeedsTradeMove, PlayerSettlements, PlayerTradeInterests, PublishedOffer,
+    PublishedOffersList, TradeButtonAction, TradeOffer,
 };
 use crate::civilization::ui::ui_builder::{
     ButtonPartial, UIBuilder, UiBuilderDefaults, BG_COLOR, BORDER_COLOR,
@@ -165,3 +165,21 @@ pub fn can_trade_removed(
         next_state.set(GameActivity::PopulationExpansion);
     }
 }
+
+/// A trade that goes away while it waits to be settled would leave both players waiting on a
+/// move for it, so they get to pick a new move.
+pub fn trade_offer_removed(
+    trigger: On<Remove, TradeOffer>,
+    settling_players: Query<(Entity, &PlayerSettlements), With<CanTrade>>,
+    mut commands: Commands,
+) {
+    let trade = trigger.event().entity;
+    for (player, settlements) in settling_players.iter() {
+        if settlements.current_trade == Some(trade) || settlements.trades.contains(&trade) {
+            commands
+                .entity(player)
+                .remove::<NeedsTradeMove>()
+                .insert(NeedsTradeMove);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod undo_functions;
+pub mod undo_plugin;
+pub mod undo_resources;
+pub mod undo_systems;
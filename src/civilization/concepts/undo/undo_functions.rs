@@ -0,0 +1,129 @@
+use crate::civilization::components::population::Population;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::Entity;
+
+/// Every token standing in one of the areas.
+pub fn tokens_on_board<'a>(
+    populations: impl IntoIterator<Item = &'a Population>,
+) -> HashSet<Entity> {
+    populations
+        .into_iter()
+        .flat_map(|population| population.player_tokens().values().flatten().copied())
+        .collect()
+}
+
+/// The acting player and everyone with tokens in the areas, since building a city sends
+/// all of them back to their stocks.
+pub fn involved_players<'a>(
+    player: Entity,
+    populations: impl IntoIterator<Item = &'a Population>,
+) -> Vec<Entity> {
+    let mut players = vec![player];
+    for population in populations {
+        for other in population.players() {
+            if !players.contains(&other) {
+                players.push(other);
+            }
+        }
+    }
+    players
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::undo::undo_resources::{
+        BoardSnapshot, UndoHistory, UndoStep, UndoableCommand,
+    };
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn step(command: UndoableCommand) -> UndoStep {
+        UndoStep {
+            command,
+            before: BoardSnapshot {
+                areas: Vec::new(),
+                players: Vec::new(),
+                moved_tokens: HashSet::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_undo_then_redo_keeps_the_redo_going() {
+        let player = create_entity();
+        let command = UndoableCommand::BuildCity {
+            player,
+            area: create_entity(),
+        };
+        let mut history = UndoHistory::default();
+        history.record(step(command));
+
+        assert_eq!(history.undo().map(|step| step.command), Some(command));
+        assert_eq!(history.redo(), Some(command));
+        // The redone command comes back through the recorder without wiping the redo list
+        history.redo.push(command);
+        history.record(step(command));
+        assert_eq!(history.redo.len(), 1);
+        assert_eq!(history.undo.len(), 1);
+    }
+
+    #[test]
+    fn test_a_new_command_clears_redo() {
+        let player = create_entity();
+        let command = UndoableCommand::Expand {
+            player,
+            area: create_entity(),
+            number_of_tokens: 1,
+        };
+        let mut history = UndoHistory::default();
+        history.record(step(command));
+        history.undo();
+
+        history.record(step(command));
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn test_another_player_acting_forgets_the_history() {
+        let player = create_entity();
+        let other = create_entity();
+        let mut history = UndoHistory::default();
+        history.record(step(UndoableCommand::BuildCity {
+            player,
+            area: create_entity(),
+        }));
+
+        history.someone_acted(player);
+        assert_eq!(history.undo.len(), 1);
+        history.someone_acted(other);
+        assert!(history.undo.is_empty());
+        assert_eq!(history.player, None);
+    }
+
+    #[test]
+    fn test_involved_players_include_everyone_in_the_area() {
+        let player = create_entity();
+        let other = create_entity();
+        let mut population = Population::new(3);
+        population.add_token_to_area(other, create_entity());
+        population.add_token_to_area(player, create_entity());
+
+        let players = involved_players(player, [&population]);
+        assert_eq!(players[0], player);
+        assert!(players.contains(&other));
+        assert_eq!(players.len(), 2);
+        assert_eq!(tokens_on_board([&population]).len(), 2);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::civilization::concepts::city_construction::city_construction_systems::build_city;
+use crate::civilization::concepts::movement::movement_systems::move_tokens_from_area_to_area;
+use crate::civilization::concepts::population_expansion::population_expansion_systems::expand_population_manually;
+use crate::civilization::concepts::undo::undo_resources::UndoHistory;
+use crate::civilization::concepts::undo::undo_systems::*;
+use crate::GameState;
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, IntoScheduleConfigs};
+
+pub struct UndoPlugin;
+
+impl Plugin for UndoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UndoHistory>().add_systems(
+            Update,
+            (
+                forget_committed_moves,
+                record_human_commands
+                    .before(expand_population_manually)
+                    .before(move_tokens_from_area_to_area)
+                    .before(build_city),
+                undo_keys,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
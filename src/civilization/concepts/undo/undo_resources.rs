@@ -0,0 +1,128 @@
+use crate::civilization::components::population::Population;
+use crate::civilization::components::{CityTokenStock, PlayerCities, TokenStock};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{Entity, Resource};
+
+/// A human command that can be taken back, and played again on redo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoableCommand {
+    Expand {
+        player: Entity,
+        area: Entity,
+        number_of_tokens: usize,
+    },
+    Move {
+        player: Entity,
+        source_area: Entity,
+        target_area: Entity,
+        number_of_tokens: usize,
+    },
+    BuildCity {
+        player: Entity,
+        area: Entity,
+    },
+}
+
+impl UndoableCommand {
+    pub fn player(&self) -> Entity {
+        match self {
+            UndoableCommand::Expand { player, .. }
+            | UndoableCommand::Move { player, .. }
+            | UndoableCommand::BuildCity { player, .. } => *player,
+        }
+    }
+
+    /// The areas the command changes.
+    pub fn areas(&self) -> Vec<Entity> {
+        match self {
+            UndoableCommand::Expand { area, .. } | UndoableCommand::BuildCity { area, .. } => {
+                vec![*area]
+            }
+            UndoableCommand::Move {
+                source_area,
+                target_area,
+                ..
+            } => vec![*source_area, *target_area],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AreaSnapshot {
+    pub area: Entity,
+    pub population: Population,
+    pub city: Option<(Entity, Entity)>,
+    pub expanding: Option<HashSet<Entity>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerSnapshot {
+    pub player: Entity,
+    pub stock: TokenStock,
+    pub city_stock: CityTokenStock,
+    pub cities: PlayerCities,
+    pub needs_expansion: Option<HashSet<Entity>>,
+    pub expand_manually: bool,
+}
+
+/// Everything a command touches, as it was before the command was carried out.
+#[derive(Debug, Clone)]
+pub struct BoardSnapshot {
+    pub areas: Vec<AreaSnapshot>,
+    pub players: Vec<PlayerSnapshot>,
+    pub moved_tokens: HashSet<Entity>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UndoStep {
+    pub command: UndoableCommand,
+    pub before: BoardSnapshot,
+}
+
+/// The commands one human has made since the phase started or another player acted.
+#[derive(Resource, Debug, Default)]
+pub struct UndoHistory {
+    pub player: Option<Entity>,
+    pub undo: Vec<UndoStep>,
+    pub redo: Vec<UndoableCommand>,
+    /// Redone commands that have been sent but not recorded yet.
+    pub replaying: usize,
+}
+
+impl UndoHistory {
+    pub fn record(&mut self, step: UndoStep) {
+        if self.player != Some(step.command.player()) {
+            self.forget();
+            self.player = Some(step.command.player());
+        }
+        if self.replaying > 0 {
+            self.replaying -= 1;
+        } else {
+            self.redo.clear();
+        }
+        self.undo.push(step);
+    }
+
+    pub fn undo(&mut self) -> Option<UndoStep> {
+        let step = self.undo.pop()?;
+        self.redo.push(step.command);
+        Some(step)
+    }
+
+    pub fn redo(&mut self) -> Option<UndoableCommand> {
+        let command = self.redo.pop()?;
+        self.replaying += 1;
+        Some(command)
+    }
+
+    pub fn forget(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Someone else acting means the board can no longer be put back safely.
+    pub fn someone_acted(&mut self, player: Entity) {
+        if self.player.is_some_and(|p| p != player) {
+            self.forget();
+        }
+    }
+}
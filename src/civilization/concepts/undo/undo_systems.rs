@@ -0,0 +1,385 @@
+use crate::civilization::components::population::{MaxPopulation, Populations};
+use crate::civilization::components::*;
+use crate::civilization::concepts::city_construction::city_construction_events::{
+    BuildCityCommand, EndPlayerCityConstruction,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_resources::ActiveSeat;
+use crate::civilization::concepts::map::map_plugin::AvailableFactions;
+use crate::civilization::concepts::movement::movement_components::TokenHasMoved;
+use crate::civilization::concepts::movement::movement_events::{
+    MoveTokenFromAreaToAreaCommand, PlayerMovementEnded,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_components::{
+    AreaIsExpanding, ExpandManually, NeedsExpansion,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_events::ExpandPopulationManuallyCommand;
+use crate::civilization::concepts::token_animation::token_animation_components::{
+    MergeIntoCity, TokenTween,
+};
+use crate::civilization::concepts::undo::undo_functions::{involved_players, tokens_on_board};
+use crate::civilization::concepts::undo::undo_resources::{
+    AreaSnapshot, BoardSnapshot, PlayerSnapshot, UndoHistory, UndoStep, UndoableCommand,
+};
+use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
+use crate::stupid_ai::prelude::IsHuman;
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    default, ButtonInput, Commands, Entity, Has, KeyCode, MessageReader, MessageWriter, Query, Res,
+    ResMut, Sprite, StateTransitionEvent, Transform, Vec3, Visibility, With,
+};
+
+/// The parts of the board the undoable commands change.
+#[derive(SystemParam)]
+pub struct UndoBoard<'w, 's> {
+    areas: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            Option<&'static BuiltCity>,
+            Option<&'static mut AreaIsExpanding>,
+        ),
+        With<MaxPopulation>,
+    >,
+    populations: Populations<'w, 's>,
+    players: Query<
+        'w,
+        's,
+        (
+            &'static mut TokenStock,
+            &'static mut CityTokenStock,
+            &'static mut PlayerCities,
+            &'static Faction,
+            Option<&'static mut NeedsExpansion>,
+            Has<ExpandManually>,
+        ),
+    >,
+    tokens: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Token,
+            Has<TokenHasMoved>,
+            Option<&'static InArea>,
+        ),
+    >,
+    game_factions: Res<'w, AvailableFactions>,
+    recalculate: MessageWriter<'w, RecalculatePlayerMoves>,
+    commands: Commands<'w, 's>,
+}
+
+impl UndoBoard<'_, '_> {
+    pub fn snapshot(&self, command: &UndoableCommand) -> BoardSnapshot {
+        let areas = command
+            .areas()
+            .into_iter()
+            .filter_map(|area| {
+                let (_, city, expanding) = self.areas.get(area).ok()?;
+                Some(AreaSnapshot {
+                    area,
+                    population: self.populations.get(area)?,
+                    city: city.map(|city| (city.city, city.player)),
+                    expanding: expanding.map(|e| e.players_that_must_expand.clone()),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let players = involved_players(command.player(), areas.iter().map(|area| &area.population))
+            .into_iter()
+            .filter_map(|player| {
+                let (stock, city_stock, cities, _, needs_expansion, expand_manually) =
+                    self.players.get(player).ok()?;
+                Some(PlayerSnapshot {
+                    player,
+                    stock: stock.clone(),
+                    city_stock: city_stock.clone(),
+                    cities: cities.clone(),
+                    needs_expansion: needs_expansion.map(|n| n.areas_that_need_expansion.clone()),
+                    expand_manually,
+                })
+            })
+            .collect();
+
+        let moved_tokens = self
+            .tokens
+            .iter()
+            .filter(|(_, token, moved, _)| *moved && token.player() == command.player())
+            .map(|(token, _, _, _)| token)
+            .collect();
+
+        BoardSnapshot {
+            areas,
+            players,
+            moved_tokens,
+        }
+    }
+
+    /// Puts the board back the way it was before the step, tokens and cities included.
+    pub fn restore(&mut self, step: &UndoStep) {
+        let before = &step.before;
+        let populations_now = before
+            .areas
+            .iter()
+            .filter_map(|area| self.populations.get(area.area))
+            .collect::<Vec<_>>();
+        let on_board_now = tokens_on_board(populations_now.iter());
+        let on_board_before = tokens_on_board(before.areas.iter().map(|area| &area.population));
+
+        // Tokens that went back to the stock, or came out of it, get their sprites back or lose them
+        for token in on_board_now.difference(&on_board_before) {
+            self.commands.entity(*token).remove::<(
+                InArea,
+                Sprite,
+                Transform,
+                Visibility,
+                TokenTween,
+                MergeIntoCity,
+            )>();
+        }
+        for snapshot in before.areas.iter() {
+            let Ok((area_transform, city, expanding)) = self.areas.get_mut(snapshot.area) else {
+                continue;
+            };
+            for (owner, tokens) in snapshot.population.player_tokens() {
+                let Ok((_, _, _, faction, _, _)) = self.players.get(*owner) else {
+                    continue;
+                };
+                // The populations are counted from InArea
+                for token in tokens.iter() {
+                    let in_area = self.tokens.get(*token).ok().and_then(|(_, _, _, a)| a);
+                    if in_area != Some(&InArea(snapshot.area)) {
+                        self.commands.entity(*token).insert(InArea(snapshot.area));
+                    }
+                }
+                for token in tokens.iter().filter(|t| !on_board_now.contains(*t)) {
+                    self.commands.entity(*token).insert((
+                        Sprite {
+                            image: self
+                                .game_factions
+                                .faction_icons
+                                .get(&faction.faction)
+                                .unwrap()
+                                .clone(),
+                            ..default()
+                        },
+                        Transform::from_scale(Vec3::ZERO)
+                            .with_translation(area_transform.translation),
+                    ));
+                }
+            }
+
+            match (snapshot.city, city) {
+                (Some((city, owner)), _) => {
+                    self.commands
+                        .entity(snapshot.area)
+                        .insert(BuiltCity::new(city, owner));
+                }
+                (None, Some(_)) => {
+                    self.commands.entity(snapshot.area).remove::<BuiltCity>();
+                }
+                (None, None) => {}
+            }
+            match (&snapshot.expanding, expanding) {
+                (Some(players), Some(mut expanding)) => {
+                    expanding.players_that_must_expand = players.clone();
+                }
+                (Some(players), None) => {
+                    self.commands
+                        .entity(snapshot.area)
+                        .insert(AreaIsExpanding::new(players.clone()));
+                }
+                (None, Some(_)) => {
+                    self.commands
+                        .entity(snapshot.area)
+                        .remove::<AreaIsExpanding>();
+                }
+                (None, None) => {}
+            }
+            self.commands
+                .entity(snapshot.area)
+                .insert(FixTokenPositions);
+        }
+
+        for snapshot in before.players.iter() {
+            let Ok((mut stock, mut city_stock, mut cities, _, needs_expansion, expand_manually)) =
+                self.players.get_mut(snapshot.player)
+            else {
+                continue;
+            };
+            for (area, city) in cities.areas_and_cities.iter() {
+                if !snapshot.cities.has_city_in(*area) {
+                    self.commands
+                        .entity(*city)
+                        .remove::<(Sprite, Transform, Visibility, TokenTween)>();
+                }
+            }
+            *stock = snapshot.stock.clone();
+            *city_stock = snapshot.city_stock.clone();
+            *cities = snapshot.cities.clone();
+
+            match (&snapshot.needs_expansion, needs_expansion) {
+                (Some(needed), Some(mut needs_expansion)) => {
+                    needs_expansion.areas_that_need_expansion = needed.clone();
+                }
+                (Some(needed), None) => {
+                    self.commands
+                        .entity(snapshot.player)
+                        .insert(NeedsExpansion::new(needed.clone()));
+                }
+                (None, Some(_)) => {
+                    self.commands
+                        .entity(snapshot.player)
+                        .remove::<NeedsExpansion>();
+                }
+                (None, None) => {}
+            }
+            if snapshot.expand_manually && !expand_manually {
+                self.commands.entity(snapshot.player).insert(ExpandManually);
+            }
+        }
+
+        let player = step.command.player();
+        for (token, owner, moved, _) in self.tokens.iter() {
+            if owner.player() != player {
+                continue;
+            }
+            let had_moved = before.moved_tokens.contains(&token);
+            if had_moved && !moved {
+                self.commands.entity(token).insert(TokenHasMoved);
+            } else if !had_moved && moved {
+                self.commands.entity(token).remove::<TokenHasMoved>();
+            }
+        }
+        self.recalculate.write(RecalculatePlayerMoves::new(player));
+    }
+}
+
+/// Sends a command again when it is redone.
+#[derive(SystemParam)]
+pub struct UndoReplay<'w> {
+    expand: MessageWriter<'w, ExpandPopulationManuallyCommand>,
+    move_tokens: MessageWriter<'w, MoveTokenFromAreaToAreaCommand>,
+    build_city: MessageWriter<'w, BuildCityCommand>,
+}
+
+impl UndoReplay<'_> {
+    pub fn send(&mut self, command: UndoableCommand) {
+        match command {
+            UndoableCommand::Expand {
+                player,
+                area,
+                number_of_tokens,
+            } => {
+                self.expand.write(ExpandPopulationManuallyCommand::new(
+                    player,
+                    area,
+                    number_of_tokens,
+                ));
+            }
+            UndoableCommand::Move {
+                player,
+                source_area,
+                target_area,
+                number_of_tokens,
+            } => {
+                self.move_tokens.write(MoveTokenFromAreaToAreaCommand::new(
+                    source_area,
+                    target_area,
+                    number_of_tokens,
+                    player,
+                ));
+            }
+            UndoableCommand::BuildCity { player, area } => {
+                self.build_city.write(BuildCityCommand::new(player, area));
+            }
+        }
+    }
+}
+
+/// Takes a snapshot before a human's command is carried out. Commands from anyone else end
+/// the human's chance to take theirs back.
+pub fn record_human_commands(
+    mut expand: MessageReader<ExpandPopulationManuallyCommand>,
+    mut move_tokens: MessageReader<MoveTokenFromAreaToAreaCommand>,
+    mut build_city: MessageReader<BuildCityCommand>,
+    humans: Query<(), With<IsHuman>>,
+    board: UndoBoard,
+    mut history: ResMut<UndoHistory>,
+) {
+    let commands = expand
+        .read()
+        .map(|command| UndoableCommand::Expand {
+            player: command.player,
+            area: command.area,
+            number_of_tokens: command.number_of_tokens,
+        })
+        .chain(move_tokens.read().map(|command| UndoableCommand::Move {
+            player: command.player,
+            source_area: command.source_area,
+            target_area: command.target_area,
+            number_of_tokens: command.number_of_tokens,
+        }))
+        .chain(build_city.read().map(|command| UndoableCommand::BuildCity {
+            player: command.player,
+            area: command.area,
+        }))
+        .collect::<Vec<_>>();
+
+    for command in commands {
+        if !humans.contains(command.player()) {
+            history.someone_acted(command.player());
+            continue;
+        }
+        let before = board.snapshot(&command);
+        history.record(UndoStep { command, before });
+    }
+}
+
+/// A new phase, or anyone ending their movement or city building, commits what was done.
+pub fn forget_committed_moves(
+    mut phase_changes: MessageReader<StateTransitionEvent<GameActivity>>,
+    mut movement_ended: MessageReader<PlayerMovementEnded>,
+    mut building_ended: MessageReader<EndPlayerCityConstruction>,
+    mut history: ResMut<UndoHistory>,
+) {
+    let committed = phase_changes.read().count()
+        + movement_ended.read().count()
+        + building_ended.read().count();
+    if committed > 0 {
+        history.forget();
+    }
+}
+
+/// Ctrl+Z takes the last command back, Ctrl+Y or Ctrl+Shift+Z plays it again.
+pub fn undo_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut history: ResMut<UndoHistory>,
+    active_seat: Res<ActiveSeat>,
+    mut board: UndoBoard,
+    mut replay: UndoReplay,
+) {
+    if !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    // With several humans on the device only the one holding it may take their moves back
+    let Some(player) = history.player else {
+        return;
+    };
+    if !active_seat.may_see(player) {
+        return;
+    }
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keyboard_input.just_pressed(KeyCode::KeyZ) && !shift {
+        if let Some(step) = history.undo() {
+            board.restore(&step);
+        }
+    } else if keyboard_input.just_pressed(KeyCode::KeyY)
+        || (keyboard_input.just_pressed(KeyCode::KeyZ) && shift)
+    {
+        if let Some(command) = history.redo() {
+            replay.send(command);
+        }
+    }
+}
@@ -1,35 +1,54 @@
-// use bevy_console::ConsoleCommand;
-// use bevy::prelude::{Entity, MessageWriter, Query, With};
-// use bevy::core::Name;
-// use clap::Parser;
-// use crate::civilization::components::city_construction_components::CityBuildTargets;
-// use crate::civilization::events::city_construction_events::BuildCityCommand;
-// use crate::player::Player;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "bc")]
-// pub struct BuildCityConsoleCommand {
-//     player: String,
-//     area: String,
-// }
-// 
-// pub fn build_city(
-//     mut command: ConsoleCommand<BuildCityConsoleCommand>,
-//     player_query: Query<(Entity, &Name, &CityBuildTargets), With<Player>>,
-//     name_query: Query<&Name>,
-//     mut build_city: MessageWriter<BuildCityCommand>,
-// ) {
-//     if let Some(Ok(BuildCityConsoleCommand { player, area })) = command.take() {
-//         if let Some((player_entity, targets)) = player_query.iter().find(|(_, name, _)| **name == Name::from(player.clone())).map(|(entity, _, targets)| (entity, targets)) {
-//             if let Some(target_entity) = targets.targets.iter().filter(|t| *name_query.get(**t).unwrap() == Name::from(area.clone())).next() {
-//                 build_city.write(BuildCityCommand {
-//                     player: player_entity,
-//                     area: *target_entity,
-//                 });
-//                 command.reply("Building City");
-//             } else {
-//                 command.reply("Could not find target area");
-//             }
-//         }
-//     }
-// }
\ No newline at end of file
+use crate::civilization::concepts::city_construction::city_construction_events::BuildCityCommand;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use bevy::prelude::{MessageWriter, Query};
+
+pub struct BuildCityConsoleCommand {
+    player: String,
+    area: String,
+}
+
+impl TypedCommand for BuildCityConsoleCommand {
+    const NAME: &'static str = "build";
+    const USAGE: &'static str = "build <player> <area>";
+    const HELP: &'static str = "builds a city where builds says the player can";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(BuildCityConsoleCommand {
+            player: args.text("player")?,
+            area: args.text("area")?,
+        })
+    }
+}
+
+pub fn build_city(
+    mut command: ConsoleCommand<BuildCityConsoleCommand>,
+    available_moves: Query<&AvailableMoves>,
+    lookup: ConsoleLookup,
+    mut build_city: MessageWriter<BuildCityCommand>,
+) {
+    if let Some(BuildCityConsoleCommand { player, area }) = command.take() {
+        let (player, area) = match lookup
+            .player(&player)
+            .and_then(|player| lookup.area(&area).map(|area| (player, area)))
+        {
+            Ok(found) => found,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        let can_build = available_moves.get(player).is_ok_and(|avail_moves| {
+            avail_moves.moves.values().any(|game_move| {
+                matches!(game_move, Move::CityConstruction(build_city_move) if build_city_move.target == area)
+            })
+        });
+        if can_build {
+            build_city.write(BuildCityCommand::new(player, area));
+            command.reply("Building City");
+        } else {
+            command.reply("Could not find target area");
+        }
+    }
+}
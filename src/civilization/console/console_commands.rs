@@ -1,38 +1,73 @@
-// use bevy::app::{App, Plugin};
-// use bevy_console::{AddConsoleCommand, ConsoleConfiguration, ConsolePlugin};
-// use crate::civilization::console::{build_city_console_command, expand_population_command, list_builds_command, list_moves_command, make_a_move_command, player_end_building_command, show_board_command, start_command, stupid_ai_command};
-// use crate::civilization::console::build_city_console_command::BuildCityConsoleCommand;
-// use crate::civilization::console::expand_population_command::ExpandPopulation;
-// use crate::civilization::console::list_builds_command::ListBuildsCommand;
-// use crate::civilization::console::list_moves_command::ListMoves;
-// use crate::civilization::console::make_a_move_command::MakeAMove;
-// use crate::civilization::console::player_end_building_command::PlayerEndBuildingCommand;
-// use crate::civilization::console::show_board_command::ShowBoardCommand;
-// use crate::civilization::console::start_command::StartCommand;
-// use crate::civilization::console::stupid_ai_command::StupidAiCommand;
-// pub struct CommandsPlugin;
-// 
-// /// This plugin handles player related stuff like movement
-// /// Player logic is only active during the State `GameState::Playing`
-// impl Plugin for CommandsPlugin {
-//     fn build(&self, app: &mut App) {
-//         app
-//             .add_plugins(ConsolePlugin)
-//             .insert_resource(ConsoleConfiguration {
-//                 // override config here
-//                 ..Default::default()
-//             })
-//             .add_console_command::<StartCommand, _>(start_command::start_command)
-//             .add_console_command::<ExpandPopulation, _>(expand_population_command::expand_population)
-//             .add_console_command::<ListMoves, _>(list_moves_command::list_moves)
-//             .add_console_command::<MakeAMove, _>(make_a_move_command::make_a_move)
-//             .add_console_command::<ShowBoardCommand, _>(show_board_command::show_board)
-//             .add_console_command::<ListBuildsCommand, _>(list_builds_command::list_builds)
-//             .add_console_command::<BuildCityConsoleCommand, _>(build_city_console_command::build_city)
-//             .add_console_command::<PlayerEndBuildingCommand, _>(player_end_building_command::end_building)
-//             .add_console_command::<StupidAiCommand, _>(stupid_ai_command::stupid_ai)
-//         ;
-//     }
-// }
-// 
-// 
+use crate::civilization::console::build_city_console_command::BuildCityConsoleCommand;
+use crate::civilization::console::console_events::ConsoleLine;
+use crate::civilization::console::console_resources::{ConsoleLog, ConsoleRegistry, ConsoleState};
+use crate::civilization::console::console_systems::*;
+use crate::civilization::console::dump_player_command::DumpPlayerCommand;
+use crate::civilization::console::expand_population_command::ExpandPopulation;
+use crate::civilization::console::give_trade_card_command::GiveTradeCardCommand;
+use crate::civilization::console::list_builds_command::ListBuildsCommand;
+use crate::civilization::console::list_moves_command::ListMoves;
+use crate::civilization::console::make_a_move_command::MakeAMove;
+use crate::civilization::console::player_end_building_command::PlayerEndBuildingCommand;
+use crate::civilization::console::rules_command::RulesCommand;
+use crate::civilization::console::set_phase_command::SetPhaseCommand;
+use crate::civilization::console::show_board_command::ShowBoardCommand;
+use crate::civilization::console::spawn_tokens_command::SpawnTokensCommand;
+use crate::civilization::console::start_command::StartCommand;
+use crate::civilization::console::stupid_ai_command::StupidAiCommand;
+use crate::civilization::console::toggle_controller_command::ToggleControllerCommand;
+use crate::civilization::console::{
+    build_city_console_command, dump_player_command, expand_population_command,
+    give_trade_card_command, list_builds_command, list_moves_command, make_a_move_command,
+    player_end_building_command, rules_command, set_phase_command, show_board_command,
+    spawn_tokens_command, start_command, stupid_ai_command, toggle_controller_command,
+};
+use crate::GameState;
+use bevy::app::{App, Plugin, PreUpdate, Update};
+use bevy::input::InputSystems;
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter};
+
+pub struct CommandsPlugin;
+
+/// A developer console, opened with the backquote key, for setting up test situations.
+impl Plugin for CommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleState>()
+            .init_resource::<ConsoleLog>()
+            .init_resource::<ConsoleRegistry>()
+            .add_message::<ConsoleLine>()
+            .add_systems(OnEnter(GameState::Playing), setup_console)
+            .add_systems(PreUpdate, swallow_keys_while_typing.after(InputSystems))
+            .add_systems(
+                Update,
+                (console_input, refresh_console)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_console_command::<StartCommand, _>(start_command::start_command)
+            .add_console_command::<ExpandPopulation, _>(
+                expand_population_command::expand_population,
+            )
+            .add_console_command::<ListMoves, _>(list_moves_command::list_moves)
+            .add_console_command::<MakeAMove, _>(make_a_move_command::make_a_move)
+            .add_console_command::<ShowBoardCommand, _>(show_board_command::show_board)
+            .add_console_command::<ListBuildsCommand, _>(list_builds_command::list_builds)
+            .add_console_command::<BuildCityConsoleCommand, _>(
+                build_city_console_command::build_city,
+            )
+            .add_console_command::<PlayerEndBuildingCommand, _>(
+                player_end_building_command::end_building,
+            )
+            .add_console_command::<StupidAiCommand, _>(stupid_ai_command::stupid_ai)
+            .add_console_command::<GiveTradeCardCommand, _>(
+                give_trade_card_command::give_trade_card,
+            )
+            .add_console_command::<SetPhaseCommand, _>(set_phase_command::set_phase)
+            .add_console_command::<SpawnTokensCommand, _>(spawn_tokens_command::spawn_tokens)
+            .add_console_command::<DumpPlayerCommand, _>(dump_player_command::dump_player)
+            .add_console_command::<ToggleControllerCommand, _>(
+                toggle_controller_command::toggle_controller,
+            )
+            .add_console_command::<RulesCommand, _>(rules_command::rules);
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component, Default)]
+pub struct ConsoleRoot;
+
+#[derive(Component, Default)]
+pub struct ConsoleText;
@@ -0,0 +1,8 @@
+use bevy::prelude::Message;
+
+/// A line typed into the console for a known command, split into its arguments.
+#[derive(Message, Debug, Clone)]
+pub struct ConsoleLine {
+    pub name: String,
+    pub args: Vec<String>,
+}
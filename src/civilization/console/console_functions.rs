@@ -0,0 +1,203 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::phase_tracker::phase_tracker_systems::ROUND_PHASES;
+use crate::civilization::concepts::rules::rules_resources::RuleVariant;
+use crate::civilization::enums::AiDifficulty;
+use crate::GameActivity;
+use std::collections::VecDeque;
+
+/// Splits a console line on whitespace. Double quotes keep names with spaces together.
+pub fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return Err("missing closing quote".to_string());
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// The arguments of a command, taken one at a time in the order the command expects them.
+#[derive(Debug, Default)]
+pub struct ConsoleArgs {
+    words: VecDeque<String>,
+}
+
+impl ConsoleArgs {
+    pub fn new(words: Vec<String>) -> Self {
+        Self {
+            words: words.into(),
+        }
+    }
+
+    pub fn text(&mut self, name: &str) -> Result<String, String> {
+        self.words
+            .pop_front()
+            .ok_or_else(|| format!("missing <{}>", name))
+    }
+
+    pub fn optional_text(&mut self) -> Option<String> {
+        self.words.pop_front()
+    }
+
+    pub fn number(&mut self, name: &str) -> Result<usize, String> {
+        let word = self.text(name)?;
+        word.parse()
+            .map_err(|_| format!("<{}> must be a number, not {}", name, word))
+    }
+
+    pub fn optional_number(&mut self, name: &str) -> Result<Option<usize>, String> {
+        if self.words.is_empty() {
+            return Ok(None);
+        }
+        self.number(name).map(Some)
+    }
+
+    /// Fails when there are arguments left that the command did not ask for.
+    pub fn finish(&self) -> Result<(), String> {
+        match self.words.front() {
+            Some(word) => Err(format!("unexpected argument {}", word)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Names like `p_Crete_0` or `12:Thrace` can be typed whole or by any of their parts.
+pub fn matches_name(name: &str, typed: &str) -> bool {
+    name.eq_ignore_ascii_case(typed)
+        || name
+            .split(['_', ':'])
+            .any(|part| part.eq_ignore_ascii_case(typed))
+}
+
+pub fn parse_trade_card(typed: &str) -> Result<TradeCard, String> {
+    TradeCard::iter()
+        .find(|card| {
+            format!("{:?}", card).eq_ignore_ascii_case(typed)
+                || card.to_string().eq_ignore_ascii_case(typed)
+        })
+        .ok_or_else(|| format!("there is no trade card called {}", typed))
+}
+
+pub fn parse_phase(typed: &str) -> Result<GameActivity, String> {
+    std::iter::once(GameActivity::StartGame)
+        .chain(ROUND_PHASES)
+        .find(|phase| format!("{:?}", phase).eq_ignore_ascii_case(typed))
+        .ok_or_else(|| {
+            format!(
+                "there is no phase called {}, try one of {:?}",
+                typed, ROUND_PHASES
+            )
+        })
+}
+
+/// Who makes the moves for a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    Human,
+    Ai(AiDifficulty),
+}
+
+pub fn parse_controller(typed: &str) -> Result<Controller, String> {
+    match typed.to_ascii_lowercase().as_str() {
+        "human" => Ok(Controller::Human),
+        "stupid" => Ok(Controller::Ai(AiDifficulty::Stupid)),
+        "heuristic" => Ok(Controller::Ai(AiDifficulty::Heuristic)),
+        "hard" | "mcts" => Ok(Controller::Ai(AiDifficulty::Hard)),
+        "external" | "bot" => Ok(Controller::Ai(AiDifficulty::External)),
+        _ => Err(format!(
+            "there is no controller called {}, try human, stupid, heuristic, hard or bot",
+            typed
+        )),
+    }
+}
+
+pub fn parse_rule_variant(typed: &str) -> Result<RuleVariant, String> {
+    match typed.to_ascii_lowercase().as_str() {
+        "civ" | "civilization" => Ok(RuleVariant::Civilization),
+        "advanced" | "advancedcivilization" => Ok(RuleVariant::AdvancedCivilization),
+        "house" | "houserules" => Ok(RuleVariant::HouseRules),
+        _ => Err(format!(
+            "there are no rules called {}, try civilization, advanced or house",
+            typed
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotes_keep_words_together() {
+        let words = split_arguments(r#"spawn Crete "Asia Minor"  3"#).unwrap();
+        assert_eq!(words, vec!["spawn", "Crete", "Asia Minor", "3"]);
+        assert!(split_arguments(r#"build "Crete"#).is_err());
+    }
+
+    #[test]
+    fn test_arguments_are_typed() {
+        let mut args = ConsoleArgs::new(vec!["Crete".to_string(), "two".to_string()]);
+        assert_eq!(args.text("player").unwrap(), "Crete");
+        assert_eq!(
+            args.number("tokens").unwrap_err(),
+            "<tokens> must be a number, not two"
+        );
+        assert_eq!(args.optional_number("tokens").unwrap(), None);
+        assert!(args.finish().is_ok());
+
+        let args = ConsoleArgs::new(vec!["extra".to_string()]);
+        assert_eq!(args.finish().unwrap_err(), "unexpected argument extra");
+    }
+
+    #[test]
+    fn test_names_match_by_any_part() {
+        assert!(matches_name("p_Crete_0", "crete"));
+        assert!(matches_name("12:Thrace", "12"));
+        assert!(matches_name("12:Thrace", "12:thrace"));
+        assert!(!matches_name("p_Crete_0", "Cret"));
+    }
+
+    #[test]
+    fn test_cards_and_phases_parse_ignoring_case() {
+        assert_eq!(parse_trade_card("ochre"), Ok(TradeCard::Ochre));
+        assert!(parse_trade_card("gold bars").is_err());
+        assert_eq!(parse_phase("census"), Ok(GameActivity::Census));
+        assert_eq!(parse_phase("StartGame"), Ok(GameActivity::StartGame));
+        assert_eq!(
+            parse_controller("MCTS"),
+            Ok(Controller::Ai(AiDifficulty::Hard))
+        );
+    }
+
+    #[test]
+    fn test_rule_variants_parse_by_short_name() {
+        assert_eq!(
+            parse_rule_variant("Advanced"),
+            Ok(RuleVariant::AdvancedCivilization)
+        );
+        assert_eq!(parse_rule_variant("civ"), Ok(RuleVariant::Civilization));
+        assert!(parse_rule_variant("monopoly").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::Resource;
+use std::collections::BTreeMap;
+
+/// How many lines of output the console keeps around.
+pub const CONSOLE_LOG_LINES: usize = 200;
+
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub history: Vec<String>,
+    pub history_index: Option<usize>,
+}
+
+impl ConsoleState {
+    pub fn submit(&mut self) -> String {
+        let line = std::mem::take(&mut self.input);
+        if !line.trim().is_empty() {
+            self.history.push(line.clone());
+        }
+        self.history_index = None;
+        line
+    }
+
+    pub fn previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = self
+            .history_index
+            .map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    pub fn next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.input = self.history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleLog {
+    pub lines: Vec<String>,
+}
+
+impl ConsoleLog {
+    pub fn push(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+        if self.lines.len() > CONSOLE_LOG_LINES {
+            let surplus = self.lines.len() - CONSOLE_LOG_LINES;
+            self.lines.drain(..surplus);
+        }
+    }
+}
+
+/// Usage and help for every command the console knows, by name.
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleRegistry {
+    pub commands: BTreeMap<&'static str, (&'static str, &'static str)>,
+}
@@ -0,0 +1,246 @@
+use crate::civilization::components::population::MaxPopulation;
+use crate::civilization::console::console_components::{ConsoleRoot, ConsoleText};
+use crate::civilization::console::console_events::ConsoleLine;
+use crate::civilization::console::console_functions::{matches_name, split_arguments, ConsoleArgs};
+use crate::civilization::console::console_resources::{ConsoleLog, ConsoleRegistry, ConsoleState};
+use crate::civilization::ui::ui_builder::BG_COLOR;
+use crate::player::Player;
+use crate::GameState;
+use bevy::app::{App, Update};
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::{ScheduleSystem, SystemParam};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::{
+    default, in_state, BackgroundColor, ButtonInput, Commands, DetectChanges, Entity, GlobalZIndex,
+    IntoScheduleConfigs, KeyCode, MessageReader, MessageWriter, Name, Node, PositionType, Query,
+    Res, ResMut, Text, TextFont, UiRect, Val, Visibility, With,
+};
+use std::marker::PhantomData;
+
+/// How many lines of output fit on the console.
+const VISIBLE_LINES: usize = 16;
+
+/// A console command and the typed arguments it takes.
+pub trait TypedCommand: Sized + Send + Sync + 'static {
+    const NAME: &'static str;
+    const USAGE: &'static str;
+    const HELP: &'static str;
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String>;
+}
+
+/// The lines typed for one command, parsed. Lines that do not parse are answered with the
+/// command's usage.
+#[derive(SystemParam)]
+pub struct ConsoleCommand<'w, 's, T: TypedCommand> {
+    lines: MessageReader<'w, 's, ConsoleLine>,
+    log: ResMut<'w, ConsoleLog>,
+    command: PhantomData<T>,
+}
+
+impl<T: TypedCommand> ConsoleCommand<'_, '_, T> {
+    pub fn take(&mut self) -> Option<T> {
+        for line in self.lines.read() {
+            if line.name != T::NAME {
+                continue;
+            }
+            let mut args = ConsoleArgs::new(line.args.clone());
+            match T::parse(&mut args).and_then(|command| args.finish().map(|_| command)) {
+                Ok(command) => return Some(command),
+                Err(err) => self.log.push(format!("{}, usage: {}", err, T::USAGE)),
+            }
+        }
+        None
+    }
+
+    pub fn reply(&mut self, line: impl Into<String>) {
+        self.log.push(line);
+    }
+}
+
+pub trait AddConsoleCommand {
+    fn add_console_command<T: TypedCommand, M>(
+        &mut self,
+        system: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> &mut Self;
+}
+
+impl AddConsoleCommand for App {
+    fn add_console_command<T: TypedCommand, M>(
+        &mut self,
+        system: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> &mut Self {
+        self.init_resource::<ConsoleRegistry>();
+        self.world_mut()
+            .resource_mut::<ConsoleRegistry>()
+            .commands
+            .insert(T::NAME, (T::USAGE, T::HELP));
+        let system: ScheduleConfigs<ScheduleSystem> =
+            system.into_configs().run_if(in_state(GameState::Playing));
+        self.add_systems(Update, system)
+    }
+}
+
+/// Finds players and areas by the names typed into the console.
+#[derive(SystemParam)]
+pub struct ConsoleLookup<'w, 's> {
+    players: Query<'w, 's, (Entity, &'static Name), With<Player>>,
+    areas: Query<'w, 's, (Entity, &'static Name), With<MaxPopulation>>,
+    names: Query<'w, 's, &'static Name>,
+}
+
+impl ConsoleLookup<'_, '_> {
+    pub fn player(&self, typed: &str) -> Result<Entity, String> {
+        self.players
+            .iter()
+            .find(|(_, name)| matches_name(name.as_str(), typed))
+            .map(|(player, _)| player)
+            .ok_or_else(|| format!("there is no player called {}", typed))
+    }
+
+    pub fn area(&self, typed: &str) -> Result<Entity, String> {
+        self.areas
+            .iter()
+            .find(|(_, name)| matches_name(name.as_str(), typed))
+            .map(|(area, _)| area)
+            .ok_or_else(|| format!("there is no area called {}", typed))
+    }
+
+    pub fn name_of(&self, entity: Entity) -> String {
+        self.names
+            .get(entity)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|_| format!("{}", entity))
+    }
+}
+
+pub fn setup_console(mut commands: Commands) {
+    commands
+        .spawn((
+            ConsoleRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.),
+                left: Val::Px(0.),
+                width: Val::Percent(100.),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(BG_COLOR),
+            GlobalZIndex(50),
+            Visibility::Hidden,
+        ))
+        .with_child((
+            ConsoleText,
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+        ));
+}
+
+/// Backquote opens and closes the console, Enter runs the line, the arrow keys go through
+/// earlier lines.
+pub fn console_input(
+    mut keys: MessageReader<KeyboardInput>,
+    mut state: ResMut<ConsoleState>,
+    mut log: ResMut<ConsoleLog>,
+    registry: Res<ConsoleRegistry>,
+    mut lines: MessageWriter<ConsoleLine>,
+) {
+    for key in keys.read() {
+        if !key.state.is_pressed() {
+            continue;
+        }
+        if key.key_code == KeyCode::Backquote {
+            state.open = !state.open;
+            continue;
+        }
+        if !state.open {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Enter => {
+                let line = state.submit();
+                log.push(format!("> {}", line));
+                run_line(&line, &registry, &mut log, &mut lines);
+            }
+            Key::Backspace => {
+                state.input.pop();
+            }
+            Key::Escape => state.open = false,
+            Key::ArrowUp => state.previous(),
+            Key::ArrowDown => state.next(),
+            _ => {
+                if let Some(text) = &key.text {
+                    state.input.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+}
+
+fn run_line(
+    line: &str,
+    registry: &ConsoleRegistry,
+    log: &mut ConsoleLog,
+    lines: &mut MessageWriter<ConsoleLine>,
+) {
+    let mut words = match split_arguments(line) {
+        Ok(words) => words,
+        Err(err) => {
+            log.push(err);
+            return;
+        }
+    };
+    if words.is_empty() {
+        return;
+    }
+    let name = words.remove(0);
+    match name.as_str() {
+        "help" => {
+            for (usage, help) in registry.commands.values() {
+                log.push(format!("{} - {}", usage, help));
+            }
+        }
+        "clear" => log.lines.clear(),
+        _ if registry.commands.contains_key(name.as_str()) => {
+            lines.write(ConsoleLine { name, args: words });
+        }
+        _ => log.push(format!("Unknown command {}, try help", name)),
+    }
+}
+
+/// Keeps typing into the console from also steering the game.
+pub fn swallow_keys_while_typing(
+    state: Res<ConsoleState>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    if state.open {
+        keyboard_input.reset_all();
+    }
+}
+
+pub fn refresh_console(
+    state: Res<ConsoleState>,
+    log: Res<ConsoleLog>,
+    mut root: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut text: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !state.is_changed() && !log.is_changed() {
+        return;
+    }
+    let (Ok(mut visibility), Ok(mut text)) = (root.single_mut(), text.single_mut()) else {
+        return;
+    };
+    *visibility = if state.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    let skip = log.lines.len().saturating_sub(VISIBLE_LINES);
+    let mut lines = log.lines.iter().skip(skip).cloned().collect::<Vec<_>>();
+    lines.push(format!("> {}_", state.input));
+    text.0 = lines.join("\n");
+}
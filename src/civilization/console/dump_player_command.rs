@@ -0,0 +1,126 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::*;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::hot_seat::hot_seat_functions::describe_cards;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::external_bot::external_bot_components::ExternalBot;
+use crate::heuristic_ai::heuristic_ai_components::HeuristicAi;
+use crate::mcts_ai::mcts_ai_components::MctsAi;
+use crate::stupid_ai::prelude::{IsHuman, StupidAi};
+use bevy::ecs::query::QueryData;
+use bevy::prelude::{Entity, Has, Name, Query};
+
+pub struct DumpPlayerCommand {
+    player: String,
+}
+
+impl TypedCommand for DumpPlayerCommand {
+    const NAME: &'static str = "player";
+    const USAGE: &'static str = "player <player>";
+    const HELP: &'static str = "prints everything about a player";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(DumpPlayerCommand {
+            player: args.text("player")?,
+        })
+    }
+}
+
+#[derive(QueryData)]
+pub struct PlayerState {
+    entity: Entity,
+    name: &'static Name,
+    faction: &'static Faction,
+    stock: &'static TokenStock,
+    city_stock: &'static CityTokenStock,
+    cities: &'static PlayerCities,
+    trade_cards: &'static PlayerTradeCards,
+    available_moves: Option<&'static AvailableMoves>,
+    is_human: Has<IsHuman>,
+    stupid_ai: Has<StupidAi>,
+    heuristic_ai: Has<HeuristicAi>,
+    mcts_ai: Has<MctsAi>,
+    external_bot: Has<ExternalBot>,
+}
+
+impl PlayerStateItem<'_, '_> {
+    fn controller(&self) -> &'static str {
+        if self.is_human {
+            "human"
+        } else if self.external_bot {
+            "external bot"
+        } else if self.mcts_ai {
+            "hard AI"
+        } else if self.heuristic_ai {
+            "heuristic AI"
+        } else if self.stupid_ai {
+            "stupid AI"
+        } else {
+            "nobody"
+        }
+    }
+}
+
+pub fn dump_player(
+    mut command: ConsoleCommand<DumpPlayerCommand>,
+    lookup: ConsoleLookup,
+    players: Query<PlayerState>,
+    populations: Populations,
+) {
+    if let Some(DumpPlayerCommand { player }) = command.take() {
+        let state = match lookup
+            .player(&player)
+            .and_then(|player| players.get(player).map_err(|err| err.to_string()))
+        {
+            Ok(state) => state,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        command.reply(format!(
+            "{} ({:?}) is played by {}",
+            state.name,
+            state.faction.faction,
+            state.controller()
+        ));
+        let player_areas = populations.player_areas(state.entity);
+        command.reply(format!(
+            "Tokens: {} on the board, {} of {} in stock",
+            player_areas.total_population(),
+            state.stock.tokens_in_stock(),
+            state.stock.max_tokens
+        ));
+        let mut areas = player_areas
+            .areas_and_population_count()
+            .into_iter()
+            .map(|(area, tokens)| format!("{} ({})", lookup.name_of(area), tokens))
+            .collect::<Vec<_>>();
+        areas.sort();
+        command.reply(format!("Areas: {}", areas.join(", ")));
+        let mut cities = state
+            .cities
+            .areas_and_cities
+            .keys()
+            .map(|area| lookup.name_of(*area))
+            .collect::<Vec<_>>();
+        cities.sort();
+        command.reply(format!(
+            "Cities: {}, {} in stock",
+            cities.join(", "),
+            state.city_stock.tokens_in_stock()
+        ));
+        command.reply(format!(
+            "Trade cards: {}",
+            describe_cards(state.trade_cards.cards())
+        ));
+        command.reply(format!(
+            "Available moves: {}",
+            state
+                .available_moves
+                .map_or(0, |available_moves| available_moves.moves.len())
+        ));
+    }
+}
@@ -1,18 +1,26 @@
-// use bevy_console::ConsoleCommand;
-// use bevy::prelude::{NextState, ResMut};
-// use clap::Parser;
-// use crate::GameActivity;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "popexp")]
-// pub struct ExpandPopulation;
-// 
-// pub fn expand_population(
-//     mut command: ConsoleCommand<ExpandPopulation>,
-//     mut next_state: ResMut<NextState<GameActivity>>,
-// ) {
-//     if let Some(Ok(ExpandPopulation {})) = command.take() {
-//         next_state.set(GameActivity::PopulationExpansion);
-//         command.reply("We are starting the expansion!")
-//     }
-// }
\ No newline at end of file
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, TypedCommand};
+use crate::GameActivity;
+use bevy::prelude::{NextState, ResMut};
+
+pub struct ExpandPopulation;
+
+impl TypedCommand for ExpandPopulation {
+    const NAME: &'static str = "popexp";
+    const USAGE: &'static str = "popexp";
+    const HELP: &'static str = "starts population expansion";
+
+    fn parse(_args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(ExpandPopulation)
+    }
+}
+
+pub fn expand_population(
+    mut command: ConsoleCommand<ExpandPopulation>,
+    mut next_state: ResMut<NextState<GameActivity>>,
+) {
+    if let Some(ExpandPopulation) = command.take() {
+        next_state.set(GameActivity::PopulationExpansion);
+        command.reply("We are starting the expansion!")
+    }
+}
@@ -0,0 +1,54 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::console::console_functions::{parse_trade_card, ConsoleArgs};
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use bevy::prelude::Query;
+
+pub struct GiveTradeCardCommand {
+    player: String,
+    card: TradeCard,
+    count: usize,
+}
+
+impl TypedCommand for GiveTradeCardCommand {
+    const NAME: &'static str = "give";
+    const USAGE: &'static str = "give <player> <card> [count]";
+    const HELP: &'static str = "hands a player trade cards, without taking them from the piles";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(GiveTradeCardCommand {
+            player: args.text("player")?,
+            card: parse_trade_card(&args.text("card")?)?,
+            count: args.optional_number("count")?.unwrap_or(1),
+        })
+    }
+}
+
+pub fn give_trade_card(
+    mut command: ConsoleCommand<GiveTradeCardCommand>,
+    lookup: ConsoleLookup,
+    mut trade_cards: Query<&mut PlayerTradeCards>,
+) {
+    if let Some(GiveTradeCardCommand {
+        player,
+        card,
+        count,
+    }) = command.take()
+    {
+        match lookup
+            .player(&player)
+            .and_then(|player| trade_cards.get_mut(player).map_err(|err| err.to_string()))
+        {
+            Ok(mut player_cards) => {
+                player_cards.add_trade_cards(card, count);
+                command.reply(format!(
+                    "{} now has {} {}",
+                    player,
+                    player_cards.number_of_cards_for_trade_card(card),
+                    card
+                ));
+            }
+            Err(err) => command.reply(err),
+        }
+    }
+}
@@ -1,22 +1,40 @@
-// use clap::Parser;
-// use bevy_console::ConsoleCommand;
-// use bevy::prelude::Query;
-// use bevy::core::Name;
-// use crate::civilization::components::city_construction_components::CityBuildTargets;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "builds")]
-// pub struct ListBuildsCommand;
-// 
-// pub fn list_builds(
-//     mut command: ConsoleCommand<ListBuildsCommand>,
-//     player_query: Query<(&Name, &CityBuildTargets)>,
-//     name_query: Query<&Name>,
-// ) {
-//     if let Some(Ok(ListBuildsCommand {})) = command.take() {
-//         for (player_name, targets) in player_query.iter() {
-//             let target_names = targets.targets.iter().map(|target| name_query.get(*target).unwrap().as_str()).collect::<Vec<&str>>().join(", ");
-//             command.reply(format!("{:#?} can build in: {:#?}", player_name, target_names));
-//         }
-//     }
-// }
\ No newline at end of file
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use bevy::prelude::{Name, Query};
+
+pub struct ListBuildsCommand;
+
+impl TypedCommand for ListBuildsCommand {
+    const NAME: &'static str = "builds";
+    const USAGE: &'static str = "builds";
+    const HELP: &'static str = "lists where players can build cities";
+
+    fn parse(_args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(ListBuildsCommand)
+    }
+}
+
+pub fn list_builds(
+    mut command: ConsoleCommand<ListBuildsCommand>,
+    available_moves: Query<(&Name, &AvailableMoves)>,
+    lookup: ConsoleLookup,
+) {
+    if let Some(ListBuildsCommand) = command.take() {
+        for (name, avail_moves) in available_moves.iter() {
+            let targets = avail_moves
+                .moves
+                .values()
+                .filter_map(|game_move| match game_move {
+                    Move::CityConstruction(build_city_move) => {
+                        Some(lookup.name_of(build_city_move.target))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if !targets.is_empty() {
+                command.reply(format!("{} can build in: {}", name, targets.join(", ")));
+            }
+        }
+    }
+}
@@ -1,60 +1,68 @@
-// use crate::civilization::components::game_moves_components::{AvailableMoves, Move};
-// use bevy::core::Name;
-// use bevy::prelude::Query;
-// use bevy_console::ConsoleCommand;
-// use clap::Parser;
-// use itertools::Itertools;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "moves")]
-// pub struct ListMoves;
-// 
-// pub fn list_moves(
-//     mut command: ConsoleCommand<ListMoves>,
-//     available_moves: Query<(&Name, &AvailableMoves)>,
-//     name_query: Query<&Name>,
-// ) {
-//     if let Some(Ok(ListMoves {})) = command.take() {
-//         for (name, avail_moves) in available_moves.iter() {
-//             command.reply(format!("Player {} can perform the following moves", name));
-// 
-//             avail_moves.moves.iter().sorted_by(|(a,_),(b,_)|Ord::cmp(a,b)).for_each(|(index, game_move)| {
-//                 match game_move {
-//                     Move::PopulationExpansion(pop_exp_move) => {
-//                         let area_name = name_query.get(pop_exp_move.area).unwrap();
-//                         command.reply(format!("{} - Expand Population in {} with max {}", index, area_name, pop_exp_move.max_tokens));
-//                     }
-//                     Move::Movement(movement_move) => {
-//                         let source_name = name_query.get(movement_move.source).unwrap();
-//                         let target_name = name_query.get(movement_move.target).unwrap();
-//                         command.reply(format!("{} - Move max {} tokens from {} to {}", index, movement_move.max_tokens, source_name, target_name));
-//                     }
-//                     Move::EndMovement => {
-//                         command.reply(format!("{} - End Current Player Movement", index));
-//                     }
-//                     Move::CityConstruction(city_build_move) => {
-//                         let target_name = name_query.get(city_build_move.target).unwrap();
-//                         command.reply(format!("{} - Build city in {}", index, target_name));
-//                     }
-//                     Move::EndCityConstruction => {
-//                         command.reply(format!("{} - End Current Player City Construction", index));
-//                     }
-//                     Move::EliminateCity(elimintation_move) => {
-//                         let target_name = name_query.get(elimintation_move.area).unwrap();
-//                         command.reply(format!("{} - Eliminate city at {}, gain {} tokens of the needed {}", index, target_name, elimintation_move.tokens_gained, elimintation_move.tokens_needed));
-//                     }
-//                     Move::AttackArea(movement_move) => {
-//                         let source_name = name_query.get(movement_move.source).unwrap();
-//                         let target_name = name_query.get(movement_move.target).unwrap();
-//                         command.reply(format!("{} - Attack with max {} tokens from {} to {}", index, movement_move.max_tokens, source_name, target_name));
-//                     }
-//                     Move::AttackCity(movement_move) => {
-//                         let source_name = name_query.get(movement_move.source).unwrap();
-//                         let target_name = name_query.get(movement_move.target).unwrap();
-//                         command.reply(format!("{} - Attack city with max {} tokens from {} to {}", index, movement_move.max_tokens, source_name, target_name));
-//                     }
-//                 }
-//             });
-//         }
-//     }
-// }
+use crate::civilization::concepts::hot_seat::hot_seat_functions::{describe_move, max_tokens};
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use bevy::prelude::{Entity, Name, Query};
+
+pub struct ListMoves {
+    pub player: Option<String>,
+}
+
+impl TypedCommand for ListMoves {
+    const NAME: &'static str = "moves";
+    const USAGE: &'static str = "moves [player]";
+    const HELP: &'static str = "lists the moves players can make right now";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(ListMoves {
+            player: args.optional_text(),
+        })
+    }
+}
+
+pub fn list_moves(
+    mut command: ConsoleCommand<ListMoves>,
+    available_moves: Query<(Entity, &Name, &AvailableMoves)>,
+    lookup: ConsoleLookup,
+) {
+    if let Some(ListMoves { player }) = command.take() {
+        let only = match player
+            .as_deref()
+            .map(|player| lookup.player(player))
+            .transpose()
+        {
+            Ok(only) => only,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        let name_of = |entity: Entity| lookup.name_of(entity);
+        for (player, name, avail_moves) in available_moves.iter() {
+            if only.is_some_and(|only| only != player) {
+                continue;
+            }
+            command.reply(format!("Player {} can perform the following moves", name));
+            let mut indexes = avail_moves.moves.keys().copied().collect::<Vec<_>>();
+            indexes.sort();
+            for index in indexes {
+                let game_move = &avail_moves.moves[&index];
+                let tokens = max_tokens(game_move);
+                if tokens > 0 {
+                    command.reply(format!(
+                        "{} - {}, at most {} tokens",
+                        index,
+                        describe_move(game_move, &name_of),
+                        tokens
+                    ));
+                } else {
+                    command.reply(format!(
+                        "{} - {}",
+                        index,
+                        describe_move(game_move, &name_of)
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -1,63 +1,62 @@
-// use crate::civilization::components::game_moves_components::{AvailableMoves, Move};
-// use crate::civilization::events::city_construction_events::{BuildCityCommand, EndPlayerCityConstruction};
-// use crate::civilization::events::movement_events::{MoveTokenFromAreaToAreaCommand, PlayerMovementEnded};
-// use bevy::core::Name;
-// use bevy::prelude::{Entity, MessageWriter, Query};
-// use bevy_console::ConsoleCommand;
-// use clap::Parser;
-// use crate::civilization::concepts::population_expansion::events::ExpandPopulationManuallyCommand;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "move")]
-// pub struct MakeAMove {
-//     pub player: String,
-//     pub index: usize,
-//     pub number: Option<usize>,
-// }
-// 
-// pub fn make_a_move(
-//     mut command: ConsoleCommand<MakeAMove>,
-//     available_moves: Query<(&Name, &AvailableMoves, Entity)>,
-//     mut expand_writer: MessageWriter<ExpandPopulationManuallyCommand>,
-//     mut move_tokens_writer: MessageWriter<MoveTokenFromAreaToAreaCommand>,
-//     mut end_movement_writer: MessageWriter<PlayerMovementEnded>,
-//     mut build_city_writer: MessageWriter<BuildCityCommand>,
-//     mut end_player_city_construction: MessageWriter<EndPlayerCityConstruction>
-// ) {
-//     if let Some(Ok(MakeAMove { player, index, number })) = command.take() {
-//         for (name, avail_moves, player_entity) in available_moves.iter() {
-//             if name.to_string() == player {
-//                 avail_moves.moves.iter().for_each(|(move_index, game_move)| {
-//                     if index.eq(move_index) {
-//                         match game_move {
-//                             Move::PopulationExpansion(pop_exp_move) => {
-//                                 expand_writer.write(ExpandPopulationManuallyCommand::new(player_entity, pop_exp_move.area, number.unwrap_or(pop_exp_move.max_tokens)));
-//                             }
-//                             Move::Movement(movement_move) => { 
-//                                 move_tokens_writer.write(MoveTokenFromAreaToAreaCommand::new(movement_move.source, movement_move.target, number.unwrap_or(movement_move.max_tokens), player_entity));
-//                             }
-//                             Move::EndMovement => {
-//                                 end_movement_writer.write(PlayerMovementEnded::new(player_entity));
-//                             }
-//                             Move::CityConstruction(build_city_move) => {
-//                                 build_city_writer.write(BuildCityCommand::new(player_entity, build_city_move.target));
-//                             }
-//                             Move::EndCityConstruction => {
-//                                 end_player_city_construction.write(EndPlayerCityConstruction::new(player_entity));
-//                             }
-//                             Move::EliminateCity(_el_move) => {
-//                                 
-//                             }
-//                             Move::AttackArea(movement_move) => {
-//                                 move_tokens_writer.write(MoveTokenFromAreaToAreaCommand::new(movement_move.source, movement_move.target, number.unwrap_or(movement_move.max_tokens), player_entity));
-//                             }
-//                             Move::AttackCity(movement_move) => {
-//                                 move_tokens_writer.write(MoveTokenFromAreaToAreaCommand::new(movement_move.source, movement_move.target, number.unwrap_or(movement_move.max_tokens), player_entity));
-//                             }
-//                         }
-//                     }
-//                 });
-//             }
-//         }
-//     }
-// }
+use crate::civilization::concepts::hot_seat::hot_seat_functions::max_tokens;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use crate::civilization::game_moves::game_moves_systems::GameMoveWriters;
+use bevy::prelude::Query;
+
+pub struct MakeAMove {
+    pub player: String,
+    pub index: usize,
+    pub number: Option<usize>,
+}
+
+impl TypedCommand for MakeAMove {
+    const NAME: &'static str = "move";
+    const USAGE: &'static str = "move <player> <index> [tokens]";
+    const HELP: &'static str = "makes one of the moves listed by moves";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(MakeAMove {
+            player: args.text("player")?,
+            index: args.number("index")?,
+            number: args.optional_number("tokens")?,
+        })
+    }
+}
+
+pub fn make_a_move(
+    mut command: ConsoleCommand<MakeAMove>,
+    available_moves: Query<&AvailableMoves>,
+    lookup: ConsoleLookup,
+    mut writers: GameMoveWriters,
+) {
+    if let Some(MakeAMove {
+        player,
+        index,
+        number,
+    }) = command.take()
+    {
+        let player = match lookup.player(&player) {
+            Ok(player) => player,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        let Some(game_move) = available_moves
+            .get(player)
+            .ok()
+            .and_then(|avail_moves| avail_moves.moves.get(&index))
+        else {
+            command.reply(format!("There is no move {} for that player", index));
+            return;
+        };
+        if let Move::Trade(_) = game_move {
+            command.reply("Trades are made in the trade window");
+            return;
+        }
+        writers.write_move(player, game_move, number.unwrap_or(max_tokens(game_move)));
+        command.reply(format!("Making move {}", index));
+    }
+}
@@ -1,23 +1,21 @@
-// pub mod console_commands;
-// mod build_city_console_command;
-// mod list_builds_command;
-// mod show_board_command;
-// mod list_moves_command;
-// mod expand_population_command;
-// mod start_command;
-// mod player_end_building_command;
-// mod stupid_ai_command;
-// mod make_a_move_command;
-// 
-// pub mod prelude {
-//     pub use super::build_city_console_command::*;
-//     pub use super::console_commands::*;
-//     pub use super::expand_population_command::*;
-//     pub use super::list_builds_command::*;
-//     pub use super::list_moves_command::*;
-//     pub use super::make_a_move_command::*;
-//     pub use super::player_end_building_command::*;
-//     pub use super::show_board_command::*;
-//     pub use super::start_command::*;
-//     pub use super::stupid_ai_command::*;
-// }
\ No newline at end of file
+pub mod build_city_console_command;
+pub mod console_commands;
+pub mod console_components;
+pub mod console_events;
+pub mod console_functions;
+pub mod console_resources;
+pub mod console_systems;
+pub mod dump_player_command;
+pub mod expand_population_command;
+pub mod give_trade_card_command;
+pub mod list_builds_command;
+pub mod list_moves_command;
+pub mod make_a_move_command;
+pub mod player_end_building_command;
+pub mod rules_command;
+pub mod set_phase_command;
+pub mod show_board_command;
+pub mod spawn_tokens_command;
+pub mod start_command;
+pub mod stupid_ai_command;
+pub mod toggle_controller_command;
@@ -1,26 +1,36 @@
-// use clap::Parser;
-// use bevy_console::ConsoleCommand;
-// use bevy::prelude::{Commands, Entity, Query};
-// use bevy::core::Name;
-// use crate::civilization::components::city_construction_components::IsBuilding;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "eb")]
-// pub struct PlayerEndBuildingCommand {
-//     pub player_name: String,
-// }
-// 
-// pub fn end_building(
-//     mut command: ConsoleCommand<PlayerEndBuildingCommand>,
-//     mut commands: Commands,
-//     player_query: Query<(Entity, &Name)>,
-// ) {
-//     if let Some(Ok(PlayerEndBuildingCommand { player_name })) = command.take() {
-//         if let Some(player_entity) = player_query
-//             .iter()
-//             .find(|(_, name)| **name == Name::from(player_name.clone()))
-//             .map(|(entity, _)| entity) {
-//             commands.entity(player_entity).insert(IsBuilding {});
-//         }
-//     }
-// }
\ No newline at end of file
+use crate::civilization::concepts::city_construction::city_construction_events::EndPlayerCityConstruction;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use bevy::prelude::MessageWriter;
+
+pub struct PlayerEndBuildingCommand {
+    pub player_name: String,
+}
+
+impl TypedCommand for PlayerEndBuildingCommand {
+    const NAME: &'static str = "endbuild";
+    const USAGE: &'static str = "endbuild <player>";
+    const HELP: &'static str = "ends city construction for a player";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(PlayerEndBuildingCommand {
+            player_name: args.text("player")?,
+        })
+    }
+}
+
+pub fn end_building(
+    mut command: ConsoleCommand<PlayerEndBuildingCommand>,
+    lookup: ConsoleLookup,
+    mut end_building: MessageWriter<EndPlayerCityConstruction>,
+) {
+    if let Some(PlayerEndBuildingCommand { player_name }) = command.take() {
+        match lookup.player(&player_name) {
+            Ok(player) => {
+                end_building.write(EndPlayerCityConstruction::new(player));
+                command.reply(format!("{} is done building", player_name));
+            }
+            Err(err) => command.reply(err),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::civilization::concepts::rules::rules_resources::{RuleSet, RuleVariant};
+use crate::civilization::concepts::rules::rules_systems::LoadedHouseRules;
+use crate::civilization::console::console_functions::{parse_rule_variant, ConsoleArgs};
+use crate::civilization::console::console_systems::{ConsoleCommand, TypedCommand};
+use bevy::prelude::ResMut;
+
+pub struct RulesCommand {
+    variant: RuleVariant,
+}
+
+impl TypedCommand for RulesCommand {
+    const NAME: &'static str = "rules";
+    const USAGE: &'static str = "rules <civilization|advanced|house>";
+    const HELP: &'static str = "switches the rules the game is played by";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(RulesCommand {
+            variant: parse_rule_variant(&args.text("rules")?)?,
+        })
+    }
+}
+
+pub fn rules(
+    mut command: ConsoleCommand<RulesCommand>,
+    mut rule_set: ResMut<RuleSet>,
+    house_rules: LoadedHouseRules,
+) {
+    if let Some(RulesCommand { variant }) = command.take() {
+        *rule_set = house_rules.rule_set(variant);
+        command.reply(format!("Playing by {:?}: {:?}", variant, *rule_set));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::civilization::console::console_functions::{parse_phase, ConsoleArgs};
+use crate::civilization::console::console_systems::{ConsoleCommand, TypedCommand};
+use crate::GameActivity;
+use bevy::prelude::{NextState, ResMut};
+
+pub struct SetPhaseCommand {
+    phase: GameActivity,
+}
+
+impl TypedCommand for SetPhaseCommand {
+    const NAME: &'static str = "phase";
+    const USAGE: &'static str = "phase <phase>";
+    const HELP: &'static str = "jumps straight to a phase of the round";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(SetPhaseCommand {
+            phase: parse_phase(&args.text("phase")?)?,
+        })
+    }
+}
+
+pub fn set_phase(
+    mut command: ConsoleCommand<SetPhaseCommand>,
+    mut next_state: ResMut<NextState<GameActivity>>,
+) {
+    if let Some(SetPhaseCommand { phase }) = command.take() {
+        command.reply(format!("Moving on to {:?}", phase));
+        next_state.set(phase);
+    }
+}
@@ -1,31 +1,69 @@
-// use crate::civilization::components::population::Population;
-// use crate::civilization::components::*;
-// use bevy::core::Name;
-// use bevy::prelude::{Has, Query};
-// use bevy_console::ConsoleCommand;
-// use clap::Parser;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "board")]
-// pub struct ShowBoardCommand;
-// 
-// pub fn show_board(
-//     mut command: ConsoleCommand<ShowBoardCommand>,
-//     area_query: Query<(&Name, &Population, Has<StartArea>, Has<CitySite>, Has<BuiltCity>, Has<Volcano>, Has<FloodPlain>)>,
-//     player_areas_query: Query<(&Name, &PlayerAreas, &TokenStock)>,
-//     name_query: Query<&Name>,
-// ) {
-//     if let Some(Ok(ShowBoardCommand {})) = command.take() {
-//         for (area_name, population, is_start_area, is_city_site, has_city, _has_volcano, _has_flood_plain) in area_query.iter().filter(|(_, population, _, _, _, _, _)| population.has_population()) {
-//             command.reply(format!("Area: {} {} has population: {}{}{}", area_name, if is_start_area { "<start>" } else { "" }, population.total_population(), if is_city_site { ", City Site" } else { "" }, if has_city { ", Has City" } else { "" }));
-//         }
-// 
-//         for (player_name, player_areas, stock) in player_areas_query.iter() {
-//             command.reply(format!("Player: {} has {} population in the following areas:", player_name, player_areas.total_population()));
-//             for area in player_areas.areas_with_population() {
-//                 command.reply(format!("  - {} : {}", name_query.get(area).unwrap(), player_areas.population_in_area(area)));
-//             }
-//             command.reply(format!("Player: {} has {} tokens in stock", player_name, stock.tokens_in_stock()));
-//         }
-//     }
-// }
\ No newline at end of file
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::*;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, TypedCommand};
+use crate::player::Player;
+use bevy::prelude::{Entity, Has, Name, Query, With};
+
+pub struct ShowBoardCommand;
+
+impl TypedCommand for ShowBoardCommand {
+    const NAME: &'static str = "board";
+    const USAGE: &'static str = "board";
+    const HELP: &'static str = "lists the areas with tokens and where every player stands";
+
+    fn parse(_args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(ShowBoardCommand)
+    }
+}
+
+pub fn show_board(
+    mut command: ConsoleCommand<ShowBoardCommand>,
+    area_query: Query<(&Name, Has<StartArea>, Has<CitySite>, Has<BuiltCity>)>,
+    populations: Populations,
+    player_query: Query<(Entity, &Name, &TokenStock), With<Player>>,
+    name_query: Query<&Name>,
+) {
+    if let Some(ShowBoardCommand) = command.take() {
+        for (area, population) in populations
+            .iter()
+            .filter(|(_, population)| population.has_population())
+        {
+            let Ok((area_name, is_start_area, is_city_site, has_city)) = area_query.get(area)
+            else {
+                continue;
+            };
+            command.reply(format!(
+                "Area: {} {} has population: {}{}{}",
+                area_name,
+                if is_start_area { "<start>" } else { "" },
+                population.total_population(),
+                if is_city_site { ", City Site" } else { "" },
+                if has_city { ", Has City" } else { "" }
+            ));
+        }
+
+        for (player, player_name, stock) in player_query.iter() {
+            let player_areas = populations.player_areas(player);
+            command.reply(format!(
+                "Player: {} has {} population in the following areas:",
+                player_name,
+                player_areas.total_population()
+            ));
+            for area in player_areas.areas_with_population() {
+                if let Ok(area_name) = name_query.get(area) {
+                    command.reply(format!(
+                        "  - {} : {}",
+                        area_name,
+                        player_areas.population_in_area(area)
+                    ));
+                }
+            }
+            command.reply(format!(
+                "Player: {} has {} tokens in stock",
+                player_name,
+                stock.tokens_in_stock()
+            ));
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::civilization::components::TokenStock;
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use bevy::prelude::{MessageWriter, Query};
+
+pub struct SpawnTokensCommand {
+    player: String,
+    area: String,
+    tokens: usize,
+}
+
+impl TypedCommand for SpawnTokensCommand {
+    const NAME: &'static str = "spawn";
+    const USAGE: &'static str = "spawn <player> <area> <tokens>";
+    const HELP: &'static str = "puts tokens from a player's stock in an area";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(SpawnTokensCommand {
+            player: args.text("player")?,
+            area: args.text("area")?,
+            tokens: args.number("tokens")?,
+        })
+    }
+}
+
+pub fn spawn_tokens(
+    mut command: ConsoleCommand<SpawnTokensCommand>,
+    lookup: ConsoleLookup,
+    stocks: Query<&TokenStock>,
+    mut writer: MessageWriter<MoveTokensFromStockToAreaCommand>,
+) {
+    if let Some(SpawnTokensCommand {
+        player,
+        area,
+        tokens,
+    }) = command.take()
+    {
+        let (player, area) = match lookup
+            .player(&player)
+            .and_then(|player| lookup.area(&area).map(|area| (player, area)))
+        {
+            Ok(found) => found,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        let in_stock = stocks
+            .get(player)
+            .map(|stock| stock.tokens_in_stock())
+            .unwrap_or_default();
+        if in_stock < tokens {
+            command.reply(format!("Only {} tokens left in stock", in_stock));
+            return;
+        }
+        writer.write(MoveTokensFromStockToAreaCommand::new(area, player, tokens));
+        command.reply(format!(
+            "{} tokens for {} in {}",
+            tokens,
+            lookup.name_of(player),
+            lookup.name_of(area)
+        ));
+    }
+}
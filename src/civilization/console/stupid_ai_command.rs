@@ -1,29 +1,36 @@
-// use crate::player::Player;
-// use crate::stupid_ai::stupid_ai_events::StupidAiMessage;
-// use bevy::core::Name;
-// use bevy::prelude::{Entity, MessageWriter, Query, With};
-// use bevy_console::ConsoleCommand;
-// use clap::Parser;
-// 
-// #[derive(Parser, ConsoleCommand)]
-// #[command(name = "sa")]
-// pub struct StupidAiCommand {
-//     player: String,
-// }
-// 
-// pub fn stupid_ai(
-//     mut command: ConsoleCommand<StupidAiCommand>,
-//     player_query: Query<(Entity, &Name), With<Player>>,
-//     mut add_stupid_ai: MessageWriter<StupidAiMessage>,
-// ) {
-//     if let Some(Ok(StupidAiCommand { player })) = command.take() {
-//         if let Some(player_entity) = player_query.iter().find(|(_, name)| **name == Name::from(player.clone())).map(|(entity, _)| entity) {
-//             add_stupid_ai.write(StupidAiMessage {
-//                 player: player_entity
-//             });
-//             command.reply("Making Player Stupid");
-//         } else {
-//             command.reply("Could not find target player");
-//         }
-//     }
-// }
\ No newline at end of file
+use crate::civilization::console::console_functions::ConsoleArgs;
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::stupid_ai::stupid_ai_events::StupidAiMessage;
+use bevy::prelude::MessageWriter;
+
+pub struct StupidAiCommand {
+    player: String,
+}
+
+impl TypedCommand for StupidAiCommand {
+    const NAME: &'static str = "stupid_ai";
+    const USAGE: &'static str = "stupid_ai <player>";
+    const HELP: &'static str = "lets the stupid AI play for a player";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(StupidAiCommand {
+            player: args.text("player")?,
+        })
+    }
+}
+
+pub fn stupid_ai(
+    mut command: ConsoleCommand<StupidAiCommand>,
+    lookup: ConsoleLookup,
+    mut add_stupid_ai: MessageWriter<StupidAiMessage>,
+) {
+    if let Some(StupidAiCommand { player }) = command.take() {
+        match lookup.player(&player) {
+            Ok(player) => {
+                add_stupid_ai.write(StupidAiMessage { player });
+                command.reply("Making Player Stupid");
+            }
+            Err(err) => command.reply(err),
+        }
+    }
+}
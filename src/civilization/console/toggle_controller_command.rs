@@ -0,0 +1,91 @@
+use crate::civilization::concepts::hot_seat::hot_seat_components::HumanSeat;
+use crate::civilization::console::console_functions::{parse_controller, ConsoleArgs, Controller};
+use crate::civilization::console::console_systems::{ConsoleCommand, ConsoleLookup, TypedCommand};
+use crate::civilization::enums::AiDifficulty;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::external_bot::external_bot_components::{BotProcess, ExternalBot, PendingBotRequest};
+use crate::heuristic_ai::heuristic_ai_components::HeuristicAi;
+use crate::mcts_ai::mcts_ai_components::MctsAi;
+use crate::stupid_ai::prelude::{IsHuman, StupidAi};
+use bevy::prelude::{Commands, Has, MessageWriter, Query, Res};
+
+pub struct ToggleControllerCommand {
+    player: String,
+    controller: Option<Controller>,
+}
+
+impl TypedCommand for ToggleControllerCommand {
+    const NAME: &'static str = "controller";
+    const USAGE: &'static str = "controller <player> [human|stupid|heuristic|hard|bot]";
+    const HELP: &'static str =
+        "hands a player to a human or an AI, without a controller it switches between the two";
+
+    fn parse(args: &mut ConsoleArgs) -> Result<Self, String> {
+        Ok(ToggleControllerCommand {
+            player: args.text("player")?,
+            controller: args
+                .optional_text()
+                .map(|typed| parse_controller(&typed))
+                .transpose()?,
+        })
+    }
+}
+
+pub fn toggle_controller(
+    mut command: ConsoleCommand<ToggleControllerCommand>,
+    lookup: ConsoleLookup,
+    players: Query<(Has<IsHuman>, Has<AvailableMoves>)>,
+    debug_options: Res<DebugOptions>,
+    mut recalculate: MessageWriter<RecalculatePlayerMoves>,
+    mut commands: Commands,
+) {
+    if let Some(ToggleControllerCommand { player, controller }) = command.take() {
+        let (player, (is_human, has_moves)) = match lookup.player(&player).and_then(|player| {
+            players
+                .get(player)
+                .map(|found| (player, found))
+                .map_err(|err| err.to_string())
+        }) {
+            Ok(found) => found,
+            Err(err) => {
+                command.reply(err);
+                return;
+            }
+        };
+        let controller = controller.unwrap_or(if is_human {
+            Controller::Ai(debug_options.ai_difficulty)
+        } else {
+            Controller::Human
+        });
+
+        let mut player_commands = commands.entity(player);
+        player_commands.remove::<(
+            IsHuman,
+            HumanSeat,
+            StupidAi,
+            HeuristicAi,
+            MctsAi,
+            ExternalBot,
+            BotProcess,
+            PendingBotRequest,
+        )>();
+        match controller {
+            Controller::Human => player_commands.insert(IsHuman),
+            Controller::Ai(AiDifficulty::Stupid) => player_commands.insert(StupidAi),
+            Controller::Ai(AiDifficulty::Heuristic) => player_commands.insert(HeuristicAi),
+            Controller::Ai(AiDifficulty::Hard) => player_commands.insert(MctsAi),
+            Controller::Ai(AiDifficulty::External) => player_commands.insert(ExternalBot),
+        };
+        // The new controller only notices moves that are offered after it took over
+        if has_moves {
+            recalculate.write(RecalculatePlayerMoves::new(player));
+        }
+        command.reply(format!(
+            "{} is now played by {:?}",
+            lookup.name_of(player),
+            controller
+        ));
+    }
+}
@@ -2,7 +2,18 @@ use bevy::prelude::Reflect;
 use std::fmt::Display;
 
 #[derive(
-    serde::Deserialize, serde::Serialize, Debug, Reflect, Copy, Clone, Eq, PartialEq, Default, Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Reflect,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Default,
+    Hash,
+    PartialOrd,
+    Ord,
 )]
 pub enum GameFaction {
     #[default]
@@ -22,3 +33,17 @@ impl Display for GameFaction {
         write!(f, "{:#?}", self)
     }
 }
+
+/// Which controller plays the players that are not human.
+#[derive(Debug, Reflect, Copy, Clone, Eq, PartialEq, Default, Hash)]
+pub enum AiDifficulty {
+    /// Picks any available move at random
+    #[default]
+    Stupid,
+    /// Scores moves by cities, surviving tokens and city support
+    Heuristic,
+    /// Plays out the rest of the round many times over before every move
+    Hard,
+    /// Asks a program outside the game for every move, see `ExternalBotSettings`
+    External,
+}
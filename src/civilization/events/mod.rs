@@ -16,3 +16,20 @@ impl MoveTokensFromStockToAreaCommand {
         }
     }
 }
+
+/// Removes the city in an area and puts up to `replacement_tokens` of its owner's tokens in its
+/// place. An owner with fewer tokens in stock puts down what it has left.
+#[derive(Message, Debug)]
+pub struct ReduceCity {
+    pub area_entity: Entity,
+    pub replacement_tokens: usize,
+}
+
+impl ReduceCity {
+    pub fn new(area_entity: Entity, replacement_tokens: usize) -> Self {
+        ReduceCity {
+            area_entity,
+            replacement_tokens,
+        }
+    }
+}
@@ -21,6 +21,7 @@ pub enum Move {
     AttackArea(MovementMove),
     AttackCity(MovementMove),
     EndMovement,
+    ChooseAttacker(ChooseAttackerMove),
     CityConstruction(BuildCityMove),
     EndCityConstruction,
     EliminateCity(EliminateCityMove),
@@ -32,6 +33,23 @@ impl Display for Move {
         write!(f, "{:#?}", self)
     }
 }
+#[derive(Clone, Debug, Reflect)]
+pub struct ChooseAttackerMove {
+    pub area: Entity,
+    pub attacker: Entity,
+    pub attacker_tokens: usize,
+}
+
+impl ChooseAttackerMove {
+    pub fn new(area: Entity, attacker: Entity, attacker_tokens: usize) -> Self {
+        ChooseAttackerMove {
+            area,
+            attacker,
+            attacker_tokens,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Reflect)]
 pub struct EliminateCityMove {
     pub player: Entity,
@@ -18,6 +18,7 @@ impl Plugin for GameMovesPlugin {
                     recalculate_pop_exp_moves_for_player
                         .run_if(in_state(GameActivity::PopulationExpansion)),
                     recalculate_movement_moves_for_player.run_if(in_state(GameActivity::Movement)),
+                    recalculate_conflict_moves_for_player.run_if(in_state(GameActivity::Conflict)),
                     recalculate_city_construction_moves_for_player
                         .run_if(in_state(GameActivity::CityConstruction)),
                     recalculate_city_support_moves_for_player
@@ -29,6 +30,7 @@ impl Plugin for GameMovesPlugin {
             .add_observer(on_add_perform_movement)
             .add_observer(on_add_is_building)
             .add_observer(on_add_has_just_moved)
+            .add_observer(on_add_choosing_city_attacker)
             .add_observer(on_add_has_too_many_cities)
             .add_observer(on_add_needs_trade_move);
     }
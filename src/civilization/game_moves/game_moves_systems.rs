@@ -1,23 +1,97 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::{MaxPopulation, Populations};
 use crate::civilization::components::*;
 use crate::civilization::concepts::check_city_support::check_city_support_components::HasTooManyCities;
+use crate::civilization::concepts::check_city_support::check_city_support_events::EliminateCity;
 use crate::civilization::concepts::city_construction::city_construction_components::IsBuilding;
+use crate::civilization::concepts::conflict::conflict_components::ChoosingCityAttacker;
+use crate::civilization::concepts::conflict::conflict_events::FightAttackerFirst;
+use crate::civilization::concepts::city_construction::city_construction_events::{
+    BuildCityCommand, EndPlayerCityConstruction,
+};
 use crate::civilization::concepts::movement::movement_components::TokenHasMoved;
-use crate::civilization::concepts::movement::movement_events::PlayerMovementEnded;
+use crate::civilization::concepts::movement::movement_events::{
+    MoveTokenFromAreaToAreaCommand, PlayerMovementEnded,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_events::ExpandPopulationManuallyCommand;
 use crate::civilization::concepts::population_expansion::population_expansion_components::{
     ExpandAutomatically, ExpandManually, NeedsExpansion,
 };
 use crate::civilization::game_moves::game_moves_components::{
-    AvailableMoves, BuildCityMove, EliminateCityMove, Move, MovementMove, PopExpMove,
+    AvailableMoves, BuildCityMove, ChooseAttackerMove, EliminateCityMove, Move, MovementMove,
+    PopExpMove,
 };
 use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
+use bevy::ecs::system::SystemParam;
 use bevy::platform::collections::HashMap;
-use bevy::prelude::{Commands, MessageReader, MessageWriter, Has, Query};
+use bevy::prelude::{Commands, Entity, MessageReader, MessageWriter, Has, Query};
+
+/// The messages that play a chosen move.
+#[derive(SystemParam)]
+pub struct GameMoveWriters<'w> {
+    expand: MessageWriter<'w, ExpandPopulationManuallyCommand>,
+    move_tokens: MessageWriter<'w, MoveTokenFromAreaToAreaCommand>,
+    end_movement: MessageWriter<'w, PlayerMovementEnded>,
+    fight_attacker: MessageWriter<'w, FightAttackerFirst>,
+    build_city: MessageWriter<'w, BuildCityCommand>,
+    end_city_construction: MessageWriter<'w, EndPlayerCityConstruction>,
+    eliminate_city: MessageWriter<'w, EliminateCity>,
+}
+
+impl GameMoveWriters<'_> {
+    pub fn write_move(&mut self, player: Entity, game_move: &Move, tokens: usize) {
+        match game_move {
+            Move::PopulationExpansion(pop_exp_move) => {
+                self.expand.write(ExpandPopulationManuallyCommand::new(
+                    player,
+                    pop_exp_move.area,
+                    tokens,
+                ));
+            }
+            Move::Movement(movement_move)
+            | Move::AttackArea(movement_move)
+            | Move::AttackCity(movement_move) => {
+                self.move_tokens.write(MoveTokenFromAreaToAreaCommand::new(
+                    movement_move.source,
+                    movement_move.target,
+                    tokens,
+                    player,
+                ));
+            }
+            Move::EndMovement => {
+                self.end_movement.write(PlayerMovementEnded::new(player));
+            }
+            Move::ChooseAttacker(choose_move) => {
+                self.fight_attacker.write(FightAttackerFirst::new(
+                    player,
+                    choose_move.area,
+                    choose_move.attacker,
+                ));
+            }
+            Move::CityConstruction(build_city_move) => {
+                self.build_city
+                    .write(BuildCityCommand::new(player, build_city_move.target));
+            }
+            Move::EndCityConstruction => {
+                self.end_city_construction
+                    .write(EndPlayerCityConstruction::new(player));
+            }
+            Move::EliminateCity(el_move) => {
+                self.eliminate_city.write(EliminateCity::new(
+                    player,
+                    el_move.city,
+                    el_move.area,
+                    false,
+                ));
+            }
+            Move::Trade(_) => {}
+        }
+    }
+}
 
 pub fn recalculate_pop_exp_moves_for_player(
     mut recalc_player_reader: MessageReader<RecalculatePlayerMoves>,
-    player_move_query: Query<(&PlayerAreas, &TokenStock)>,
-    area_population_query: Query<&Population>,
+    player_move_query: Query<&TokenStock>,
+    populations: Populations,
     mut commands: Commands,
 ) {
     for event in recalc_player_reader.read() {
@@ -29,9 +103,10 @@ pub fn recalculate_pop_exp_moves_for_player(
         that
         */
         let mut command_index = 0;
-        if let Ok((player_areas, stock)) = player_move_query.get(event.player) {
+        if let Ok(stock) = player_move_query.get(event.player) {
+            let player_areas = populations.player_areas(event.player);
             for area in player_areas.areas().iter() {
-                if let Ok(pop) = area_population_query.get(*area) {
+                if let Some(pop) = populations.get(*area) {
                     command_index += 1;
                     moves.insert(
                         command_index,
@@ -60,9 +135,9 @@ pub fn recalculate_pop_exp_moves_for_player(
 
 pub fn recalculate_movement_moves_for_player(
     mut recalc_player_reader: MessageReader<RecalculatePlayerMoves>,
-    player_move_query: Query<&PlayerAreas>,
+    populations: Populations,
     area_connections_query: Query<&LandPassage>,
-    area_pop_and_city_query: Query<(&Population, Option<&BuiltCity>)>,
+    area_city_query: Query<Option<&BuiltCity>>,
     token_filter_query: Query<Has<TokenHasMoved>>,
     mut commands: Commands,
     mut end_player_movement: MessageWriter<PlayerMovementEnded>,
@@ -71,48 +146,26 @@ pub fn recalculate_movement_moves_for_player(
         commands.entity(event.player).remove::<AvailableMoves>();
         let mut moves = HashMap::default();
         let mut command_index = 0;
-        if let Ok(player_areas) = player_move_query.get(event.player) {
-            for (area, tokens) in player_areas.areas_and_population() {
-                let tokens_that_can_move = tokens
-                    .iter()
-                    .filter(|t| !token_filter_query.get(**t).unwrap())
-                    .collect::<Vec<_>>();
+        let player_areas = populations.player_areas(event.player);
+        for (area, tokens) in player_areas.areas_and_population() {
+            let tokens_that_can_move = tokens
+                .iter()
+                .filter(|t| !token_filter_query.get(**t).unwrap())
+                .collect::<Vec<_>>();
 
-                if !tokens_that_can_move.is_empty() {
-                    if let Ok(connections) = area_connections_query.get(area) {
-                        for target_area in connections.to_areas.iter() {
-                            if let Ok((population, optional_city)) =
-                                area_pop_and_city_query.get(*target_area)
-                            {
-                                if let Some(has_city) = optional_city {
-                                    if has_city.player != event.player {
-                                        command_index += 1;
-                                        moves.insert(
-                                            command_index,
-                                            Move::AttackCity(MovementMove::new(
-                                                area,
-                                                *target_area,
-                                                event.player,
-                                                tokens_that_can_move.len(),
-                                            )),
-                                        );
-                                    }
-                                } else if population.has_other_players(&event.player) {
+            if !tokens_that_can_move.is_empty() {
+                if let Ok(connections) = area_connections_query.get(area) {
+                    for target_area in connections.to_areas.iter() {
+                        if let (Some(population), Ok(optional_city)) = (
+                            populations.get(*target_area),
+                            area_city_query.get(*target_area),
+                        ) {
+                            if let Some(has_city) = optional_city {
+                                if has_city.player != event.player {
                                     command_index += 1;
                                     moves.insert(
                                         command_index,
-                                        Move::AttackArea(MovementMove::new(
-                                            area,
-                                            *target_area,
-                                            event.player,
-                                            tokens_that_can_move.len(),
-                                        )),
-                                    );
-                                } else {
-                                    command_index += 1;
-                                    moves.insert(
-                                        command_index,
-                                        Move::Movement(MovementMove::new(
+                                        Move::AttackCity(MovementMove::new(
                                             area,
                                             *target_area,
                                             event.player,
@@ -120,6 +173,28 @@ pub fn recalculate_movement_moves_for_player(
                                         )),
                                     );
                                 }
+                            } else if population.has_other_players(&event.player) {
+                                command_index += 1;
+                                moves.insert(
+                                    command_index,
+                                    Move::AttackArea(MovementMove::new(
+                                        area,
+                                        *target_area,
+                                        event.player,
+                                        tokens_that_can_move.len(),
+                                    )),
+                                );
+                            } else {
+                                command_index += 1;
+                                moves.insert(
+                                    command_index,
+                                    Move::Movement(MovementMove::new(
+                                        area,
+                                        *target_area,
+                                        event.player,
+                                        tokens_that_can_move.len(),
+                                    )),
+                                );
                             }
                         }
                     }
@@ -138,21 +213,54 @@ pub fn recalculate_movement_moves_for_player(
     }
 }
 
+pub fn recalculate_conflict_moves_for_player(
+    mut recalc_player_reader: MessageReader<RecalculatePlayerMoves>,
+    choosing_query: Query<&ChoosingCityAttacker>,
+    populations: Populations,
+    mut commands: Commands,
+) {
+    for event in recalc_player_reader.read() {
+        commands.entity(event.player).remove::<AvailableMoves>();
+        let mut moves = HashMap::default();
+        if let Ok(choosing) = choosing_query.get(event.player) {
+            if let Some(population) = populations.get(choosing.area) {
+                for (index, attacker) in choosing.attackers.iter().enumerate() {
+                    moves.insert(
+                        index + 1,
+                        Move::ChooseAttacker(ChooseAttackerMove::new(
+                            choosing.area,
+                            *attacker,
+                            population.population_for_player(*attacker),
+                        )),
+                    );
+                }
+            }
+        }
+        if !moves.is_empty() {
+            commands
+                .entity(event.player)
+                .insert(AvailableMoves::new(moves));
+        }
+    }
+}
+
 pub fn recalculate_city_construction_moves_for_player(
     mut recalc_player_reader: MessageReader<RecalculatePlayerMoves>,
-    player_move_query: Query<(&PlayerAreas, &CityTokenStock)>,
-    area_property_query: Query<(&Population, Has<CitySite>)>,
+    player_move_query: Query<&CityTokenStock>,
+    populations: Populations,
+    area_property_query: Query<Has<CitySite>>,
     mut commands: Commands,
 ) {
     for event in recalc_player_reader.read() {
         commands.entity(event.player).remove::<AvailableMoves>();
         let mut moves = HashMap::default();
         let mut command_index = 0;
-        if let Ok((player_areas, city_token_stock)) = player_move_query.get(event.player) {
+        if let Ok(city_token_stock) = player_move_query.get(event.player) {
             if city_token_stock.has_tokens() {
+                let player_areas = populations.player_areas(event.player);
                 for (area, population) in player_areas.areas_and_population_count().iter() {
                     if population >= &6 {
-                        if let Ok((_area_pop, has_city_site)) = area_property_query.get(*area) {
+                        if let Ok(has_city_site) = area_property_query.get(*area) {
                             if (has_city_site && population >= &6) || (population >= &12) {
                                 command_index += 1;
                                 moves.insert(
@@ -180,7 +288,7 @@ pub fn recalculate_city_construction_moves_for_player(
 pub fn recalculate_city_support_moves_for_player(
     mut recalc_player_reader: MessageReader<RecalculatePlayerMoves>,
     player_city_query: Query<(&PlayerCities, &HasTooManyCities)>,
-    area_property_query: Query<&Population>,
+    area_property_query: Query<&MaxPopulation>,
     mut commands: Commands,
 ) {
     for event in recalc_player_reader.read() {
@@ -189,7 +297,7 @@ pub fn recalculate_city_support_moves_for_player(
         let mut command_index = 0;
         if let Ok((player_cities, has_too_many_cities)) = player_city_query.get(event.player) {
             for (area, city) in player_cities.areas_and_cities.iter() {
-                if let Ok(pop) = area_property_query.get(*area) {
+                if let Ok(max_population) = area_property_query.get(*area) {
                     command_index += 1;
                     moves.insert(
                         command_index,
@@ -197,7 +305,7 @@ pub fn recalculate_city_support_moves_for_player(
                             event.player,
                             *area,
                             *city,
-                            pop.max_population,
+                            max_population.0,
                             has_too_many_cities.needed_tokens,
                         )),
                     );
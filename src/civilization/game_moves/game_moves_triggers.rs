@@ -4,6 +4,7 @@ use crate::civilization::game_moves::game_moves_events::RecalculatePlayerMoves;
 use bevy::prelude::{Commands, MessageWriter, Add, On};
 use crate::civilization::concepts::check_city_support::check_city_support_components::HasTooManyCities;
 use crate::civilization::concepts::city_construction::city_construction_components::IsBuilding;
+use crate::civilization::concepts::conflict::conflict_components::ChoosingCityAttacker;
 use crate::civilization::concepts::movement::movement_components::{HasJustMoved, PerformingMovement};
 
 pub fn on_add_manual_expansion(
@@ -39,6 +40,13 @@ pub fn on_add_has_too_many_cities(
     event_writer.write(RecalculatePlayerMoves::new(trigger.event().entity));
 }
 
+pub fn on_add_choosing_city_attacker(
+    trigger: On<Add, ChoosingCityAttacker>,
+    mut event_writer: MessageWriter<RecalculatePlayerMoves>,
+) {
+    event_writer.write(RecalculatePlayerMoves::new(trigger.event().entity));
+}
+
 pub fn on_add_is_building(
     trigger: On<Add, IsBuilding>,
     mut event_writer: MessageWriter<RecalculatePlayerMoves>,
@@ -1,17 +1,34 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::{MaxPopulation, Populations};
 use crate::civilization::components::*;
 use crate::civilization::concepts::acquire_trade_cards::trade_card_components::{
     CivilizationTradeCards, PlayerTradeCards,
 };
-use crate::civilization::concepts::census::census_components::Census;
+use crate::civilization::concepts::ai_personality::ai_personality_resources::{
+    AiPersonalities, AiPersonalitiesHandle, AiPersonalitySetup,
+};
+use crate::civilization::concepts::census::census_components::{AstPosition, Census};
+use crate::civilization::concepts::hot_seat::hot_seat_components::HumanSeat;
+use crate::civilization::concepts::hot_seat::hot_seat_resources::HotSeatSetup;
 use crate::civilization::concepts::map::map_plugin::AvailableFactions;
-use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::civilization::concepts::scenario::scenario_resources::ScenarioHandle;
+use crate::civilization::concepts::scenario::scenario_systems::{LoadedScenario, ScenarioBoard};
+use crate::civilization::concepts::token_animation::token_animation_components::{
+    TokenTween, TOKEN_SCALE,
+};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use crate::civilization::enums::{AiDifficulty, GameFaction};
+use crate::civilization::events::{MoveTokensFromStockToAreaCommand, ReduceCity};
+use crate::civilization::functions::{reduce_city, token_on_board};
 use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::external_bot::prelude::ExternalBot;
+use crate::heuristic_ai::prelude::HeuristicAi;
+use crate::mcts_ai::prelude::MctsAi;
 use crate::player::Player;
 use crate::stupid_ai::prelude::*;
 use crate::GameActivity;
-use bevy::math::{vec3, Vec3};
-use bevy::prelude::{debug, default, info, Commands, Entity, MessageReader, MessageWriter, Name, NextState, Query, Res, ResMut, Sprite, StateTransitionEvent, Transform, With, Without};
+use bevy::math::vec3;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{debug, info, warn, Assets, Commands, Entity, MessageReader, MessageWriter, Name, NextState, Query, Res, ResMut, StateTransitionEvent, Transform, With, Without};
 use rand::seq::IteratorRandom;
 
 pub fn start_game(
@@ -19,8 +36,19 @@ pub fn start_game(
     start_area_query: Query<(Entity, &Name, &StartArea)>,
     mut writer: MessageWriter<MoveTokensFromStockToAreaCommand>,
     mut next_state: ResMut<NextState<GameActivity>>,
+    scenario: LoadedScenario,
+    mut scenario_board: ScenarioBoard,
 ) {
     debug!("4. Starting the game!");
+    if let Some(scenario) = scenario.get() {
+        let players = player_query
+            .iter()
+            .map(|(entity, _, faction)| (entity, faction.faction))
+            .collect::<Vec<_>>();
+        scenario_board.lay_out(scenario, &players, &mut writer);
+        next_state.set(scenario.activity.clone());
+        return;
+    }
     for (player_entity, name, player_faction) in player_query.iter() {
         debug!("Starting the game for player: {:#?}", name);
         if let Some((area_entity, area_name, _)) = start_area_query
@@ -43,34 +71,68 @@ pub fn setup_players(
     mut trade_card_resource: ResMut<CivilizationTradeCards>,
     mut commands: Commands,
     mut available_factions: ResMut<AvailableFactions>,
+    hot_seat_setup: Res<HotSeatSetup>,
+    personalities: (
+        Res<AiPersonalitySetup>,
+        Option<Res<AiPersonalitiesHandle>>,
+        Res<Assets<AiPersonalities>>,
+    ),
+    scenario: LoadedScenario,
 ) {
     debug!("3. Setting up players!");
-    (1..=debug_options.number_of_players).for_each(|n| {
-        if let Some(faction) = available_factions
-            .remaining_factions
-            .clone()
-            .iter()
-            .choose(&mut rand::rng())
-        {
-            available_factions.remaining_factions.remove(faction);
-            // Create Player
-            let player = commands
-                .spawn((
-                    Player,
-                    Name::new(format!("p_{:#?}_{n}", faction)),
-                    Census { population: 0 },
-                    Treasury::default(),
-                    Faction::new(*faction),
-                    PlayerAreas::default(),
-                    PlayerCities::default(),
-                    StupidAi,
-                    PlayerTradeCards::default(),
-                ))
-                .id();
+    let (personality_setup, personalities_handle, personality_assets) = personalities;
+    let loaded_personalities =
+        personalities_handle.and_then(|handle| personality_assets.get(handle.0.id()));
+    let scenario = scenario.validated();
+    if scenario.is_none() {
+        // The game starts from the regular position instead
+        commands.remove_resource::<ScenarioHandle>();
+    }
+    let number_of_players = scenario.map_or(debug_options.number_of_players, |scenario| {
+        scenario.players.len()
+    });
+    (1..=number_of_players).for_each(|n| {
+        let scenario_player = scenario.and_then(|scenario| scenario.players.get(n - 1));
+        let faction = match scenario_player {
+            Some(scenario_player) => available_factions
+                .remaining_factions
+                .contains(&scenario_player.faction)
+                .then_some(scenario_player.faction),
+            None => available_factions
+                .remaining_factions
+                .iter()
+                .choose(&mut rand::rng())
+                .copied(),
+        };
+        if let Some(faction) = faction {
+            available_factions.remaining_factions.remove(&faction);
+            let treasury = scenario_player.map_or(0, |scenario_player| scenario_player.treasury);
+            let player = spawn_player(&mut commands, faction, n, treasury);
+            // The first players are the humans sharing the device, they play without an AI
+            let is_seat = n <= hot_seat_setup.human_seats;
+            if is_seat {
+                commands.entity(player).insert(HumanSeat::new(n));
+            } else {
+                match debug_options.ai_difficulty {
+                    AiDifficulty::Stupid => commands.entity(player).insert(StupidAi),
+                    AiDifficulty::Heuristic => commands.entity(player).insert(HeuristicAi),
+                    AiDifficulty::Hard => commands.entity(player).insert(MctsAi),
+                    AiDifficulty::External => commands.entity(player).insert(ExternalBot),
+                };
+                if let Some(name) = personality_setup.personality_for_player(n) {
+                    match loaded_personalities.and_then(|loaded| loaded.get(name)) {
+                        Some(personality) => {
+                            commands.entity(player).insert(personality.clone());
+                        }
+                        None => warn!("No AI personality called {}", name),
+                    }
+                }
+            }
 
-            if debug_options.add_human_player && *faction == debug_options.human_faction {
+            if is_seat || (debug_options.add_human_player && faction == debug_options.human_faction)
+            {
                 commands.entity(player).insert(IsHuman);
-                if debug_options.human_starts_with_trade_cards {
+                if debug_options.human_starts_with_trade_cards && scenario_player.is_none() {
                     let mut player_trade_cards = PlayerTradeCards::default();
                     (1..=9).for_each(|pile| {
                         if let Some(pulled_card) = trade_card_resource.pull_card_from(pile) {
@@ -81,36 +143,79 @@ pub fn setup_players(
                 }
             }
 
-            let tokens = (0..47)
-                .map(|_| {
-                    commands
-                        .spawn((Name::new(format!("Token {n}")), Token::new(player)))
-                        .id()
-                })
-                .collect::<Vec<Entity>>();
-
-            let city_tokens = (0..9)
-                .map(|_| {
-                    commands
-                        .spawn((Name::new(format!("City {n}")), CityToken::new(player)))
-                        .id()
-                })
-                .collect::<Vec<Entity>>();
-            commands.entity(player).insert((
-                TokenStock::new(47, tokens),
-                CityTokenStock::new(9, city_tokens),
-            ));
+            if let Some(scenario_player) = scenario_player {
+                let mut player_trade_cards = PlayerTradeCards::default();
+                for (card, count) in scenario_player.trade_cards.iter() {
+                    player_trade_cards.add_trade_cards(*card, *count);
+                }
+                commands.entity(player).insert(player_trade_cards);
+            }
         }
     });
     //debug!("Players are set up!");
 }
 
+/// Spawns player number n with its tokens and city tokens, putting the given number of tokens
+/// in the treasury and the rest in stock.
+pub fn spawn_player(
+    commands: &mut Commands,
+    faction: GameFaction,
+    n: usize,
+    tokens_in_treasury: usize,
+) -> Entity {
+    let player = commands
+        .spawn((
+            Player,
+            Name::new(format!("p_{:#?}_{n}", faction)),
+            Census { population: 0 },
+            AstPosition::default(),
+            Faction::new(faction),
+            PlayerCities::default(),
+            PlayerTradeCards::default(),
+        ))
+        .id();
+
+    let mut tokens = (0..47)
+        .map(|_| {
+            commands
+                .spawn((Name::new(format!("Token {n}")), Token::new(player)))
+                .id()
+        })
+        .collect::<Vec<Entity>>();
+
+    let city_tokens = (0..9)
+        .map(|_| {
+            commands
+                .spawn((Name::new(format!("City {n}")), CityToken::new(player)))
+                .id()
+        })
+        .collect::<Vec<Entity>>();
+    let stock_tokens = tokens.split_off(tokens_in_treasury.min(tokens.len()));
+    let mut treasury = Treasury::default();
+    tokens
+        .into_iter()
+        .for_each(|token| treasury.add_token_to_treasury(token));
+    commands.entity(player).insert((
+        treasury,
+        TokenStock::new(47, stock_tokens),
+        CityTokenStock::new(9, city_tokens),
+    ));
+    player
+}
+
 pub fn connect_areas(
-    mut area_query: Query<(Entity, &mut LandPassage, &NeedsConnections)>,
+    mut area_query: Query<(
+        Entity,
+        &mut LandPassage,
+        &mut SeaPassage,
+        &NeedsConnections,
+    )>,
     named_areas: Query<(Entity, &GameArea)>,
     mut commands: Commands,
 ) {
-    for (area_entity, mut land_passages, needed_connections) in area_query.iter_mut() {
+    for (area_entity, mut land_passages, mut sea_passages, needed_connections) in
+        area_query.iter_mut()
+    {
         for named_area in needed_connections.land_connections.iter() {
             //This is fucking stupid, but who cares?
             for (target_area_entity, target_area) in named_areas.iter() {
@@ -119,25 +224,52 @@ pub fn connect_areas(
                 }
             }
         }
+        for named_area in needed_connections.sea_connections.iter() {
+            for (target_area_entity, target_area) in named_areas.iter() {
+                if target_area.id == *named_area {
+                    sea_passages.to_areas.push(target_area_entity);
+                }
+            }
+        }
         commands.entity(area_entity).remove::<NeedsConnections>();
     }
 }
 
+/// Lines the tokens in an area up in one column per player. Tokens slide into their spot
+/// unless animations are skipped.
 pub fn fix_token_positions(
-    population_query: Query<(Entity, &Population, &Transform, &FixTokenPositions), Without<Token>>,
+    area_query: Query<(Entity, &Transform), (With<FixTokenPositions>, Without<Token>)>,
+    populations: Populations,
     mut token_transform_query: Query<&mut Transform, With<Token>>,
+    animation_settings: Res<TokenAnimationSettings>,
     mut commands: Commands,
 ) {
-    for (area_entity, pop, area_transform, _) in population_query.iter() {
+    for (area_entity, area_transform) in area_query.iter() {
+        let Some(pop) = populations.get(area_entity) else {
+            continue;
+        };
         for (player_index, (_, tokens)) in pop.player_tokens().iter().enumerate() {
             for (token_index, token) in tokens.iter().enumerate() {
                 if let Ok(mut token_transform) = token_transform_query.get_mut(*token) {
-                    token_transform.translation = area_transform.translation
+                    let target = area_transform.translation
                         + vec3(
                         (player_index * 15) as f32,
                         ((token_index as i32) * -5) as f32,
                         0.0,
                     );
+                    if animation_settings.skip_animations {
+                        token_transform.translation = target;
+                        token_transform.scale = TOKEN_SCALE;
+                    } else if token_transform.translation != target
+                        || token_transform.scale != TOKEN_SCALE
+                    {
+                        commands.entity(*token).insert(TokenTween::new(
+                            &token_transform,
+                            target,
+                            TOKEN_SCALE,
+                            animation_settings.duration(),
+                        ));
+                    }
                 }
             }
         }
@@ -150,29 +282,22 @@ This is 100% needed to be able to test expansion and stuff.
 */
 pub fn move_tokens_from_stock_to_area(
     mut move_commands: MessageReader<MoveTokensFromStockToAreaCommand>,
-    mut population_query: Query<(&mut Population, &Transform)>,
-    mut player_query: Query<(&mut PlayerAreas, &mut TokenStock, &Faction)>,
+    area_query: Query<&Transform, With<MaxPopulation>>,
+    mut player_query: Query<(&mut TokenStock, &Faction)>,
     mut commands: Commands,
     game_factions: Res<AvailableFactions>,
 ) {
     for ev in move_commands.read() {
-        if let Ok((mut player_areas, mut stock, faction)) = player_query.get_mut(ev.player_entity) {
-            if let Ok((mut population, area_transform)) = population_query.get_mut(ev.area_entity) {
+        if let Ok((mut stock, faction)) = player_query.get_mut(ev.player_entity) {
+            if let Ok(area_transform) = area_query.get(ev.area_entity) {
                 if let Some(tokens_to_move) = stock.remove_tokens_from_stock(ev.number_of_tokens) {
                     tokens_to_move.iter().for_each(|t| {
-                        population.add_token_to_area(ev.player_entity, *t);
-                        player_areas.add_token_to_area(ev.area_entity, *t);
                         commands.entity(*t).insert((
-                            Sprite {
-                                image: game_factions
-                                    .faction_icons
-                                    .get(&faction.faction)
-                                    .unwrap()
-                                    .clone(),
-                                ..default()
-                            },
-                            Transform::from_scale(Vec3::new(0.25, 0.25, 0.25))
-                                .with_translation(area_transform.translation),
+                            InArea(ev.area_entity),
+                            token_on_board(
+                                game_factions.faction_icons[&faction.faction].clone(),
+                                area_transform,
+                            ),
                         ));
                     });
                 }
@@ -182,6 +307,44 @@ pub fn move_tokens_from_stock_to_area(
     }
 }
 
+/// Reduces cities to their owner's tokens.
+pub fn reduce_cities(
+    mut reduce_commands: MessageReader<ReduceCity>,
+    area_query: Query<(&BuiltCity, &Transform)>,
+    mut player_query: Query<(
+        &mut CityTokenStock,
+        &mut TokenStock,
+        &mut PlayerCities,
+        &Faction,
+    )>,
+    mut commands: Commands,
+    game_factions: Res<AvailableFactions>,
+) {
+    // The city stays on the area until the commands are applied
+    let mut reduced_areas = HashSet::new();
+    for ev in reduce_commands.read() {
+        if !reduced_areas.insert(ev.area_entity) {
+            continue;
+        }
+        let Ok((built_city, area_transform)) = area_query.get(ev.area_entity) else {
+            continue;
+        };
+        let Ok((mut city_stock, mut token_stock, mut player_cities, faction)) =
+            player_query.get_mut(built_city.player)
+        else {
+            continue;
+        };
+        reduce_city(
+            &mut commands,
+            (ev.area_entity, area_transform),
+            built_city,
+            ev.replacement_tokens,
+            game_factions.faction_icons[&faction.faction].clone(),
+            (&mut city_stock, &mut token_stock, &mut player_cities),
+        );
+    }
+}
+
 pub fn print_names_of_phases(
     mut state_transition_event: MessageReader<StateTransitionEvent<GameActivity>>,
 ) {
@@ -1,4 +1,4 @@
-// pub mod console;
+pub mod console;
 pub mod components;
 pub mod concepts;
 pub mod enums;
@@ -7,5 +7,7 @@ pub mod functions;
 pub mod game_moves;
 pub mod plugins;
 pub mod general_systems;
+#[cfg(test)]
+pub mod testing;
 pub mod triggers;
 pub mod ui;
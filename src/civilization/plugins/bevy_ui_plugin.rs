@@ -2,6 +2,7 @@
 
 use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
 use crate::civilization::concepts::acquire_trade_cards::trade_card_events::HumanPlayerTradeCardsUpdated;
+use crate::civilization::concepts::hot_seat::hot_seat_resources::ActiveSeat;
 use crate::civilization::concepts::trade::trade_components::{TradeCardList, TradeCardUiRoot};
 use crate::civilization::ui::ui_builder::{
     ButtonPartial, NodePartial, UIBuilder, UiBuilderDefaults, BG_COLOR, BORDER_COLOR, CARD_COLOR,
@@ -76,9 +77,14 @@ fn handle_player_draws_cards(
     ui_builder_defaults: Res<UiBuilderDefaults>,
     trade_card_list: Query<Entity, With<TradeCardList>>,
     player_trade_cards: Query<&PlayerTradeCards, With<IsHuman>>,
+    active_seat: Res<ActiveSeat>,
 ) {
     let mut new_commands = commands;
     for event in reader.read() {
+        // With several humans at the device, only the one holding it sees their cards
+        if !active_seat.may_see(event.player_entity) {
+            continue;
+        }
         if let Ok(trade_card_list) = trade_card_list.single() {
             if let Ok(player_trade_cards) = player_trade_cards.get(event.player_entity) {
                 let grouped_cards = player_trade_cards.trade_cards_grouped_by_value();
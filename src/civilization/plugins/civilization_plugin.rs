@@ -1,25 +1,35 @@
-use crate::civilization::components::population::Population;
+use crate::civilization::components::population::MaxPopulation;
 use crate::civilization::components::*;
-use crate::civilization::concepts::acquire_trade_cards::trade_card_plugin::TradeCardPlugin;
-use crate::civilization::concepts::census::prelude::{CensusPlugin, GameInfoAndStuff};
-use crate::civilization::concepts::check_city_support::check_city_support_plugin::CitySupportPlugin;
-use crate::civilization::concepts::city_construction::city_construction_plugin::CityConstructionPlugin;
-use crate::civilization::concepts::conflict::conflict_plugin::ConflictPlugin;
+use crate::civilization::concepts::ai_personality::ai_personality_plugin::AiPersonalityPlugin;
+use crate::civilization::concepts::area_info::area_info_plugin::AreaInfoPlugin;
+#[cfg(debug_assertions)]
+use crate::civilization::concepts::board_audit::board_audit_plugin::BoardAuditPlugin;
+use crate::civilization::concepts::camera::camera_plugin::CameraPlugin;
+use crate::civilization::concepts::hot_seat::hot_seat_plugin::HotSeatPlugin;
+use crate::civilization::concepts::spectator::spectator_plugin::SpectatorPlugin;
+use crate::civilization::concepts::undo::undo_plugin::UndoPlugin;
+use crate::civilization::console::console_commands::CommandsPlugin;
 use crate::civilization::concepts::map::map_plugin::MapPlugin;
-use crate::civilization::concepts::movement::movement_plugin::MovementPlugin;
-use crate::civilization::concepts::population_expansion::population_expansion_plugin::PopulationExpansionPlugin;
-use crate::civilization::concepts::remove_surplus_population::remove_surplus_plugin::RemoveSurplusPlugin;
+use crate::civilization::concepts::phase_tracker::phase_tracker_plugin::PhaseTrackerPlugin;
+use crate::civilization::concepts::player_dashboard::player_dashboard_plugin::PlayerDashboardPlugin;
+use crate::civilization::concepts::remote_seat::remote_seat_plugin::RemoteSeatPlugin;
+use crate::civilization::concepts::remote_seat::remote_seat_systems::playing_locally;
+use crate::civilization::concepts::rules::rules_plugin::HouseRulesPlugin;
+use crate::civilization::concepts::scenario::scenario_plugin::ScenarioPlugin;
+use crate::civilization::concepts::token_animation::token_animation_plugin::TokenAnimationPlugin;
 use crate::civilization::concepts::trade::trade_plugin::TradePlugin;
-use crate::civilization::enums::GameFaction;
-use crate::civilization::events::MoveTokensFromStockToAreaCommand;
-use crate::civilization::game_moves::game_moves_plugin::GameMovesPlugin;
-use crate::civilization::general_systems::{connect_areas, fix_token_positions, move_tokens_from_stock_to_area, print_names_of_phases, start_game};
+use crate::civilization::enums::{AiDifficulty, GameFaction};
+use crate::civilization::general_systems::{fix_token_positions, print_names_of_phases, start_game};
 use crate::civilization::plugins::bevy_ui_plugin::BevyUiPlugin;
-use crate::civilization::triggers::on_add_return_token_to_stock;
+use crate::civilization::plugins::game_rules_plugin::GameRulesPlugin;
+use crate::external_bot::prelude::ExternalBotPlugin;
+use crate::heuristic_ai::prelude::HeuristicAiPlugin;
+use crate::mcts_ai::prelude::MctsAiPlugin;
 use crate::stupid_ai::prelude::*;
+use crate::trade_ai::prelude::TradeAiPlugin;
 use crate::{GameActivity, GameState};
 use bevy::app::{App, Plugin, Update};
-use bevy::prelude::{in_state, AppExtStates, IntoScheduleConfigs, OnEnter, Resource};
+use bevy::prelude::{in_state, IntoScheduleConfigs, OnEnter, Resource};
 
 pub struct CivilizationPlugin;
 
@@ -36,51 +46,60 @@ impl Plugin for CivilizationPlugin {
             true,
             false,
             3,
+            AiDifficulty::Stupid,
         ))
         .register_type::<Token>()
         .register_type::<LandPassage>()
+        .register_type::<SeaPassage>()
         .register_type::<TokenStock>()
         .register_type::<GameArea>()
-        .register_type::<Population>()
-        .register_type::<PlayerAreas>()
+        .register_type::<MaxPopulation>()
         .register_type::<Faction>()
         .register_type::<Treasury>()
-        .add_message::<MoveTokensFromStockToAreaCommand>()
-        .add_sub_state::<GameActivity>()
         .add_systems(
             Update,
             (print_names_of_phases.run_if(in_state(GameState::Playing)),),
         )
         .add_plugins((
-            // CommandsPlugin,
-            PopulationExpansionPlugin,
-            CensusPlugin,
-            MovementPlugin,
-            ConflictPlugin,
+            GameRulesPlugin,
+            CommandsPlugin,
             TradePlugin,
+            UndoPlugin,
+            ScenarioPlugin,
+            AiPersonalityPlugin,
+            MapPlugin,
+            CameraPlugin,
+            AreaInfoPlugin,
+            PlayerDashboardPlugin,
+            HotSeatPlugin,
+            SpectatorPlugin,
+            PhaseTrackerPlugin,
+            TokenAnimationPlugin,
+            BevyUiPlugin,
         ))
         .add_plugins((
-            CityConstructionPlugin,
-            RemoveSurplusPlugin,
-            CitySupportPlugin,
             StupidAiPlugin,
-            GameMovesPlugin,
-            TradeCardPlugin,
-            MapPlugin,
-            BevyUiPlugin,
+            HeuristicAiPlugin,
+            MctsAiPlugin,
+            TradeAiPlugin,
+            ExternalBotPlugin,
+            RemoteSeatPlugin,
         ))
-        .add_systems(OnEnter(GameActivity::StartGame), start_game)
+        .add_plugins(HouseRulesPlugin)
+        .add_systems(
+            OnEnter(GameActivity::StartGame),
+            start_game.run_if(playing_locally),
+        )
         // .add_plugins(WorldInspectorPlugin::new())
-        .insert_resource(GameInfoAndStuff::default())
         .add_systems(
             Update,
-            (
-                connect_areas.run_if(in_state(GameState::Playing)),
-                move_tokens_from_stock_to_area.run_if(in_state(GameState::Playing)),
-                fix_token_positions.run_if(in_state(GameState::Playing)),
-            ),
-        )
-        .add_observer(on_add_return_token_to_stock);
+            fix_token_positions.run_if(in_state(GameState::Playing)),
+        );
+
+        #[cfg(debug_assertions)]
+        {
+            app.add_plugins(BoardAuditPlugin);
+        }
     }
 }
 
@@ -94,6 +113,7 @@ pub struct DebugOptions {
     pub auto_trading: bool,
     pub print_selected_moves: bool,
     pub number_of_players: usize,
+    pub ai_difficulty: AiDifficulty,
 }
 
 impl DebugOptions {
@@ -106,6 +126,7 @@ impl DebugOptions {
         auto_trading: bool,
         print_selected_moves: bool,
         number_of_players: usize,
+        ai_difficulty: AiDifficulty,
     ) -> Self {
         Self {
             add_human_player,
@@ -116,6 +137,7 @@ impl DebugOptions {
             auto_trading,
             print_selected_moves,
             number_of_players,
+            ai_difficulty,
         }
     }
 }
@@ -0,0 +1,50 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_plugin::TradeCardPlugin;
+use crate::civilization::concepts::census::prelude::{CensusPlugin, GameInfoAndStuff};
+use crate::civilization::concepts::check_city_support::check_city_support_plugin::CitySupportPlugin;
+use crate::civilization::concepts::city_construction::city_construction_plugin::CityConstructionPlugin;
+use crate::civilization::concepts::conflict::conflict_plugin::ConflictPlugin;
+use crate::civilization::concepts::movement::movement_plugin::MovementPlugin;
+use crate::civilization::concepts::population_expansion::population_expansion_plugin::PopulationExpansionPlugin;
+use crate::civilization::concepts::remove_surplus_population::remove_surplus_plugin::RemoveSurplusPlugin;
+use crate::civilization::concepts::rules::rules_plugin::RulesPlugin;
+use crate::civilization::events::{MoveTokensFromStockToAreaCommand, ReduceCity};
+use crate::civilization::game_moves::game_moves_plugin::GameMovesPlugin;
+use crate::civilization::general_systems::{
+    connect_areas, move_tokens_from_stock_to_area, reduce_cities,
+};
+use crate::civilization::triggers::on_add_return_token_to_stock;
+use crate::{GameActivity, GameState};
+use bevy::app::{App, Plugin, Update};
+use bevy::prelude::{in_state, AppExtStates, IntoScheduleConfigs};
+
+/// The phases of a round up to acquiring trade cards and the systems that move tokens on the
+/// board, without graphics or input. The trade phase is left to whoever runs the game, since
+/// trading needs the trade plugin and someone to make the offers.
+pub struct GameRulesPlugin;
+
+impl Plugin for GameRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<GameActivity>()
+            .add_message::<MoveTokensFromStockToAreaCommand>()
+            .add_message::<ReduceCity>()
+            .insert_resource(GameInfoAndStuff::default())
+            .add_plugins((
+                PopulationExpansionPlugin,
+                CensusPlugin,
+                MovementPlugin,
+                ConflictPlugin,
+                CityConstructionPlugin,
+                RemoveSurplusPlugin,
+                CitySupportPlugin,
+                GameMovesPlugin,
+                TradeCardPlugin,
+                RulesPlugin,
+            ))
+            .add_systems(
+                Update,
+                (connect_areas, move_tokens_from_stock_to_area, reduce_cities)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_observer(on_add_return_token_to_stock);
+    }
+}
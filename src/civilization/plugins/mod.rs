@@ -1,2 +1,3 @@
 mod bevy_ui_plugin;
 pub mod civilization_plugin;
+pub mod game_rules_plugin;
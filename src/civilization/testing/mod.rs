@@ -0,0 +1,5 @@
+pub mod scripted_player;
+pub mod test_game;
+
+mod remote_seat_tests;
+mod round_tests;
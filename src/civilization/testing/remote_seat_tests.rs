@@ -0,0 +1,138 @@
+use crate::civilization::concepts::census::census_components::Census;
+use crate::civilization::concepts::remote_seat::remote_seat_components::HiddenTradeCards;
+use crate::civilization::concepts::remote_seat::remote_seat_resources::{
+    RemoteBoard, RemotePlayers,
+};
+use crate::civilization::concepts::remote_seat::remote_seat_systems::mirror_remote_board;
+use crate::civilization::enums::GameFaction;
+use crate::civilization::testing::test_game::{test_area, TestGame};
+use crate::network::network_components::{AreaView, GameView, PlayerTokens, PlayerView};
+use bevy::app::Update;
+use bevy::platform::collections::HashMap;
+use std::collections::BTreeMap;
+
+const EGYPT: u64 = 10;
+const CRETE: u64 = 20;
+
+fn area_view(map_id: i32, city_owner: Option<u64>, population: &[(u64, usize)]) -> AreaView {
+    AreaView {
+        id: map_id as u64 + 100,
+        map_id,
+        name: "Test".to_string(),
+        max_population: 3,
+        city_site: true,
+        city_owner,
+        population: population
+            .iter()
+            .map(|(player, tokens)| PlayerTokens {
+                player: *player,
+                tokens: *tokens,
+            })
+            .collect(),
+        connections: vec![],
+    }
+}
+
+fn player_view(id: u64, faction: &str, hand: Option<&[(&str, usize)]>) -> PlayerView {
+    PlayerView {
+        id,
+        faction: faction.to_string(),
+        tokens_in_stock: 0,
+        cities_in_stock: 0,
+        cities: 0,
+        trade_cards: 4,
+        hand: hand.map(|hand| {
+            hand.iter()
+                .map(|(card, count)| (card.to_string(), *count))
+                .collect::<BTreeMap<_, _>>()
+        }),
+    }
+}
+
+/// Egypt is the seat on this device, Crete plays on another one.
+fn mirrored_game() -> TestGame {
+    let mut game = TestGame::new(vec![test_area(1, 3, &[2]), test_area(2, 3, &[1])]);
+    let egypt = game.add_player(GameFaction::Egypt);
+    let crete = game.add_player(GameFaction::Crete);
+    game.app
+        .insert_resource(RemotePlayers {
+            players: HashMap::from([(EGYPT, egypt), (CRETE, crete)]),
+        })
+        .init_resource::<RemoteBoard>()
+        .add_systems(Update, mirror_remote_board);
+    game
+}
+
+fn show(game: &mut TestGame, round: usize, areas: Vec<AreaView>) {
+    let mut board = game.app.world_mut().resource_mut::<RemoteBoard>();
+    board.round = round;
+    board.view = Some(GameView {
+        areas,
+        players: vec![
+            player_view(EGYPT, "Egypt", Some(&[("Ochre", 2)])),
+            player_view(CRETE, "Crete", None),
+        ],
+        offers: vec![],
+    });
+    game.app.update();
+    game.app.update();
+}
+
+#[test]
+fn test_the_board_from_the_server_is_laid_out_on_the_map() {
+    let mut game = mirrored_game();
+
+    show(
+        &mut game,
+        3,
+        vec![
+            area_view(1, None, &[(EGYPT, 3)]),
+            area_view(2, Some(CRETE), &[(EGYPT, 1)]),
+        ],
+    );
+
+    assert_eq!(game.round(), 3);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 3);
+    assert_eq!(game.population(GameFaction::Egypt, 2), 1);
+    assert_eq!(game.city_owner(2), Some(GameFaction::Crete));
+    assert_eq!(game.trade_cards(GameFaction::Egypt), 2);
+    let world = game.app.world();
+    assert_eq!(
+        world
+            .get::<Census>(game.player(GameFaction::Egypt))
+            .map(|census| census.population),
+        Some(4)
+    );
+    assert_eq!(
+        world.get::<HiddenTradeCards>(game.player(GameFaction::Crete)),
+        Some(&HiddenTradeCards(4))
+    );
+}
+
+#[test]
+fn test_the_map_follows_the_server_when_the_board_changes() {
+    let mut game = mirrored_game();
+    show(
+        &mut game,
+        3,
+        vec![
+            area_view(1, None, &[(EGYPT, 3)]),
+            area_view(2, Some(CRETE), &[]),
+        ],
+    );
+
+    show(
+        &mut game,
+        4,
+        vec![
+            area_view(1, None, &[(EGYPT, 1), (CRETE, 2)]),
+            area_view(2, Some(EGYPT), &[]),
+        ],
+    );
+
+    assert_eq!(game.population(GameFaction::Egypt, 1), 1);
+    assert_eq!(game.population(GameFaction::Crete, 1), 2);
+    assert_eq!(game.city_owner(2), Some(GameFaction::Egypt));
+    assert_eq!(game.tokens_in_stock(GameFaction::Egypt), 46);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
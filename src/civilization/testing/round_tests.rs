@@ -0,0 +1,264 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::map::map_plugin::Area;
+use crate::civilization::enums::GameFaction;
+use crate::civilization::testing::scripted_player::ScriptedMove;
+use crate::civilization::testing::test_game::{test_area, TestGame};
+use crate::GameActivity;
+
+/// A city site in the middle with room to gather an army on each side.
+fn city_and_staging_areas() -> TestGame {
+    TestGame::new(vec![
+        test_area(1, 3, &[2, 3]),
+        test_area(2, 12, &[1]),
+        test_area(3, 12, &[1]),
+    ])
+}
+
+#[test]
+fn test_a_round_doubles_a_lone_token() {
+    let mut game = TestGame::new(vec![test_area(1, 3, &[2]), test_area(2, 2, &[1])]);
+    game.add_player(GameFaction::Egypt);
+    game.place_tokens(GameFaction::Egypt, 1, 1);
+
+    game.play_round();
+
+    assert_eq!(game.round(), 2);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 2);
+    assert_eq!(game.tokens_in_stock(GameFaction::Egypt), 45);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_scripted_movement_is_played() {
+    let mut game = TestGame::new(vec![test_area(1, 3, &[2]), test_area(2, 2, &[1])]);
+    game.add_player(GameFaction::Egypt);
+    game.place_tokens(GameFaction::Egypt, 1, 3);
+    game.script(
+        GameFaction::Egypt,
+        [ScriptedMove::Move {
+            from: 1,
+            to: 2,
+            tokens: 2,
+        }],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    assert_eq!(game.population(GameFaction::Egypt, 1), 1);
+    assert_eq!(game.population(GameFaction::Egypt, 2), 2);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_scripted_city_is_built_and_supported() {
+    let mut game = TestGame::new(vec![
+        Area {
+            city_site: true,
+            ..test_area(1, 6, &[2])
+        },
+        test_area(2, 2, &[1]),
+    ]);
+    game.add_player(GameFaction::Egypt);
+    game.place_tokens(GameFaction::Egypt, 1, 6);
+    game.place_tokens(GameFaction::Egypt, 2, 4);
+    game.script(GameFaction::Egypt, [ScriptedMove::BuildCity(1)]);
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::AcquireTradeCards);
+
+    assert_eq!(game.city_owner(1), Some(GameFaction::Egypt));
+    assert_eq!(game.population(GameFaction::Egypt, 1), 0);
+    assert_eq!(game.population(GameFaction::Egypt, 2), 2);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_city_conflict_with_one_attacker_above_six_tokens() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.add_player(GameFaction::Crete);
+    game.place_city(GameFaction::Egypt, 1);
+    game.place_tokens(GameFaction::Crete, 2, 8);
+    game.script(
+        GameFaction::Crete,
+        [ScriptedMove::Move {
+            from: 2,
+            to: 1,
+            tokens: 7,
+        }],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    // The city is replaced by six tokens that fight the attackers as a regular conflict
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(
+        game.population(GameFaction::Egypt, 1) + game.population(GameFaction::Crete, 1),
+        3
+    );
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_fallen_city_is_pillaged() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.add_player(GameFaction::Crete);
+    game.place_city(GameFaction::Egypt, 1);
+    game.fill_treasury(GameFaction::Egypt, 5);
+    game.give_trade_card(GameFaction::Egypt, TradeCard::Ochre);
+    game.place_tokens(GameFaction::Crete, 2, 8);
+    game.script(
+        GameFaction::Crete,
+        [ScriptedMove::Move {
+            from: 2,
+            to: 1,
+            tokens: 7,
+        }],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    assert_eq!(game.tokens_in_treasury(GameFaction::Egypt), 2);
+    assert_eq!(game.tokens_in_treasury(GameFaction::Crete), 3);
+    assert_eq!(game.trade_cards(GameFaction::Egypt), 0);
+    assert_eq!(game.trade_cards(GameFaction::Crete), 1);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_fallen_city_with_an_empty_treasury_is_pillaged_for_nothing() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.add_player(GameFaction::Crete);
+    game.place_city(GameFaction::Egypt, 1);
+    game.place_tokens(GameFaction::Crete, 2, 8);
+    game.script(
+        GameFaction::Crete,
+        [ScriptedMove::Move {
+            from: 2,
+            to: 1,
+            tokens: 7,
+        }],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(game.tokens_in_treasury(GameFaction::Crete), 0);
+    assert_eq!(game.trade_cards(GameFaction::Crete), 0);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_city_conflict_with_one_strong_and_one_weak_attacker() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.add_player(GameFaction::Crete);
+    game.add_player(GameFaction::Asia);
+    game.place_city(GameFaction::Egypt, 1);
+    game.place_tokens(GameFaction::Crete, 2, 8);
+    game.place_tokens(GameFaction::Asia, 3, 3);
+    game.script(
+        GameFaction::Crete,
+        [ScriptedMove::Move {
+            from: 2,
+            to: 1,
+            tokens: 7,
+        }],
+    );
+    game.script(
+        GameFaction::Asia,
+        [ScriptedMove::Move {
+            from: 3,
+            to: 1,
+            tokens: 2,
+        }],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    // Egypt has nobody to choose from, the city falls and everyone fights it out at once
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(game.population(GameFaction::Asia, 1), 0);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 1);
+    assert_eq!(game.population(GameFaction::Crete, 1), 2);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_city_conflict_with_two_attackers_above_six_tokens() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.add_player(GameFaction::Crete);
+    game.add_player(GameFaction::Asia);
+    game.place_city(GameFaction::Egypt, 1);
+    game.place_tokens(GameFaction::Crete, 2, 9);
+    game.place_tokens(GameFaction::Asia, 3, 8);
+    game.script(
+        GameFaction::Crete,
+        [ScriptedMove::Move {
+            from: 2,
+            to: 1,
+            tokens: 8,
+        }],
+    );
+    game.script(
+        GameFaction::Asia,
+        [ScriptedMove::Move {
+            from: 3,
+            to: 1,
+            tokens: 7,
+        }],
+    );
+    game.script(
+        GameFaction::Egypt,
+        [ScriptedMove::FightFirst(GameFaction::Asia)],
+    );
+
+    game.start(GameActivity::Census);
+    game.run_until(GameActivity::CityConstruction);
+
+    // The city falls to Egypt's six tokens fighting Asia, then everyone left fights it out
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 0);
+    assert_eq!(game.population(GameFaction::Asia, 1), 0);
+    assert_eq!(game.population(GameFaction::Crete, 1), 7);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_unsupported_city_is_reduced_to_tokens() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.place_city(GameFaction::Egypt, 1);
+
+    game.start(GameActivity::CheckCitySupport);
+    game.run_until(GameActivity::AcquireTradeCards);
+
+    // The city makes way for as many tokens as the area supports
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 3);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
+
+#[test]
+fn test_city_reduced_with_an_empty_stock_leaves_the_area_empty() {
+    let mut game = city_and_staging_areas();
+    game.add_player(GameFaction::Egypt);
+    game.place_city(GameFaction::Egypt, 1);
+    let stock = game.tokens_in_stock(GameFaction::Egypt);
+    game.fill_treasury(GameFaction::Egypt, stock);
+
+    game.start(GameActivity::CheckCitySupport);
+    game.run_until(GameActivity::AcquireTradeCards);
+
+    assert_eq!(game.city_owner(1), None);
+    assert_eq!(game.population(GameFaction::Egypt, 1), 0);
+    assert_eq!(game.board_problems(), Vec::<String>::new());
+}
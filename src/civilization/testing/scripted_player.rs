@@ -0,0 +1,88 @@
+use crate::civilization::components::{Faction, GameArea};
+use crate::civilization::enums::GameFaction;
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use crate::civilization::game_moves::game_moves_systems::GameMoveWriters;
+use bevy::prelude::{Add, Component, Entity, On, Query};
+use std::collections::VecDeque;
+
+/// A move for a scripted player, with areas given by their map id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptedMove {
+    Move { from: i32, to: i32, tokens: usize },
+    BuildCity(i32),
+    /// A city owner attacked by several players fights this one first.
+    FightFirst(GameFaction),
+}
+
+/// Plays its script in order. When the next scripted move is not available the player
+/// expands into its first area, ends its movement and city construction, fights its first
+/// attacker and gives up its first city when it has too many.
+#[derive(Component, Debug, Default)]
+pub struct ScriptedPlayer {
+    pub script: VecDeque<ScriptedMove>,
+}
+
+pub fn on_add_available_moves(
+    trigger: On<Add, AvailableMoves>,
+    mut players: Query<(&AvailableMoves, &mut ScriptedPlayer)>,
+    areas: Query<&GameArea>,
+    factions: Query<&Faction>,
+    mut writers: GameMoveWriters,
+) {
+    let player = trigger.event().entity;
+    let Ok((available_moves, mut scripted)) = players.get_mut(player) else {
+        return;
+    };
+    let area_id = |area: Entity| areas.get(area).map(|area| area.id).ok();
+    let mut moves = available_moves.moves.iter().collect::<Vec<_>>();
+    moves.sort_by_key(|(index, _)| **index);
+
+    let scripted_move = scripted.script.front().and_then(|next| {
+        moves
+            .iter()
+            .find_map(|(_, game_move)| match (next, game_move) {
+                (
+                    ScriptedMove::Move { from, to, tokens },
+                    Move::Movement(movement_move)
+                    | Move::AttackArea(movement_move)
+                    | Move::AttackCity(movement_move),
+                ) if area_id(movement_move.source) == Some(*from)
+                    && area_id(movement_move.target) == Some(*to) =>
+                {
+                    Some((*game_move, *tokens))
+                }
+                (ScriptedMove::BuildCity(area), Move::CityConstruction(build_city_move))
+                    if area_id(build_city_move.target) == Some(*area) =>
+                {
+                    Some((*game_move, 0))
+                }
+                (ScriptedMove::FightFirst(faction), Move::ChooseAttacker(choose_move))
+                    if factions
+                        .get(choose_move.attacker)
+                        .is_ok_and(|attacker| attacker.faction == *faction) =>
+                {
+                    Some((*game_move, 0))
+                }
+                _ => None,
+            })
+    });
+    if let Some((game_move, tokens)) = scripted_move {
+        scripted.script.pop_front();
+        writers.write_move(player, game_move, tokens);
+        return;
+    }
+
+    let fallback = moves.iter().find_map(|(_, game_move)| match game_move {
+        Move::PopulationExpansion(pop_exp_move) => Some((*game_move, pop_exp_move.max_tokens)),
+        Move::EndMovement
+        | Move::EndCityConstruction
+        | Move::ChooseAttacker(_)
+        | Move::EliminateCity(_) => {
+            Some((*game_move, 0))
+        }
+        _ => None,
+    });
+    if let Some((game_move, tokens)) = fallback {
+        writers.write_move(player, game_move, tokens);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::civilization::components::{
+    AreaTokens, BuiltCity, CityTokenStock, Faction, PlayerCities, Token, TokenStock, Treasury,
+};
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::TradeCard;
+use crate::civilization::concepts::board_audit::board_audit_systems::BoardState;
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::city_construction::city_construction_events::BuildCityCommand;
+use crate::civilization::concepts::map::map_plugin::{spawn_area, Area, AvailableFactions};
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use crate::civilization::enums::{AiDifficulty, GameFaction};
+use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::civilization::functions::build_city_in_area;
+use crate::civilization::general_systems::spawn_player;
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::civilization::plugins::game_rules_plugin::GameRulesPlugin;
+use crate::civilization::testing::scripted_player::{
+    on_add_available_moves, ScriptedMove, ScriptedPlayer,
+};
+use crate::{GameActivity, GameState};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::{
+    default, App, AppExtStates, Commands, Entity, Handle, In, MinimalPlugins, NextState, OnEnter,
+    Query, ResMut, State, Transform,
+};
+use bevy::state::app::StatesPlugin;
+use std::collections::HashMap;
+
+/// Frames a test may take to reach an activity before it is considered stuck.
+const MAX_FRAMES: usize = 500;
+
+/// An area of a test map.
+pub fn test_area(id: i32, max_population: usize, land_connections: &[i32]) -> Area {
+    Area {
+        id,
+        x: 0.0,
+        y: 0.0,
+        max_population,
+        land_connections: land_connections.to_vec(),
+        sea_connections: vec![],
+        start_area: None,
+        city_site: false,
+        flood_plain: false,
+        city_flood: false,
+        volcano: false,
+    }
+}
+
+/// A headless game running the rule plugins on a small map, played by scripted players.
+/// Trading is left out, the round goes straight on to population expansion.
+pub struct TestGame {
+    pub app: App,
+    areas: HashMap<i32, Entity>,
+    players: HashMap<GameFaction, Entity>,
+}
+
+impl TestGame {
+    pub fn new(map: Vec<Area>) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .insert_resource(DebugOptions::new(
+                false,
+                GameFaction::Egypt,
+                false,
+                false,
+                false,
+                false,
+                false,
+                0,
+                AiDifficulty::Stupid,
+            ))
+            .insert_resource(TokenAnimationSettings {
+                skip_animations: true,
+                ..default()
+            })
+            .init_resource::<AvailableFactions>()
+            .add_plugins(GameRulesPlugin)
+            .add_systems(OnEnter(GameActivity::Trade), skip_trade)
+            .add_observer(on_add_available_moves);
+
+        let areas = map
+            .iter()
+            .map(|area| {
+                let entity = app
+                    .world_mut()
+                    .run_system_once_with(
+                        |In(area): In<Area>, mut commands: Commands| {
+                            spawn_area(&mut commands, &area, "Test".to_string())
+                        },
+                        area.clone(),
+                    )
+                    .unwrap();
+                (area.id, entity)
+            })
+            .collect();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+        TestGame {
+            app,
+            areas,
+            players: HashMap::new(),
+        }
+    }
+
+    pub fn add_player(&mut self, faction: GameFaction) -> Entity {
+        let n = self.players.len() + 1;
+        let mut factions = self.app.world_mut().resource_mut::<AvailableFactions>();
+        factions.faction_icons.insert(faction, Handle::default());
+        factions
+            .faction_city_icons
+            .insert(faction, Handle::default());
+        let player = self
+            .app
+            .world_mut()
+            .run_system_once_with(
+                |In((faction, n)): In<(GameFaction, usize)>, mut commands: Commands| {
+                    let player = spawn_player(&mut commands, faction, n, 0);
+                    commands.entity(player).insert(ScriptedPlayer::default());
+                    player
+                },
+                (faction, n),
+            )
+            .unwrap();
+        self.players.insert(faction, player);
+        player
+    }
+
+    pub fn area(&self, id: i32) -> Entity {
+        self.areas[&id]
+    }
+
+    pub fn player(&self, faction: GameFaction) -> Entity {
+        self.players[&faction]
+    }
+
+    pub fn place_tokens(&mut self, faction: GameFaction, area: i32, tokens: usize) {
+        let message = MoveTokensFromStockToAreaCommand {
+            area_entity: self.area(area),
+            player_entity: self.player(faction),
+            number_of_tokens: tokens,
+        };
+        self.app.world_mut().write_message(message);
+        self.app.update();
+    }
+
+    pub fn place_city(&mut self, faction: GameFaction, area: i32) {
+        let command = BuildCityCommand::new(self.player(faction), self.area(area));
+        self.app
+            .world_mut()
+            .run_system_once_with(place_city, command)
+            .unwrap();
+        self.app.update();
+    }
+
+    /// Moves tokens from the player's stock into its treasury.
+    pub fn fill_treasury(&mut self, faction: GameFaction, tokens: usize) {
+        let player = self.player(faction);
+        let mut entity = self.app.world_mut().entity_mut(player);
+        let moved = (0..tokens)
+            .filter_map(|_| {
+                entity
+                    .get_mut::<TokenStock>()
+                    .unwrap()
+                    .remove_token_from_stock()
+            })
+            .collect::<Vec<_>>();
+        let mut treasury = entity.get_mut::<Treasury>().unwrap();
+        for token in moved {
+            treasury.add_token_to_treasury(token);
+        }
+    }
+
+    pub fn give_trade_card(&mut self, faction: GameFaction, card: TradeCard) {
+        let player = self.player(faction);
+        self.app
+            .world_mut()
+            .get_mut::<PlayerTradeCards>(player)
+            .unwrap()
+            .add_trade_card(card);
+    }
+
+    pub fn script(&mut self, faction: GameFaction, moves: impl IntoIterator<Item = ScriptedMove>) {
+        let player = self.player(faction);
+        self.app
+            .world_mut()
+            .get_mut::<ScriptedPlayer>(player)
+            .unwrap()
+            .script
+            .extend(moves);
+    }
+
+    pub fn activity(&self) -> GameActivity {
+        self.app
+            .world()
+            .resource::<State<GameActivity>>()
+            .get()
+            .clone()
+    }
+
+    pub fn start(&mut self, activity: GameActivity) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameActivity>>()
+            .set(activity);
+        self.app.update();
+    }
+
+    /// Runs the game until the given activity has started.
+    pub fn run_until(&mut self, activity: GameActivity) {
+        for _ in 0..MAX_FRAMES {
+            if self.activity() == activity {
+                return;
+            }
+            self.app.update();
+        }
+        panic!(
+            "The game got stuck in {:?} on its way to {:?}",
+            self.activity(),
+            activity
+        );
+    }
+
+    /// Plays from population expansion until the next round starts.
+    pub fn play_round(&mut self) {
+        if self.activity() != GameActivity::PopulationExpansion {
+            self.start(GameActivity::PopulationExpansion);
+        }
+        self.run_until(GameActivity::Census);
+        self.run_until(GameActivity::PopulationExpansion);
+    }
+
+    pub fn population(&self, faction: GameFaction, area: i32) -> usize {
+        let world = self.app.world();
+        let player = self.player(faction);
+        world
+            .get::<AreaTokens>(self.area(area))
+            .map_or(0, |area_tokens| {
+                area_tokens
+                    .tokens()
+                    .iter()
+                    .filter(|token| {
+                        world
+                            .get::<Token>(**token)
+                            .is_some_and(|token| token.player() == player)
+                    })
+                    .count()
+            })
+    }
+
+    pub fn city_owner(&self, area: i32) -> Option<GameFaction> {
+        let built_city = self.app.world().get::<BuiltCity>(self.area(area))?;
+        self.app
+            .world()
+            .get::<Faction>(built_city.player)
+            .map(|faction| faction.faction)
+    }
+
+    pub fn tokens_in_stock(&self, faction: GameFaction) -> usize {
+        self.app
+            .world()
+            .get::<TokenStock>(self.player(faction))
+            .map_or(0, |stock| stock.tokens_in_stock())
+    }
+
+    pub fn tokens_in_treasury(&self, faction: GameFaction) -> usize {
+        self.app
+            .world()
+            .get::<Treasury>(self.player(faction))
+            .map_or(0, |treasury| treasury.tokens_in_treasury())
+    }
+
+    pub fn trade_cards(&self, faction: GameFaction) -> usize {
+        self.app
+            .world()
+            .get::<PlayerTradeCards>(self.player(faction))
+            .map_or(0, |cards| cards.cards().values().sum())
+    }
+
+    pub fn round(&self) -> usize {
+        self.app.world().resource::<GameInfoAndStuff>().round
+    }
+
+    pub fn board_problems(&mut self) -> Vec<String> {
+        self.app
+            .world_mut()
+            .run_system_once(|board: BoardState| board.problems())
+            .unwrap()
+    }
+}
+
+fn place_city(
+    In(command): In<BuildCityCommand>,
+    mut commands: Commands,
+    mut players: Query<(&mut CityTokenStock, &mut PlayerCities)>,
+    areas: Query<&Transform>,
+) {
+    if let (Ok((mut city_stock, mut player_cities)), Ok(area_transform)) =
+        (players.get_mut(command.player), areas.get(command.area))
+    {
+        build_city_in_area(
+            &mut commands,
+            Handle::default(),
+            &command,
+            &mut city_stock,
+            &mut player_cities,
+            area_transform,
+        );
+    }
+}
+
+fn skip_trade(mut next_state: ResMut<NextState<GameActivity>>) {
+    next_state.set(GameActivity::PopulationExpansion);
+}
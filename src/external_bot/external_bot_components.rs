@@ -0,0 +1,66 @@
+use crate::network::network_components::{MoveChoice, RemoteMove};
+use bevy::platform::time::Instant;
+use bevy::prelude::{Component, Reflect};
+use std::io::Write;
+use std::process::{Child, ChildStdin};
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
+
+/// A player whose moves are chosen by a program outside the game.
+#[derive(Component, Debug, Reflect, Default)]
+pub struct ExternalBot;
+
+/// The running bot program. Lines it prints end up in `replies`, the process is killed when
+/// the component goes away.
+#[derive(Component)]
+pub struct BotProcess {
+    pub child: Child,
+    pub stdin: ChildStdin,
+    pub replies: Mutex<Receiver<String>>,
+}
+
+impl BotProcess {
+    pub fn send(&mut self, message: &serde_json::Value) -> std::io::Result<()> {
+        writeln!(self.stdin, "{}", message)?;
+        self.stdin.flush()
+    }
+
+    pub fn next_reply(&self) -> Option<String> {
+        self.replies.lock().ok()?.try_recv().ok()
+    }
+}
+
+impl Drop for BotProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The bot has been sent the current moves and we are waiting for its answer. Move ids are
+/// indexes into `moves`.
+#[derive(Component, Debug)]
+pub struct PendingBotRequest {
+    pub request_id: u64,
+    pub sent_at: Instant,
+    pub moves: Vec<RemoteMove>,
+}
+
+impl PendingBotRequest {
+    pub fn new(request_id: u64, moves: Vec<RemoteMove>) -> Self {
+        PendingBotRequest {
+            request_id,
+            sent_at: Instant::now(),
+            moves,
+        }
+    }
+}
+
+/// A move chosen by a bot, with the tokens and cards it plays it with the way network clients
+/// send them.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct BotReply {
+    pub request_id: u64,
+    #[serde(flatten)]
+    pub choice: MoveChoice,
+}
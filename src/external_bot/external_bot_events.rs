@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct SelectExternalBotMove {
+    pub player: Entity,
+}
+
+impl SelectExternalBotMove {
+    pub fn new(player: Entity) -> Self {
+        SelectExternalBotMove { player }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::external_bot::external_bot_components::{BotReply, PendingBotRequest};
+use crate::network::network_components::{GameView, MoveView, RemoteMove};
+use bevy::prelude::Entity;
+use serde_json::{json, Value};
+
+/// Entities are sent to bots as the number Bevy packs them into.
+pub fn entity_id(entity: Entity) -> u64 {
+    entity.to_bits()
+}
+
+/// Asks the bot of `player` for a move, the moves are described the way network clients get
+/// them.
+pub fn request_message(
+    request_id: u64,
+    phase: &str,
+    player: Entity,
+    state: &GameView,
+    moves: &[MoveView],
+) -> Value {
+    json!({
+        "type": "request",
+        "request_id": request_id,
+        "phase": phase,
+        "player": entity_id(player),
+        "state": state,
+        "moves": moves,
+    })
+}
+
+pub fn error_message(request_id: Option<u64>, message: &str) -> Value {
+    json!({ "type": "error", "request_id": request_id, "message": message })
+}
+
+pub fn parse_reply(line: &str) -> Result<BotReply, String> {
+    serde_json::from_str(line.trim()).map_err(|err| format!("could not read the reply: {err}"))
+}
+
+/// The move a reply picks, or why it cannot be played.
+pub fn choose_move<'a>(
+    reply: &BotReply,
+    pending: &'a PendingBotRequest,
+) -> Result<&'a RemoteMove, String> {
+    if reply.request_id != pending.request_id {
+        return Err(format!(
+            "request {} is no longer open, answer request {}",
+            reply.request_id, pending.request_id
+        ));
+    }
+    pending
+        .moves
+        .get(reply.choice.move_id)
+        .ok_or_else(|| format!("there is no move with id {}", reply.choice.move_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::game_moves::game_moves_components::{
+        AvailableMoves, Move, MovementMove, TradeMove,
+    };
+    use crate::network::network_functions::{move_views, remote_moves, tokens_for};
+    use bevy::platform::collections::HashMap;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn movement_moves() -> AvailableMoves {
+        let movement_move = MovementMove::new(create_entity(), create_entity(), create_entity(), 3);
+        AvailableMoves::new(HashMap::from([
+            (1, Move::Movement(movement_move)),
+            (2, Move::EndMovement),
+        ]))
+    }
+
+    fn pending(request_id: u64, available_moves: &AvailableMoves) -> PendingBotRequest {
+        let (moves, _): (Vec<_>, Vec<_>) =
+            remote_moves(create_entity(), available_moves, &HashMap::default())
+                .into_iter()
+                .unzip();
+        PendingBotRequest::new(request_id, moves)
+    }
+
+    #[test]
+    fn test_reply_without_tokens_uses_them_all() {
+        let reply = parse_reply(r#"{"request_id": 7, "move_id": 0}"#).unwrap();
+
+        let pending = pending(7, &movement_moves());
+        let chosen = choose_move(&reply, &pending).unwrap();
+
+        let RemoteMove::Play(game_move) = chosen else {
+            panic!("expected a move to play, got {:?}", chosen);
+        };
+        assert!(matches!(game_move, Move::Movement(_)));
+        assert_eq!(tokens_for(game_move, reply.choice.tokens), Ok(3));
+    }
+
+    #[test]
+    fn test_too_many_tokens_is_illegal() {
+        let reply = parse_reply(r#"{"request_id": 1, "move_id": 0, "tokens": 4}"#).unwrap();
+
+        let pending = pending(1, &movement_moves());
+        let Ok(RemoteMove::Play(game_move)) = choose_move(&reply, &pending) else {
+            panic!("expected a move to play");
+        };
+        assert!(tokens_for(game_move, reply.choice.tokens).is_err());
+    }
+
+    #[test]
+    fn test_unknown_move_is_illegal() {
+        let reply = parse_reply(r#"{"request_id": 1, "move_id": 9}"#).unwrap();
+
+        assert!(choose_move(&reply, &pending(1, &movement_moves())).is_err());
+    }
+
+    #[test]
+    fn test_answer_to_an_old_request_is_illegal() {
+        let reply = parse_reply(r#"{"request_id": 1, "move_id": 0}"#).unwrap();
+
+        assert!(choose_move(&reply, &pending(2, &movement_moves())).is_err());
+    }
+
+    #[test]
+    fn test_garbage_is_not_a_reply() {
+        assert!(parse_reply("move 1 please").is_err());
+    }
+
+    #[test]
+    fn test_reply_reads_the_cards() {
+        let reply = parse_reply(
+            r#"{"request_id": 3, "move_id": 0, "gives": {"Ochre": 3}, "asks": {"Salt": 3}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(reply.choice.gives.get("Ochre"), Some(&3));
+        assert_eq!(reply.choice.asks.get("Salt"), Some(&3));
+    }
+
+    #[test]
+    fn test_moves_are_sent_in_id_order() {
+        let actions = remote_moves(create_entity(), &movement_moves(), &HashMap::default())
+            .into_iter()
+            .map(|(_, action)| action)
+            .collect();
+        let moves = serde_json::to_value(move_views(actions)).unwrap();
+
+        assert_eq!(moves[0]["id"], 0);
+        assert_eq!(moves[0]["kind"], "move");
+        assert_eq!(moves[0]["max_tokens"], 3);
+        assert_eq!(moves[1]["kind"], "end_movement");
+    }
+
+    #[test]
+    fn test_trade_moves_are_sent_as_actions() {
+        let receiver = create_entity();
+        let available_moves = AvailableMoves::new(HashMap::from([
+            (
+                1,
+                Move::Trade(TradeMove::ProposeTrade(receiver, HashMap::default())),
+            ),
+            (2, Move::Trade(TradeMove::StopTrading)),
+        ]));
+        let actions = remote_moves(create_entity(), &available_moves, &HashMap::default())
+            .into_iter()
+            .map(|(_, action)| action)
+            .collect();
+        let moves = serde_json::to_value(move_views(actions)).unwrap();
+
+        assert_eq!(moves[0]["kind"], "propose_trade");
+        assert_eq!(moves[0]["receiver"], entity_id(receiver));
+        assert_eq!(moves[1]["kind"], "stop_trading");
+    }
+}
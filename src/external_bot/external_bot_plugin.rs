@@ -0,0 +1,24 @@
+use crate::external_bot::prelude::*;
+use crate::GameState;
+use bevy::app::{Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs};
+
+pub struct ExternalBotPlugin;
+
+impl Plugin for ExternalBotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ExternalBot>()
+            .register_type::<ExternalBotSettings>()
+            .init_resource::<ExternalBotSettings>()
+            .add_message::<SelectExternalBotMove>()
+            .add_systems(
+                Update,
+                (request_bot_moves, receive_bot_moves)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_observer(on_add_available_moves_for_external_bot)
+            .add_observer(on_remove_available_moves_for_external_bot)
+            .add_observer(on_add_external_bot);
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::{Reflect, Resource};
+
+/// The program every external bot player runs as. Each bot player gets a process of its own.
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct ExternalBotSettings {
+    /// Program to start, nothing is started while this is empty
+    pub command: String,
+    pub args: Vec<String>,
+    /// Time a bot has to answer before the heuristic AI moves for it
+    pub timeout_ms: u64,
+}
+
+impl Default for ExternalBotSettings {
+    fn default() -> Self {
+        ExternalBotSettings {
+            command: String::new(),
+            args: vec![],
+            timeout_ms: 5000,
+        }
+    }
+}
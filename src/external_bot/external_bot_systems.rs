@@ -0,0 +1,137 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::civilization::game_moves::game_moves_systems::GameMoveWriters;
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::external_bot::external_bot_components::{BotProcess, PendingBotRequest};
+use crate::external_bot::external_bot_events::SelectExternalBotMove;
+use crate::external_bot::external_bot_functions::{
+    choose_move, error_message, parse_reply, request_message,
+};
+use crate::external_bot::external_bot_resources::ExternalBotSettings;
+use crate::heuristic_ai::heuristic_ai_events::SelectHeuristicMove;
+use crate::network::network_functions::{move_views, remote_moves};
+use crate::network::network_systems::{RemoteObservation, RemoteTrading};
+use crate::trade_ai::trade_ai_events::SelectTradeMove;
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    debug, warn, Commands, Entity, Local, MessageReader, MessageWriter, Name, Query, Res, State,
+};
+use std::time::Duration;
+
+/// Moves for a bot that is missing, broken or too slow.
+#[derive(SystemParam)]
+pub struct BotFallback<'w> {
+    heuristic: MessageWriter<'w, SelectHeuristicMove>,
+    trade: MessageWriter<'w, SelectTradeMove>,
+}
+
+impl BotFallback<'_> {
+    pub fn hand_over(&mut self, player: Entity, phase: &GameActivity) {
+        if *phase == GameActivity::Trade {
+            self.trade.write(SelectTradeMove::new(player));
+        } else {
+            self.heuristic.write(SelectHeuristicMove::new(player));
+        }
+    }
+}
+
+/// Sends the board and the available moves to the bot of every player that has to move.
+pub fn request_bot_moves(
+    mut event_reader: MessageReader<SelectExternalBotMove>,
+    mut players: Query<(&Name, &AvailableMoves, Option<&mut BotProcess>)>,
+    observation: RemoteObservation,
+    mut fallback: BotFallback,
+    mut next_request_id: Local<u64>,
+    mut commands: Commands,
+) {
+    for event in event_reader.read() {
+        let Ok((player_name, available_moves, bot_process)) = players.get_mut(event.player) else {
+            continue;
+        };
+        let Some(mut bot_process) = bot_process else {
+            fallback.hand_over(event.player, observation.phase());
+            continue;
+        };
+        let (moves, actions): (Vec<_>, Vec<_>) =
+            remote_moves(event.player, available_moves, &observation.offers())
+                .into_iter()
+                .unzip();
+        if moves.is_empty() {
+            fallback.hand_over(event.player, observation.phase());
+            continue;
+        }
+        *next_request_id += 1;
+        let request = request_message(
+            *next_request_id,
+            &format!("{:?}", observation.phase()),
+            event.player,
+            &observation.view(event.player),
+            &move_views(actions),
+        );
+        match bot_process.send(&request) {
+            Ok(()) => {
+                commands
+                    .entity(event.player)
+                    .insert(PendingBotRequest::new(*next_request_id, moves));
+            }
+            Err(err) => {
+                warn!("The bot for {} stopped listening: {}", player_name, err);
+                commands.entity(event.player).remove::<BotProcess>();
+                fallback.hand_over(event.player, observation.phase());
+            }
+        }
+    }
+}
+
+/// Plays the moves bots answer with. Bad answers are reported back and the bot may try again
+/// until its time is up, after which an AI moves instead.
+pub fn receive_bot_moves(
+    mut players: Query<(
+        Entity,
+        &Name,
+        &mut BotProcess,
+        &PendingBotRequest,
+        &PlayerTradeCards,
+    )>,
+    mut writers: GameMoveWriters,
+    mut trading: RemoteTrading,
+    mut fallback: BotFallback,
+    phase: Res<State<GameActivity>>,
+    (settings, debug_options): (Res<ExternalBotSettings>, Res<DebugOptions>),
+    mut commands: Commands,
+) {
+    for (player, player_name, mut bot_process, pending, hand) in players.iter_mut() {
+        let mut answered = false;
+        while let Some(line) = bot_process.next_reply() {
+            let played = parse_reply(&line).and_then(|reply| {
+                let remote_move = choose_move(&reply, pending)?;
+                if debug_options.print_selected_moves {
+                    debug!("The bot for {} plays {:?}", player_name, remote_move);
+                }
+                trading.play(player, remote_move, &reply.choice, hand, &mut writers)
+            });
+            match played {
+                Ok(()) => {
+                    answered = true;
+                    break;
+                }
+                Err(message) => {
+                    debug!("The bot for {} made a mistake: {}", player_name, message);
+                    let _ = bot_process.send(&error_message(Some(pending.request_id), &message));
+                }
+            }
+        }
+
+        if !answered && pending.sent_at.elapsed() > Duration::from_millis(settings.timeout_ms) {
+            warn!("The bot for {} did not answer in time", player_name);
+            let _ = bot_process.send(&error_message(Some(pending.request_id), "timed out"));
+            fallback.hand_over(player, phase.get());
+            answered = true;
+        }
+
+        if answered {
+            commands.entity(player).remove::<PendingBotRequest>();
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::civilization::concepts::spectator::spectator_systems::SpectatorGate;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::external_bot::external_bot_components::{BotProcess, ExternalBot, PendingBotRequest};
+use crate::external_bot::external_bot_events::SelectExternalBotMove;
+use crate::external_bot::external_bot_functions::entity_id;
+use crate::external_bot::external_bot_resources::ExternalBotSettings;
+use bevy::prelude::{warn, Add, Commands, MessageWriter, Name, On, Query, Remove, Res, With};
+use serde_json::json;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Mutex};
+
+pub fn on_add_available_moves_for_external_bot(
+    trigger: On<Add, AvailableMoves>,
+    is_external_bot: Query<&ExternalBot>,
+    mut event_writer: MessageWriter<SelectExternalBotMove>,
+    mut gate: SpectatorGate,
+) {
+    if is_external_bot.contains(trigger.event().entity) && !gate.hold_back(trigger.event().entity) {
+        event_writer.write(SelectExternalBotMove::new(trigger.event().entity));
+    }
+}
+
+/// An answer to moves that are gone can no longer be played.
+pub fn on_remove_available_moves_for_external_bot(
+    trigger: On<Remove, AvailableMoves>,
+    pending: Query<(), With<PendingBotRequest>>,
+    mut commands: Commands,
+) {
+    if pending.contains(trigger.event().entity) {
+        commands
+            .entity(trigger.event().entity)
+            .try_remove::<PendingBotRequest>();
+    }
+}
+
+/// Starts the bot program for a new bot player and says hello with the player's id.
+pub fn on_add_external_bot(
+    trigger: On<Add, ExternalBot>,
+    names: Query<&Name>,
+    settings: Res<ExternalBotSettings>,
+    mut commands: Commands,
+) {
+    let player = trigger.event().entity;
+    if settings.command.is_empty() {
+        warn!("No external bot command is set, the heuristic AI plays instead");
+        return;
+    }
+    let mut child = match Command::new(&settings.command)
+        .args(&settings.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            warn!("Could not start the bot {}: {}", settings.command, err);
+            return;
+        }
+    };
+    let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+        let _ = child.kill();
+        return;
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut bot_process = BotProcess {
+        child,
+        stdin,
+        replies: Mutex::new(receiver),
+    };
+    let name = names.get(player).map(|name| name.as_str()).unwrap_or("");
+    let hello = json!({ "type": "hello", "player": entity_id(player), "name": name });
+    if let Err(err) = bot_process.send(&hello) {
+        warn!("The bot {} is not listening: {}", settings.command, err);
+        return;
+    }
+    commands.entity(player).insert(bot_process);
+}
@@ -0,0 +1,29 @@
+//! Players controlled by a program outside the game, talking JSON, one message per line.
+//!
+//! The game writes to the bot's stdin:
+//! - `{"type": "hello", "player": id, "name": ...}` once the bot is started
+//! - `{"type": "request", "request_id": n, "phase": ..., "player": id, "state": {...}, "moves": [...]}`
+//!   whenever the player has to move, trade moves included. The state and the moves are the
+//!   ones network clients get, see the `network` module.
+//! - `{"type": "error", "request_id": n, "message": ...}` when an answer cannot be played
+//!
+//! The bot answers a request on stdout with `{"request_id": n, "move_id": m, "tokens": t}`,
+//! where `tokens` may be left out to use as many as the move allows. Trade offers and
+//! settlements take their cards in `"gives"` and `"asks"`, like `{"Ochre": 2, "Salt": 1}`.
+
+pub mod external_bot_components;
+pub mod external_bot_events;
+pub mod external_bot_functions;
+pub mod external_bot_plugin;
+pub mod external_bot_resources;
+pub mod external_bot_systems;
+pub mod external_bot_triggers;
+
+pub mod prelude {
+    pub use super::external_bot_components::*;
+    pub use super::external_bot_events::*;
+    pub use super::external_bot_plugin::*;
+    pub use super::external_bot_resources::*;
+    pub use super::external_bot_systems::*;
+    pub use super::external_bot_triggers::*;
+}
@@ -0,0 +1,39 @@
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{Component, Entity, Reflect};
+
+#[derive(Component, Debug, Reflect)]
+pub struct HeuristicAi;
+
+/// The parts of an area the heuristic AI looks at, cheap to clone so moves can be tried out
+/// on a copy of the board.
+#[derive(Clone, Debug, Default)]
+pub struct AreaSnapshot {
+    pub max_population: usize,
+    pub city_site: bool,
+    pub city_owner: Option<Entity>,
+    pub tokens: HashMap<Entity, usize>,
+}
+
+impl AreaSnapshot {
+    pub fn new(max_population: usize, city_site: bool, city_owner: Option<Entity>) -> Self {
+        AreaSnapshot {
+            max_population,
+            city_site,
+            city_owner,
+            tokens: HashMap::default(),
+        }
+    }
+
+    pub fn add_tokens(&mut self, player: Entity, count: usize) {
+        *self.tokens.entry(player).or_default() += count;
+    }
+
+    pub fn remove_tokens(&mut self, player: Entity, count: usize) {
+        if let Some(tokens) = self.tokens.get_mut(&player) {
+            *tokens = tokens.saturating_sub(count);
+            if *tokens == 0 {
+                self.tokens.remove(&player);
+            }
+        }
+    }
+}
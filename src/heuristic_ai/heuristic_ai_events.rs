@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct SelectHeuristicMove {
+    pub player: Entity,
+}
+
+impl SelectHeuristicMove {
+    pub fn new(player: Entity) -> Self {
+        SelectHeuristicMove { player }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::civilization::components::population::Population;
+use crate::civilization::concepts::conflict::conflict_functions::{
+    fight_between, resolve_conflict as resolve_area_conflict,
+};
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::heuristic_ai::heuristic_ai_components::AreaSnapshot;
+use crate::heuristic_ai::heuristic_ai_resources::HeuristicAiWeights;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::Entity;
+
+pub fn tokens_needed_for_city(area: &AreaSnapshot) -> usize {
+    if area.city_site {
+        6
+    } else {
+        12
+    }
+}
+
+/// Token counts and city owner of an area after the conflict phase, fought out by the same
+/// conflict functions as the conflict triggers.
+pub fn resolve_conflict(
+    area: &AreaSnapshot,
+    rules: &RuleSet,
+) -> (HashMap<Entity, usize>, Option<Entity>) {
+    let mut population = stand_in_population(area);
+    let mut city_owner = area.city_owner;
+
+    if let Some(owner) = area.city_owner {
+        let mut others = population.players();
+        others.remove(&owner);
+        let strong_attackers = others
+            .iter()
+            .filter(|player| population.population_for_player(**player) > 6)
+            .copied()
+            .collect::<Vec<_>>();
+        if !strong_attackers.is_empty() {
+            // The city is replaced by six of the owner's tokens and fought over like any area
+            city_owner = None;
+            add_stand_in_tokens(&mut population, owner, 6);
+            if strong_attackers.len() > 1 {
+                // Against several strong attackers the owner is taken to fight the weakest one first
+                let attacker = strong_attackers
+                    .iter()
+                    .min_by_key(|player| (population.population_for_player(**player), **player))
+                    .copied()
+                    .unwrap();
+                let removed = fight_between(&population, owner, attacker, rules.conflict_ties);
+                for fighter in [owner, attacker] {
+                    for token in removed.iter() {
+                        population.remove_token_from_area(fighter, *token);
+                    }
+                }
+            }
+        } else {
+            for player in others {
+                population.remove_all_tokens_for_player(&player);
+            }
+        }
+    }
+    if population.is_conflict_zone(false) {
+        resolve_area_conflict(&mut population, rules.conflict_ties);
+    }
+    let counts = population
+        .player_tokens()
+        .iter()
+        .map(|(player, tokens)| (*player, tokens.len()))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    (counts, city_owner)
+}
+
+/// The area as a population of made up tokens, as the conflict functions only care about how
+/// many tokens each player has.
+fn stand_in_population(area: &AreaSnapshot) -> Population {
+    let mut population = Population::new(area.max_population);
+    for (player, count) in area.tokens.iter() {
+        add_stand_in_tokens(&mut population, *player, *count);
+    }
+    population
+}
+
+fn add_stand_in_tokens(population: &mut Population, player: Entity, count: usize) {
+    let first = population.total_population() as u32;
+    let tokens = (first..first + count as u32)
+        .filter_map(Entity::from_raw_u32)
+        .collect::<HashSet<_>>();
+    population.add_tokens_to_area(player, tokens);
+}
+
+/// Tokens of `player` that are still in the area after surplus removal. Areas with a city
+/// lose all their tokens and areas shared by several players are left alone.
+pub fn retained_tokens(
+    area: &AreaSnapshot,
+    counts: &HashMap<Entity, usize>,
+    city_owner: Option<Entity>,
+    player: &Entity,
+) -> usize {
+    let own = counts.get(player).copied().unwrap_or(0);
+    if city_owner.is_some() {
+        0
+    } else if counts.len() > 1 {
+        own
+    } else {
+        own.min(area.max_population)
+    }
+}
+
+/// True if `player` holds the area alone after the conflict phase, with no enemy city left.
+pub fn attack_wins(area: &AreaSnapshot, player: &Entity, rules: &RuleSet) -> bool {
+    let (counts, city_owner) = resolve_conflict(area, rules);
+    counts.get(player).is_some_and(|count| *count > 0)
+        && counts.len() == 1
+        && city_owner.is_none_or(|owner| owner == *player)
+}
+
+/// Scores the board from the view of `player`. Cities that can still be built this round only
+/// count when `count_city_potential` is set, as there is no point in planning for them once
+/// city construction is under way.
+pub fn evaluate_board(
+    areas: &HashMap<Entity, AreaSnapshot>,
+    player: &Entity,
+    city_tokens_in_stock: usize,
+    count_city_potential: bool,
+    weights: &HeuristicAiWeights,
+    rules: &RuleSet,
+) -> f32 {
+    let mut score = 0.0;
+    let mut cities = 0;
+    let mut new_cities = 0;
+    let mut retained = 0;
+
+    for area in areas.values() {
+        let (counts, city_owner) = resolve_conflict(area, rules);
+        if city_owner == Some(*player) {
+            cities += 1;
+            continue;
+        }
+        let own = counts.get(player).copied().unwrap_or(0);
+        if own == 0 {
+            continue;
+        }
+        if count_city_potential
+            && city_owner.is_none()
+            && own >= tokens_needed_for_city(area)
+            && new_cities < city_tokens_in_stock
+        {
+            new_cities += 1;
+            continue;
+        }
+        let kept = retained_tokens(area, &counts, city_owner, player);
+        retained += kept;
+        score += kept as f32 * weights.token;
+        if city_owner.is_none() && area.city_site {
+            score += weights.city_progress * kept.min(6) as f32 / 6.0;
+        }
+    }
+
+    score += (cities + new_cities) as f32 * weights.city;
+    let required = rules.required_population(cities + new_cities);
+    if required > retained {
+        score -= (required - retained) as f32 * weights.support_deficit;
+    }
+    score
+}
+
+/// Enemy tokens and cities that disappear from an area in the conflict phase.
+pub fn enemy_losses(area: &AreaSnapshot, player: &Entity, rules: &RuleSet) -> usize {
+    let (counts, city_owner) = resolve_conflict(area, rules);
+    let before = area
+        .tokens
+        .iter()
+        .filter(|(p, _)| *p != player)
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    let after = counts
+        .iter()
+        .filter(|(p, _)| *p != player)
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    let lost_city = if area.city_owner.is_some() && city_owner.is_none() {
+        6
+    } else {
+        0
+    };
+    before.saturating_sub(after) + lost_city
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn area_with(max_population: usize, tokens: &[(Entity, usize)]) -> AreaSnapshot {
+        let mut area = AreaSnapshot::new(max_population, false, None);
+        for (player, count) in tokens {
+            area.add_tokens(*player, *count);
+        }
+        area
+    }
+
+    #[test]
+    fn test_larger_force_wins_unequal_conflict() {
+        let attacker = create_entity();
+        let defender = create_entity();
+        let area = area_with(3, &[(attacker, 5), (defender, 2)]);
+
+        let (counts, city_owner) = resolve_conflict(&area, &RuleSet::default());
+
+        // Conflicts stop when one player is left, the surplus goes later
+        assert_eq!(counts.get(&attacker), Some(&4));
+        assert_eq!(retained_tokens(&area, &counts, city_owner, &attacker), 3);
+        assert_eq!(counts.get(&defender), None);
+        assert!(attack_wins(&area, &attacker, &RuleSet::default()));
+    }
+
+    #[test]
+    fn test_equal_forces_both_lose_tokens() {
+        let attacker = create_entity();
+        let defender = create_entity();
+        let area = area_with(2, &[(attacker, 2), (defender, 2)]);
+
+        let (counts, _) = resolve_conflict(&area, &RuleSet::default());
+
+        assert_eq!(counts.get(&attacker), Some(&1));
+        assert_eq!(counts.get(&defender), Some(&1));
+        assert!(!attack_wins(&area, &attacker, &RuleSet::default()));
+    }
+
+    #[test]
+    fn test_city_survives_attack_with_six_tokens() {
+        let attacker = create_entity();
+        let owner = create_entity();
+        let mut area = area_with(3, &[(attacker, 6)]);
+        area.city_owner = Some(owner);
+
+        let (counts, city_owner) = resolve_conflict(&area, &RuleSet::default());
+
+        assert!(counts.is_empty());
+        assert_eq!(city_owner, Some(owner));
+        assert!(!attack_wins(&area, &attacker, &RuleSet::default()));
+    }
+
+    #[test]
+    fn test_city_owner_fights_the_weaker_attacker_first() {
+        let owner = create_entity();
+        let strong = create_entity();
+        let weak = create_entity();
+        let mut area = area_with(3, &[(strong, 8), (weak, 7)]);
+        area.city_owner = Some(owner);
+
+        let (counts, city_owner) = resolve_conflict(&area, &RuleSet::default());
+
+        assert_eq!(city_owner, None);
+        assert_eq!(counts, HashMap::from_iter([(strong, 7)]));
+    }
+
+    #[test]
+    fn test_surplus_tokens_are_not_retained() {
+        let player = create_entity();
+        let area = area_with(2, &[(player, 5)]);
+        let (counts, city_owner) = resolve_conflict(&area, &RuleSet::default());
+
+        assert_eq!(retained_tokens(&area, &counts, city_owner, &player), 2);
+    }
+
+    #[test]
+    fn test_reaching_a_city_site_beats_spreading_out() {
+        let player = create_entity();
+        let weights = HeuristicAiWeights::default();
+        let rules = RuleSet::default();
+        let mut city_site = AreaSnapshot::new(2, true, None);
+        city_site.add_tokens(player, 6);
+        let spread_out = area_with(2, &[(player, 6)]);
+
+        let with_city = HashMap::from_iter([(create_entity(), city_site)]);
+        let without_city = HashMap::from_iter([(create_entity(), spread_out)]);
+
+        assert!(
+            evaluate_board(&with_city, &player, 1, true, &weights, &rules)
+                > evaluate_board(&without_city, &player, 1, true, &weights, &rules)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_cities_are_penalized() {
+        let player = create_entity();
+        let weights = HeuristicAiWeights::default();
+        let rules = RuleSet::default();
+        let city = AreaSnapshot::new(2, true, Some(player));
+        let supported = area_with(3, &[(player, 2)]);
+        let areas = HashMap::from_iter([(create_entity(), city.clone())]);
+        let supported_areas =
+            HashMap::from_iter([(create_entity(), city), (create_entity(), supported)]);
+
+        assert!(
+            evaluate_board(&supported_areas, &player, 0, true, &weights, &rules)
+                > evaluate_board(&areas, &player, 0, true, &weights, &rules) + 2.0 * weights.token
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use crate::heuristic_ai::prelude::*;
+use crate::GameActivity;
+use bevy::app::{Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs};
+
+pub struct HeuristicAiPlugin;
+
+impl Plugin for HeuristicAiPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HeuristicAi>()
+            .register_type::<HeuristicAiWeights>()
+            .init_resource::<HeuristicAiWeights>()
+            .add_message::<SelectHeuristicMove>()
+            .add_systems(
+                Update,
+                (
+                    select_heuristic_pop_exp.run_if(in_state(GameActivity::PopulationExpansion)),
+                    select_heuristic_movement.run_if(in_state(GameActivity::Movement)),
+                    select_heuristic_attacker.run_if(in_state(GameActivity::Conflict)),
+                    select_heuristic_city_building.run_if(in_state(GameActivity::CityConstruction)),
+                    select_heuristic_city_elimination
+                        .run_if(in_state(GameActivity::CheckCitySupport)),
+                    delegate_heuristic_trade_move.run_if(in_state(GameActivity::Trade)),
+                ),
+            )
+            .add_observer(on_add_available_moves_for_heuristic_ai);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use bevy::prelude::{Reflect, Resource};
+
+/// How much the heuristic AI cares about the different parts of a board position, in tokens.
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct HeuristicAiWeights {
+    /// A city, standing or ready to be built this round
+    pub city: f32,
+    /// A token that survives conflicts and surplus removal
+    pub token: f32,
+    /// Bonus for a full city site worth of tokens, given in proportion to how close we are
+    pub city_progress: f32,
+    /// Penalty for every token we are short of supporting our cities
+    pub support_deficit: f32,
+    /// Bonus for every enemy token removed by an attack we win
+    pub attack: f32,
+}
+
+impl HeuristicAiWeights {
+    /// The weights as seen by a player with the given personality.
+    pub fn with_personality(&self, personality: Option<&AiPersonality>) -> Self {
+        let Some(personality) = personality else {
+            return self.clone();
+        };
+        HeuristicAiWeights {
+            city: self.city * personality.city_building,
+            token: self.token,
+            city_progress: self.city_progress * personality.city_building,
+            support_deficit: self.support_deficit / personality.risk_tolerance.max(0.1),
+            attack: self.attack * personality.attack,
+        }
+    }
+}
+
+impl Default for HeuristicAiWeights {
+    fn default() -> Self {
+        HeuristicAiWeights {
+            city: 12.0,
+            token: 1.0,
+            city_progress: 2.0,
+            support_deficit: 4.0,
+            attack: 0.5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_city_building_scales_cities_and_progress() {
+        let weights = HeuristicAiWeights::default();
+
+        let adjusted =
+            weights.with_personality(Some(&AiPersonality::new("test", 1.0, 2.0, 1.0, 1.0)));
+
+        assert_eq!(adjusted.city, weights.city * 2.0);
+        assert_eq!(adjusted.city_progress, weights.city_progress * 2.0);
+        assert_eq!(adjusted.token, weights.token);
+    }
+
+    #[test]
+    fn test_attack_scales_the_attack_bonus() {
+        let weights = HeuristicAiWeights::default();
+
+        let adjusted =
+            weights.with_personality(Some(&AiPersonality::new("test", 3.0, 1.0, 1.0, 1.0)));
+
+        assert_eq!(adjusted.attack, weights.attack * 3.0);
+        assert_eq!(adjusted.city, weights.city);
+    }
+
+    #[test]
+    fn test_risk_tolerance_eases_the_support_deficit_penalty() {
+        let weights = HeuristicAiWeights::default();
+
+        let daring =
+            weights.with_personality(Some(&AiPersonality::new("test", 1.0, 1.0, 1.0, 2.0)));
+        let reckless =
+            weights.with_personality(Some(&AiPersonality::new("test", 1.0, 1.0, 1.0, 0.0)));
+
+        assert_eq!(daring.support_deficit, weights.support_deficit / 2.0);
+        // A tolerance of zero is taken as 0.1 rather than dividing by zero
+        assert_eq!(reckless.support_deficit, weights.support_deficit / 0.1);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::civilization::components::population::{MaxPopulation, Populations};
+use crate::civilization::components::*;
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::check_city_support::check_city_support_events::EliminateCity;
+use crate::civilization::concepts::city_construction::city_construction_events::{
+    BuildCityCommand, EndPlayerCityConstruction,
+};
+use crate::civilization::concepts::conflict::conflict_events::FightAttackerFirst;
+use crate::civilization::concepts::movement::movement_events::{
+    MoveTokenFromAreaToAreaCommand, PlayerMovementEnded,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_events::ExpandPopulationManuallyCommand;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::heuristic_ai::heuristic_ai_components::AreaSnapshot;
+use crate::heuristic_ai::heuristic_ai_events::SelectHeuristicMove;
+use crate::heuristic_ai::heuristic_ai_functions::{attack_wins, enemy_losses, evaluate_board};
+use crate::heuristic_ai::heuristic_ai_resources::HeuristicAiWeights;
+use crate::trade_ai::trade_ai_events::SelectTradeMove;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{debug, Entity, Has, MessageReader, MessageWriter, Name, Query, Res, With};
+
+/// The areas the heuristic AI looks at, with the population standing in them.
+#[derive(SystemParam)]
+pub struct HeuristicBoard<'w, 's> {
+    areas: Query<'w, 's, (Entity, Has<CitySite>, Option<&'static BuiltCity>), With<MaxPopulation>>,
+    populations: Populations<'w, 's>,
+}
+
+impl HeuristicBoard<'_, '_> {
+    pub fn snapshot(&self) -> HashMap<Entity, AreaSnapshot> {
+        self.areas
+            .iter()
+            .filter_map(|(area, city_site, built_city)| {
+                let population = self.populations.get(area)?;
+                let mut snapshot = AreaSnapshot::new(
+                    population.max_population,
+                    city_site,
+                    built_city.map(|city| city.player),
+                );
+                for (player, tokens) in population.player_tokens().iter() {
+                    snapshot.add_tokens(*player, tokens.len());
+                }
+                Some((area, snapshot))
+            })
+            .collect()
+    }
+}
+
+pub fn select_heuristic_pop_exp(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    player_moves: Query<(
+        &Name,
+        &AvailableMoves,
+        &CityTokenStock,
+        Option<&AiPersonality>,
+    )>,
+    area_board: HeuristicBoard,
+    mut expand_writer: MessageWriter<ExpandPopulationManuallyCommand>,
+    (weights, rules): (Res<HeuristicAiWeights>, Res<RuleSet>),
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, city_stock, personality)) =
+            player_moves.get(event.player)
+        {
+            let weights = weights.with_personality(personality);
+            let board = area_board.snapshot();
+            let best_move = available_moves
+                .moves
+                .values()
+                .filter_map(|m| match m {
+                    Move::PopulationExpansion(pop_exp_move) => {
+                        let mut after = board.clone();
+                        if let Some(area) = after.get_mut(&pop_exp_move.area) {
+                            area.add_tokens(event.player, pop_exp_move.max_tokens);
+                        }
+                        let score = evaluate_board(
+                            &after,
+                            &event.player,
+                            city_stock.tokens_in_stock(),
+                            true,
+                            &weights,
+                            &rules,
+                        );
+                        Some((pop_exp_move, score))
+                    }
+                    _ => None,
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            if let Some((pop_exp_move, score)) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!(
+                        "{} expands into {:?}, scored {}",
+                        player_name, pop_exp_move.area, score
+                    );
+                }
+                expand_writer.write(ExpandPopulationManuallyCommand::new(
+                    event.player,
+                    pop_exp_move.area,
+                    pop_exp_move.max_tokens,
+                ));
+            }
+        }
+    }
+}
+
+/// Tries every move with every number of tokens on a copy of the board and goes with the one
+/// that improves the position the most. Attacks are only considered when they are won.
+pub fn select_heuristic_movement(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    player_moves: Query<(
+        &Name,
+        &AvailableMoves,
+        &CityTokenStock,
+        Option<&AiPersonality>,
+    )>,
+    area_board: HeuristicBoard,
+    mut move_tokens_writer: MessageWriter<MoveTokenFromAreaToAreaCommand>,
+    mut end_movement_writer: MessageWriter<PlayerMovementEnded>,
+    (weights, rules): (Res<HeuristicAiWeights>, Res<RuleSet>),
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, city_stock, personality)) =
+            player_moves.get(event.player)
+        {
+            let weights = weights.with_personality(personality);
+            let board = area_board.snapshot();
+            let current_score = evaluate_board(
+                &board,
+                &event.player,
+                city_stock.tokens_in_stock(),
+                true,
+                &weights,
+                &rules,
+            );
+
+            let mut best: Option<(Entity, Entity, usize, f32)> = None;
+            for m in available_moves.moves.values() {
+                let (movement_move, is_attack) = match m {
+                    Move::Movement(movement_move) => (movement_move, false),
+                    Move::AttackArea(movement_move) | Move::AttackCity(movement_move) => {
+                        (movement_move, true)
+                    }
+                    _ => continue,
+                };
+                for number_of_tokens in 1..=movement_move.max_tokens {
+                    let mut after = board.clone();
+                    if let Some(source) = after.get_mut(&movement_move.source) {
+                        source.remove_tokens(event.player, number_of_tokens);
+                    }
+                    let Some(target) = after.get_mut(&movement_move.target) else {
+                        continue;
+                    };
+                    target.add_tokens(event.player, number_of_tokens);
+                    let mut score = 0.0;
+                    if is_attack {
+                        if !attack_wins(target, &event.player, &rules) {
+                            continue;
+                        }
+                        score +=
+                            enemy_losses(target, &event.player, &rules) as f32 * weights.attack;
+                    }
+                    score += evaluate_board(
+                        &after,
+                        &event.player,
+                        city_stock.tokens_in_stock(),
+                        true,
+                        &weights,
+                        &rules,
+                    );
+                    if score > current_score && best.is_none_or(|(_, _, _, best)| score > best) {
+                        best = Some((
+                            movement_move.source,
+                            movement_move.target,
+                            number_of_tokens,
+                            score,
+                        ));
+                    }
+                }
+            }
+
+            if let Some((source, target, number_of_tokens, score)) = best {
+                if debug_options.print_selected_moves {
+                    debug!(
+                        "{} moves {} tokens from {:?} to {:?}, scored {}",
+                        player_name, number_of_tokens, source, target, score
+                    );
+                }
+                move_tokens_writer.write(MoveTokenFromAreaToAreaCommand::new(
+                    source,
+                    target,
+                    number_of_tokens,
+                    event.player,
+                ));
+            } else {
+                end_movement_writer.write(PlayerMovementEnded::new(event.player));
+            }
+        }
+    }
+}
+
+/// Builds the city that helps the most, as long as the rest of our population can support it.
+pub fn select_heuristic_city_building(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    player_moves: Query<(
+        &Name,
+        &AvailableMoves,
+        &CityTokenStock,
+        Option<&AiPersonality>,
+    )>,
+    area_board: HeuristicBoard,
+    mut build_city_writer: MessageWriter<BuildCityCommand>,
+    mut end_player_city_construction: MessageWriter<EndPlayerCityConstruction>,
+    (weights, rules): (Res<HeuristicAiWeights>, Res<RuleSet>),
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, city_stock, personality)) =
+            player_moves.get(event.player)
+        {
+            let weights = weights.with_personality(personality);
+            let board = area_board.snapshot();
+            let current_score = evaluate_board(&board, &event.player, 0, false, &weights, &rules);
+
+            let best_move = available_moves
+                .moves
+                .values()
+                .filter_map(|m| match m {
+                    Move::CityConstruction(build_city_move) => {
+                        let mut after = board.clone();
+                        let area = after.get_mut(&build_city_move.target)?;
+                        area.tokens.clear();
+                        area.city_owner = Some(event.player);
+                        let score = evaluate_board(
+                            &after,
+                            &event.player,
+                            city_stock.tokens_in_stock().saturating_sub(1),
+                            false,
+                            &weights,
+                            &rules,
+                        );
+                        Some((build_city_move, score))
+                    }
+                    _ => None,
+                })
+                .filter(|(_, score)| *score > current_score)
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            if let Some((build_city_move, score)) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!(
+                        "{} builds a city in {:?}, scored {}",
+                        player_name, build_city_move.target, score
+                    );
+                }
+                build_city_writer
+                    .write(BuildCityCommand::new(event.player, build_city_move.target));
+            } else {
+                end_player_city_construction.write(EndPlayerCityConstruction::new(event.player));
+            }
+        }
+    }
+}
+
+/// Gives up the city in the area that hands back the most tokens.
+pub fn select_heuristic_city_elimination(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    player_moves: Query<(&Name, &AvailableMoves)>,
+    mut eliminate_city: MessageWriter<EliminateCity>,
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves)) = player_moves.get(event.player) {
+            let best_move = available_moves
+                .moves
+                .values()
+                .filter_map(|m| match m {
+                    Move::EliminateCity(el_move) => Some(el_move),
+                    _ => None,
+                })
+                .max_by_key(|el_move| el_move.tokens_gained);
+
+            if let Some(el_move) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!("{} eliminates the city in {:?}", player_name, el_move.area);
+                }
+                eliminate_city.write(EliminateCity::new(
+                    el_move.player,
+                    el_move.city,
+                    el_move.area,
+                    false,
+                ));
+            }
+        }
+    }
+}
+
+/// Takes on the weakest of the attackers first.
+pub fn select_heuristic_attacker(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    player_moves: Query<(&Name, &AvailableMoves)>,
+    mut fight_attacker: MessageWriter<FightAttackerFirst>,
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves)) = player_moves.get(event.player) {
+            let weakest = available_moves
+                .moves
+                .values()
+                .filter_map(|m| match m {
+                    Move::ChooseAttacker(choose_move) => Some(choose_move),
+                    _ => None,
+                })
+                .min_by_key(|choose_move| (choose_move.attacker_tokens, choose_move.attacker));
+
+            if let Some(choose_move) = weakest {
+                if debug_options.print_selected_moves {
+                    debug!("{} fights {:?} first", player_name, choose_move.attacker);
+                }
+                fight_attacker.write(FightAttackerFirst::new(
+                    event.player,
+                    choose_move.area,
+                    choose_move.attacker,
+                ));
+            }
+        }
+    }
+}
+
+/// The heuristic AI leaves its trading to the trade AI.
+pub fn delegate_heuristic_trade_move(
+    mut event_reader: MessageReader<SelectHeuristicMove>,
+    mut trade_writer: MessageWriter<SelectTradeMove>,
+) {
+    for event in event_reader.read() {
+        trade_writer.write(SelectTradeMove::new(event.player));
+    }
+}
@@ -0,0 +1,16 @@
+use crate::civilization::concepts::spectator::spectator_systems::SpectatorGate;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::heuristic_ai::heuristic_ai_components::HeuristicAi;
+use crate::heuristic_ai::heuristic_ai_events::SelectHeuristicMove;
+use bevy::prelude::{Add, MessageWriter, On, Query};
+
+pub fn on_add_available_moves_for_heuristic_ai(
+    trigger: On<Add, AvailableMoves>,
+    is_heuristic_ai: Query<&HeuristicAi>,
+    mut event_writer: MessageWriter<SelectHeuristicMove>,
+    mut gate: SpectatorGate,
+) {
+    if is_heuristic_ai.contains(trigger.event().entity) && !gate.hold_back(trigger.event().entity) {
+        event_writer.write(SelectHeuristicMove::new(trigger.event().entity));
+    }
+}
@@ -0,0 +1,16 @@
+pub mod heuristic_ai_components;
+pub mod heuristic_ai_events;
+pub mod heuristic_ai_functions;
+pub mod heuristic_ai_plugin;
+pub mod heuristic_ai_resources;
+pub mod heuristic_ai_systems;
+pub mod heuristic_ai_triggers;
+
+pub mod prelude {
+    pub use super::heuristic_ai_components::*;
+    pub use super::heuristic_ai_events::*;
+    pub use super::heuristic_ai_plugin::*;
+    pub use super::heuristic_ai_resources::*;
+    pub use super::heuristic_ai_systems::*;
+    pub use super::heuristic_ai_triggers::*;
+}
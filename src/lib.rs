@@ -6,13 +6,18 @@ mod loading;
 mod menu;
 mod player;
 mod civilization;
+mod external_bot;
+mod heuristic_ai;
+mod mcts_ai;
+pub mod network;
 mod stupid_ai;
+pub mod tournament;
+mod trade_ai;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
-use crate::player::PlayerPlugin;
 
 use bevy::app::App;
 #[cfg(debug_assertions)]
@@ -28,7 +33,18 @@ pub enum GameState {
     Menu,
 }
 
-#[derive(SubStates, Clone, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[derive(
+    SubStates,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Default,
+    Reflect,
+    serde::Deserialize,
+    serde::Serialize,
+)]
 #[source(GameState = GameState::Playing)]
 pub enum GameActivity {
     #[default]
@@ -0,0 +1,67 @@
+use crate::civilization::components::population::Population;
+use crate::civilization::components::{CityTokenStock, PlayerAreas, PlayerCities, TokenStock};
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::GameActivity;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::{Component, Entity, Reflect};
+
+#[derive(Component, Debug, Reflect)]
+pub struct MctsAi;
+
+/// A move the search can try out. These are the same choices [`Move`] offers, with the number
+/// of tokens to move spelled out.
+///
+/// [`Move`]: crate::civilization::game_moves::game_moves_components::Move
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimAction {
+    Expand {
+        area: Entity,
+        tokens: usize,
+    },
+    Move {
+        source: Entity,
+        target: Entity,
+        tokens: usize,
+    },
+    EndMovement,
+    BuildCity {
+        area: Entity,
+    },
+    EndCityConstruction,
+    EliminateCity {
+        area: Entity,
+        city: Entity,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct SimArea {
+    pub population: Population,
+    pub connections: Vec<Entity>,
+    pub city_site: bool,
+    /// Owner and city token of the city in the area
+    pub city: Option<(Entity, Entity)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SimPlayer {
+    pub token_stock: TokenStock,
+    pub city_stock: CityTokenStock,
+    pub player_cities: PlayerCities,
+    pub player_areas: PlayerAreas,
+}
+
+/// A copy of everything on the board the rule functions need, so a round can be played out
+/// without touching the real game.
+#[derive(Clone, Debug)]
+pub struct GameSnapshot {
+    pub areas: HashMap<Entity, SimArea>,
+    pub players: HashMap<Entity, SimPlayer>,
+    pub token_owners: HashMap<Entity, Entity>,
+    pub phase: GameActivity,
+    /// Players that still have to move this round, the next one first
+    pub movement_order: Vec<Entity>,
+    pub moved_tokens: HashSet<Entity>,
+    pub needs_expansion: HashMap<Entity, HashSet<Entity>>,
+    pub rules: RuleSet,
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct SelectMctsMove {
+    pub player: Entity,
+}
+
+impl SelectMctsMove {
+    pub fn new(player: Entity) -> Self {
+        SelectMctsMove { player }
+    }
+}
@@ -0,0 +1,825 @@
+use crate::civilization::components::population::Population;
+use crate::civilization::components::{
+    BuiltCity, CityTokenStock, PlayerAreas, PlayerCities, TokenStock,
+};
+use crate::civilization::concepts::conflict::conflict_functions::{
+    fight_between, resolve_conflict,
+};
+use crate::mcts_ai::mcts_ai_components::{GameSnapshot, SimAction, SimPlayer};
+use crate::mcts_ai::mcts_ai_resources::MctsSettings;
+use crate::GameActivity;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::platform::time::Instant;
+use bevy::prelude::Entity;
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use std::time::Duration;
+
+/// Upper limit on the moves a player makes in a rollout, random players rarely end on their own.
+const MAX_ROLLOUT_ACTIONS: usize = 30;
+
+/*
+The board only keeps token positions in InArea, a snapshot keeps its own populations and player
+areas. These keep the two of them in step while a round is played out.
+ */
+
+pub fn move_from_stock_to_area(
+    player: Entity,
+    area: Entity,
+    at_most_tokens: usize,
+    population: &mut Population,
+    token_stock: &mut TokenStock,
+    player_areas: &mut PlayerAreas,
+) {
+    let tokens = token_stock
+        .remove_at_most_n_tokens_from_stock(at_most_tokens)
+        .unwrap_or_default();
+    population.add_tokens_to_area(player, tokens.clone());
+    player_areas.add_tokens_to_area(area, tokens);
+}
+
+pub fn return_all_tokens_from_area_to_player(
+    player: &Entity,
+    area: &Entity,
+    population: &mut Population,
+    token_stock: &mut TokenStock,
+    player_areas: &mut PlayerAreas,
+) {
+    let tokens = population.remove_all_tokens_for_player(player);
+    token_stock.return_tokens_to_stock(tokens.clone());
+    player_areas.remove_area(area);
+}
+
+pub fn return_token_to_stock(
+    token: Entity,
+    token_stock: &mut TokenStock,
+    player_areas: &mut PlayerAreas,
+) {
+    token_stock.return_token_to_stock(token);
+    player_areas.remove_token(token);
+}
+
+pub fn replace_city_with_tokens_for_conflict(
+    area_entity: Entity,
+    population: &mut Population,
+    built_city: &BuiltCity,
+    city_stock: &mut CityTokenStock,
+    token_stock: &mut TokenStock,
+    player_cities: &mut PlayerCities,
+    player_areas: &mut PlayerAreas,
+) {
+    player_cities.remove_city_from_area(area_entity);
+    city_stock.return_token_to_stock(built_city.city);
+    move_from_stock_to_area(
+        built_city.player,
+        area_entity,
+        6,
+        population,
+        token_stock,
+        player_areas,
+    );
+}
+
+pub fn legal_actions(snapshot: &GameSnapshot, player: Entity) -> Vec<SimAction> {
+    let Some(sim_player) = snapshot.players.get(&player) else {
+        return vec![];
+    };
+    match snapshot.phase {
+        GameActivity::PopulationExpansion => {
+            let stock = sim_player.token_stock.tokens_in_stock();
+            snapshot
+                .needs_expansion
+                .get(&player)
+                .into_iter()
+                .flatten()
+                .filter_map(|area| {
+                    let tokens = snapshot
+                        .areas
+                        .get(area)?
+                        .population
+                        .max_expansion_for_player(player)
+                        .min(stock);
+                    (tokens > 0).then_some(SimAction::Expand {
+                        area: *area,
+                        tokens,
+                    })
+                })
+                .collect()
+        }
+        GameActivity::Movement => {
+            let mut actions = vec![];
+            for (source, tokens) in sim_player.player_areas.areas_and_population() {
+                let movable = tokens
+                    .iter()
+                    .filter(|token| !snapshot.moved_tokens.contains(*token))
+                    .count();
+                let Some(source_area) = snapshot.areas.get(&source) else {
+                    continue;
+                };
+                for target in source_area.connections.iter() {
+                    let Some(target_area) = snapshot.areas.get(target) else {
+                        continue;
+                    };
+                    if target_area.city.is_some_and(|(owner, _)| owner == player) {
+                        continue;
+                    }
+                    for tokens in 1..=movable {
+                        actions.push(SimAction::Move {
+                            source,
+                            target: *target,
+                            tokens,
+                        });
+                    }
+                }
+            }
+            actions.push(SimAction::EndMovement);
+            actions
+        }
+        GameActivity::CityConstruction => {
+            let mut actions = vec![];
+            if sim_player.city_stock.has_tokens() {
+                for (area, population) in sim_player.player_areas.areas_and_population_count() {
+                    if let Some(sim_area) = snapshot.areas.get(&area) {
+                        if sim_area.city.is_none()
+                            && ((sim_area.city_site && population >= 6) || population >= 12)
+                        {
+                            actions.push(SimAction::BuildCity { area });
+                        }
+                    }
+                }
+            }
+            actions.push(SimAction::EndCityConstruction);
+            actions
+        }
+        GameActivity::CheckCitySupport => {
+            if snapshot
+                .rules
+                .required_population(sim_player.player_cities.number_of_cities())
+                > sim_player.player_areas.total_population()
+            {
+                sim_player
+                    .player_cities
+                    .areas_and_cities
+                    .iter()
+                    .map(|(area, city)| SimAction::EliminateCity {
+                        area: *area,
+                        city: *city,
+                    })
+                    .collect()
+            } else {
+                vec![]
+            }
+        }
+        _ => vec![],
+    }
+}
+
+/// Moves that hand the turn over to someone else.
+pub fn ends_decision(action: &SimAction) -> bool {
+    matches!(
+        action,
+        SimAction::EndMovement | SimAction::EndCityConstruction
+    )
+}
+
+pub fn apply_action(snapshot: &mut GameSnapshot, player: Entity, action: &SimAction) {
+    let GameSnapshot {
+        areas,
+        players,
+        moved_tokens,
+        movement_order,
+        needs_expansion,
+        ..
+    } = snapshot;
+    match action {
+        SimAction::Expand { area, tokens } => {
+            if let (Some(sim_area), Some(sim_player)) =
+                (areas.get_mut(area), players.get_mut(&player))
+            {
+                move_from_stock_to_area(
+                    player,
+                    *area,
+                    *tokens,
+                    &mut sim_area.population,
+                    &mut sim_player.token_stock,
+                    &mut sim_player.player_areas,
+                );
+            }
+            if let Some(areas_to_expand) = needs_expansion.get_mut(&player) {
+                areas_to_expand.remove(area);
+            }
+        }
+        SimAction::Move {
+            source,
+            target,
+            tokens,
+        } => {
+            let Some(sim_player) = players.get_mut(&player) else {
+                return;
+            };
+            let mut moving = vec![];
+            if let Some(source_area) = areas.get_mut(source) {
+                moving = source_area
+                    .population
+                    .tokens_for_player(&player)
+                    .into_iter()
+                    .flatten()
+                    .filter(|token| !moved_tokens.contains(*token))
+                    .take(*tokens)
+                    .copied()
+                    .collect::<Vec<_>>();
+                for token in moving.iter() {
+                    source_area
+                        .population
+                        .remove_token_from_area(player, *token);
+                    sim_player
+                        .player_areas
+                        .remove_token_from_area(source, *token);
+                }
+            }
+            if let Some(target_area) = areas.get_mut(target) {
+                for token in moving {
+                    target_area.population.add_token_to_area(player, token);
+                    sim_player.player_areas.add_token_to_area(*target, token);
+                    moved_tokens.insert(token);
+                }
+            }
+        }
+        SimAction::EndMovement => {
+            movement_order.retain(|mover| *mover != player);
+        }
+        SimAction::BuildCity { area } => {
+            let Some(sim_area) = areas.get_mut(area) else {
+                return;
+            };
+            for other in sim_area.population.players() {
+                if let Some(other_player) = players.get_mut(&other) {
+                    return_all_tokens_from_area_to_player(
+                        &other,
+                        area,
+                        &mut sim_area.population,
+                        &mut other_player.token_stock,
+                        &mut other_player.player_areas,
+                    );
+                }
+            }
+            if let Some(sim_player) = players.get_mut(&player) {
+                if let Some(city_token) = sim_player.city_stock.get_token_from_stock() {
+                    sim_player
+                        .player_cities
+                        .build_city_in_area(*area, city_token);
+                    sim_area.city = Some((player, city_token));
+                }
+            }
+        }
+        SimAction::EndCityConstruction => {}
+        SimAction::EliminateCity { area, city } => {
+            if let (Some(sim_area), Some(sim_player)) =
+                (areas.get_mut(area), players.get_mut(&player))
+            {
+                sim_player.player_cities.remove_city_from_area(*area);
+                sim_player.city_stock.return_token_to_stock(*city);
+                sim_area.city = None;
+                move_from_stock_to_area(
+                    player,
+                    *area,
+                    sim_area.population.max_population,
+                    &mut sim_area.population,
+                    &mut sim_player.token_stock,
+                    &mut sim_player.player_areas,
+                );
+            }
+        }
+    }
+}
+
+fn return_tokens_to_stock(
+    players: &mut HashMap<Entity, SimPlayer>,
+    token_owners: &HashMap<Entity, Entity>,
+    tokens: HashSet<Entity>,
+) {
+    for token in tokens {
+        if let Some(sim_player) = token_owners
+            .get(&token)
+            .and_then(|owner| players.get_mut(owner))
+        {
+            return_token_to_stock(
+                token,
+                &mut sim_player.token_stock,
+                &mut sim_player.player_areas,
+            );
+        }
+    }
+}
+
+/// The conflict phase, the same way the conflict triggers resolve it.
+pub fn resolve_conflicts(snapshot: &mut GameSnapshot) {
+    let GameSnapshot {
+        areas,
+        players,
+        token_owners,
+        rules,
+        ..
+    } = snapshot;
+    for (area_entity, area) in areas.iter_mut() {
+        if !area.population.is_conflict_zone(area.city.is_some()) {
+            continue;
+        }
+        let Some((owner, city)) = area.city else {
+            let removed = resolve_conflict(&mut area.population, rules.conflict_ties);
+            return_tokens_to_stock(players, token_owners, removed);
+            continue;
+        };
+        let mut others = area.population.players();
+        others.remove(&owner);
+        // Against several strong attackers the owner is taken to fight the weakest one first
+        let strong_attackers = others
+            .iter()
+            .filter(|other| area.population.population_for_player(**other) > 6)
+            .copied()
+            .collect::<Vec<_>>();
+        let weakest = strong_attackers
+            .iter()
+            .min_by_key(|other| (area.population.population_for_player(**other), **other))
+            .copied();
+        if let Some(weakest) = weakest {
+            if let Some(sim_owner) = players.get_mut(&owner) {
+                replace_city_with_tokens_for_conflict(
+                    *area_entity,
+                    &mut area.population,
+                    &BuiltCity::new(city, owner),
+                    &mut sim_owner.city_stock,
+                    &mut sim_owner.token_stock,
+                    &mut sim_owner.player_cities,
+                    &mut sim_owner.player_areas,
+                );
+                area.city = None;
+            }
+            if strong_attackers.len() > 1 {
+                let removed = fight_between(&area.population, owner, weakest, rules.conflict_ties);
+                for token in removed.iter() {
+                    if let Some(player) = token_owners.get(token) {
+                        area.population.remove_token_from_area(*player, *token);
+                    }
+                }
+                return_tokens_to_stock(players, token_owners, removed);
+            }
+            let removed = resolve_conflict(&mut area.population, rules.conflict_ties);
+            return_tokens_to_stock(players, token_owners, removed);
+        } else {
+            for other in others {
+                if let Some(sim_other) = players.get_mut(&other) {
+                    return_all_tokens_from_area_to_player(
+                        &other,
+                        area_entity,
+                        &mut area.population,
+                        &mut sim_other.token_stock,
+                        &mut sim_other.player_areas,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Areas with a city are emptied, areas with a single player are trimmed to their limit.
+pub fn remove_surplus_population(snapshot: &mut GameSnapshot) {
+    let GameSnapshot {
+        areas,
+        players,
+        token_owners,
+        ..
+    } = snapshot;
+    for area in areas.values_mut() {
+        let has_city = area.city.is_some();
+        if !area.population.has_surplus(has_city) {
+            continue;
+        }
+        let removed = if has_city {
+            area.population.remove_all_tokens()
+        } else if area.population.number_of_players() > 1 {
+            HashSet::new()
+        } else {
+            area.population.remove_surplus()
+        };
+        return_tokens_to_stock(players, token_owners, removed);
+    }
+}
+
+fn play_random_actions(snapshot: &mut GameSnapshot, player: Entity, rng: &mut StdRng) {
+    for _ in 0..MAX_ROLLOUT_ACTIONS {
+        let Some(action) = legal_actions(snapshot, player).into_iter().choose(rng) else {
+            break;
+        };
+        apply_action(snapshot, player, &action);
+        if ends_decision(&action) {
+            break;
+        }
+    }
+}
+
+/// Plays the rest of the round with random moves for everyone.
+pub fn play_out_round(snapshot: &mut GameSnapshot, rng: &mut StdRng) {
+    let players = snapshot.players.keys().copied().collect::<Vec<_>>();
+    if snapshot.phase == GameActivity::PopulationExpansion {
+        for player in players.iter() {
+            play_random_actions(snapshot, *player, rng);
+        }
+        snapshot.phase = GameActivity::Movement;
+    }
+    if snapshot.phase == GameActivity::Movement {
+        while let Some(mover) = snapshot.movement_order.first().copied() {
+            play_random_actions(snapshot, mover, rng);
+            snapshot.movement_order.retain(|player| *player != mover);
+        }
+        snapshot.phase = GameActivity::Conflict;
+    }
+    if snapshot.phase == GameActivity::Conflict {
+        resolve_conflicts(snapshot);
+        snapshot.phase = GameActivity::CityConstruction;
+    }
+    if snapshot.phase == GameActivity::CityConstruction {
+        for player in players.iter() {
+            play_random_actions(snapshot, *player, rng);
+        }
+        snapshot.phase = GameActivity::RemoveSurplusPopulation;
+    }
+    if snapshot.phase == GameActivity::RemoveSurplusPopulation {
+        remove_surplus_population(snapshot);
+        snapshot.phase = GameActivity::CheckCitySupport;
+    }
+    if snapshot.phase == GameActivity::CheckCitySupport {
+        for player in players.iter() {
+            play_random_actions(snapshot, *player, rng);
+        }
+        snapshot.phase = GameActivity::AcquireTradeCards;
+    }
+}
+
+/// Share of all points on the board that belong to `player`, between 0 and 1. The points of the
+/// other players are weighted by `opponent_weight`, so a higher weight makes hurting them pay.
+pub fn reward(snapshot: &GameSnapshot, player: Entity, settings: &MctsSettings) -> f32 {
+    let points = |sim_player: &SimPlayer| {
+        sim_player.player_areas.total_population() as f32
+            + sim_player.player_cities.number_of_cities() as f32 * settings.city_value
+    };
+    let Some(own) = snapshot.players.get(&player).map(points) else {
+        return 0.0;
+    };
+    let others = snapshot
+        .players
+        .iter()
+        .filter(|(other, _)| **other != player)
+        .map(|(_, sim_player)| points(sim_player))
+        .sum::<f32>();
+    let total = own + others * settings.opponent_weight;
+    if total > 0.0 {
+        own / total
+    } else {
+        0.0
+    }
+}
+
+struct Node {
+    action: Option<SimAction>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<SimAction>,
+    visits: u32,
+    total_reward: f32,
+}
+
+impl Node {
+    fn new(action: Option<SimAction>, parent: Option<usize>, untried: Vec<SimAction>) -> Self {
+        Node {
+            action,
+            parent,
+            children: vec![],
+            untried,
+            visits: 0,
+            total_reward: 0.0,
+        }
+    }
+}
+
+fn best_uct_child(nodes: &[Node], parent: usize, exploration: f32) -> usize {
+    let parent_visits = (nodes[parent].visits.max(1) as f32).ln();
+    *nodes[parent]
+        .children
+        .iter()
+        .max_by(|a, b| {
+            let uct = |child: &Node| {
+                child.total_reward / child.visits as f32
+                    + exploration * (parent_visits / child.visits as f32).sqrt()
+            };
+            uct(&nodes[**a]).total_cmp(&uct(&nodes[**b]))
+        })
+        .unwrap()
+}
+
+/// Monte Carlo tree search over the moves of `player` in the current phase. Every iteration
+/// walks down the tree of our own moves, plays the rest of the round out at random and scores
+/// the result. Stops when either the iteration or the time budget runs out.
+pub fn search(
+    root: &GameSnapshot,
+    player: Entity,
+    root_actions: Vec<SimAction>,
+    settings: &MctsSettings,
+    rng: &mut StdRng,
+) -> Option<SimAction> {
+    if root_actions.len() <= 1 {
+        return root_actions.into_iter().next();
+    }
+    let started = Instant::now();
+    let time_budget = Duration::from_millis(settings.time_budget_ms);
+    let mut nodes = vec![Node::new(None, None, root_actions)];
+
+    for _ in 0..settings.iterations {
+        if started.elapsed() >= time_budget {
+            break;
+        }
+        let mut snapshot = root.clone();
+        let mut node = 0;
+
+        while nodes[node].untried.is_empty() && !nodes[node].children.is_empty() {
+            node = best_uct_child(&nodes, node, settings.exploration);
+            if let Some(action) = nodes[node].action.as_ref() {
+                apply_action(&mut snapshot, player, action);
+            }
+        }
+
+        if let Some(index) = (0..nodes[node].untried.len()).choose(rng) {
+            let action = nodes[node].untried.swap_remove(index);
+            apply_action(&mut snapshot, player, &action);
+            let untried = if ends_decision(&action) {
+                vec![]
+            } else {
+                legal_actions(&snapshot, player)
+            };
+            nodes.push(Node::new(Some(action), Some(node), untried));
+            let child = nodes.len() - 1;
+            nodes[node].children.push(child);
+            node = child;
+        }
+
+        play_out_round(&mut snapshot, rng);
+        let result = reward(&snapshot, player, settings);
+
+        let mut current = Some(node);
+        while let Some(index) = current {
+            nodes[index].visits += 1;
+            nodes[index].total_reward += result;
+            current = nodes[index].parent;
+        }
+    }
+
+    nodes[0]
+        .children
+        .iter()
+        .max_by_key(|child| nodes[**child].visits)
+        .and_then(|child| nodes[*child].action.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::rules::rules_resources::RuleSet;
+    use crate::mcts_ai::mcts_ai_components::SimArea;
+    use rand::SeedableRng;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn empty_snapshot(phase: GameActivity) -> GameSnapshot {
+        GameSnapshot {
+            areas: HashMap::default(),
+            players: HashMap::default(),
+            token_owners: HashMap::default(),
+            phase,
+            movement_order: vec![],
+            moved_tokens: HashSet::default(),
+            needs_expansion: HashMap::default(),
+            rules: RuleSet::default(),
+        }
+    }
+
+    fn add_player(snapshot: &mut GameSnapshot, tokens: usize) -> Entity {
+        let player = create_entity();
+        let stock = (0..tokens).map(|_| create_entity()).collect::<Vec<_>>();
+        for token in stock.iter() {
+            snapshot.token_owners.insert(*token, player);
+        }
+        snapshot.players.insert(
+            player,
+            SimPlayer {
+                token_stock: TokenStock::new(tokens, stock),
+                city_stock: CityTokenStock::new(1, vec![create_entity()]),
+                player_cities: PlayerCities::default(),
+                player_areas: PlayerAreas::default(),
+            },
+        );
+        player
+    }
+
+    fn add_area(snapshot: &mut GameSnapshot, max_population: usize, city_site: bool) -> Entity {
+        let area = create_entity();
+        snapshot.areas.insert(
+            area,
+            SimArea {
+                population: Population::new(max_population),
+                connections: vec![],
+                city_site,
+                city: None,
+            },
+        );
+        area
+    }
+
+    fn put_tokens(snapshot: &mut GameSnapshot, player: Entity, area: Entity, tokens: usize) {
+        snapshot
+            .needs_expansion
+            .insert(player, HashSet::from([area]));
+        apply_action(snapshot, player, &SimAction::Expand { area, tokens });
+    }
+
+    #[test]
+    fn test_building_a_city_returns_the_tokens() {
+        let mut snapshot = empty_snapshot(GameActivity::CityConstruction);
+        let player = add_player(&mut snapshot, 10);
+        let area = add_area(&mut snapshot, 3, true);
+        put_tokens(&mut snapshot, player, area, 6);
+
+        assert!(legal_actions(&snapshot, player).contains(&SimAction::BuildCity { area }));
+        apply_action(&mut snapshot, player, &SimAction::BuildCity { area });
+
+        let sim_player = &snapshot.players[&player];
+        assert_eq!(sim_player.player_cities.number_of_cities(), 1);
+        assert_eq!(sim_player.token_stock.tokens_in_stock(), 10);
+        assert!(!snapshot.areas[&area].population.has_population());
+    }
+
+    #[test]
+    fn test_conflict_returns_losing_tokens_to_their_owner() {
+        let mut snapshot = empty_snapshot(GameActivity::Conflict);
+        let attacker = add_player(&mut snapshot, 5);
+        let defender = add_player(&mut snapshot, 5);
+        let area = add_area(&mut snapshot, 2, false);
+        put_tokens(&mut snapshot, attacker, area, 4);
+        put_tokens(&mut snapshot, defender, area, 1);
+
+        resolve_conflicts(&mut snapshot);
+
+        let population = &snapshot.areas[&area].population;
+        assert_eq!(population.population_for_player(defender), 0);
+        assert_eq!(snapshot.players[&defender].token_stock.tokens_in_stock(), 5);
+        assert!(population.population_for_player(attacker) > 0);
+    }
+
+    #[test]
+    fn test_weighing_opponents_heavier_lowers_the_reward() {
+        let mut snapshot = empty_snapshot(GameActivity::Movement);
+        let player = add_player(&mut snapshot, 4);
+        let opponent = add_player(&mut snapshot, 4);
+        let area = add_area(&mut snapshot, 4, false);
+        let opponent_area = add_area(&mut snapshot, 4, false);
+        put_tokens(&mut snapshot, player, area, 2);
+        put_tokens(&mut snapshot, opponent, opponent_area, 2);
+
+        let settings = MctsSettings::default();
+        let aggressive = MctsSettings {
+            opponent_weight: 3.0,
+            ..MctsSettings::default()
+        };
+
+        assert_eq!(reward(&snapshot, player, &settings), 0.5);
+        assert_eq!(reward(&snapshot, player, &aggressive), 0.25);
+    }
+
+    #[test]
+    fn test_search_moves_to_the_city_site() {
+        let mut snapshot = empty_snapshot(GameActivity::Movement);
+        let player = add_player(&mut snapshot, 6);
+        let source = add_area(&mut snapshot, 1, false);
+        let city_site = add_area(&mut snapshot, 1, true);
+        snapshot.areas.get_mut(&source).unwrap().connections = vec![city_site];
+        put_tokens(&mut snapshot, player, source, 6);
+        let opponent = add_player(&mut snapshot, 4);
+        let opponent_area = add_area(&mut snapshot, 4, false);
+        put_tokens(&mut snapshot, opponent, opponent_area, 4);
+        snapshot.movement_order = vec![player];
+
+        let settings = MctsSettings {
+            iterations: 200,
+            ..MctsSettings::default()
+        };
+        let actions = legal_actions(&snapshot, player);
+
+        // The rest of the tokens can follow later, so any number of them is a good start
+        assert!(matches!(
+            search(
+                &snapshot,
+                player,
+                actions,
+                &settings,
+                &mut StdRng::seed_from_u64(1)
+            ),
+            Some(SimAction::Move { target, .. }) if target == city_site
+        ));
+    }
+
+    #[test]
+    fn test_move_from_stock_to_area() {
+        let mut population = Population::new(4);
+        let token_1 = create_entity();
+        let token_2 = create_entity();
+        let mut token_stock = TokenStock::new(47, vec![token_1, token_2]);
+        let mut player_areas = PlayerAreas::default();
+        let player = create_entity();
+        let area = create_entity();
+
+        move_from_stock_to_area(
+            player,
+            area,
+            1,
+            &mut population,
+            &mut token_stock,
+            &mut player_areas,
+        );
+
+        assert!(population.has_player(&player));
+        assert_eq!(token_stock.tokens_in_stock(), 1);
+    }
+
+    #[test]
+    fn test_return_all_tokens_from_area_to_player() {
+        let mut population = Population::new(4);
+        let token_1 = create_entity();
+        let token_2 = create_entity();
+        let mut token_stock = TokenStock::new(47, vec![token_1, token_2]);
+        let mut player_areas = PlayerAreas::default();
+        let player = create_entity();
+        let area = create_entity();
+
+        move_from_stock_to_area(
+            player,
+            area,
+            2,
+            &mut population,
+            &mut token_stock,
+            &mut player_areas,
+        );
+        return_all_tokens_from_area_to_player(
+            &player,
+            &area,
+            &mut population,
+            &mut token_stock,
+            &mut player_areas,
+        );
+
+        assert!(token_stock.tokens_in_stock() >= 2);
+        assert!(!player_areas.contains(area));
+    }
+
+    #[test]
+    fn test_replace_city_with_tokens_for_conflict_city_removed() {
+        let area_entity = create_entity();
+        let mut population = Population::new(4);
+        let city_token = create_entity();
+        let mut city_stock = CityTokenStock::new(7, vec![]);
+        let mut token_stock = TokenStock::new(47, vec![]);
+        let mut player_cities = PlayerCities::default();
+        let mut player_areas = PlayerAreas::default();
+        let built_city = BuiltCity {
+            player: create_entity(),
+            city: city_token,
+        };
+
+        // Build city first to simulate a scenario
+        player_cities.build_city_in_area(area_entity, city_token);
+
+        replace_city_with_tokens_for_conflict(
+            area_entity,
+            &mut population,
+            &built_city,
+            &mut city_stock,
+            &mut token_stock,
+            &mut player_cities,
+            &mut player_areas,
+        );
+
+        // Check that the city has been removed from the player cities
+        assert!(!player_cities.has_city_in(area_entity));
+        // Check that the city token has been returned to the stock
+        assert_eq!(city_stock.get_token_from_stock().unwrap(), city_token);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::mcts_ai::prelude::*;
+use crate::GameActivity;
+use bevy::app::{Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs};
+
+pub struct MctsAiPlugin;
+
+impl Plugin for MctsAiPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MctsAi>()
+            .register_type::<MctsSettings>()
+            .init_resource::<MctsSettings>()
+            .add_message::<SelectMctsMove>()
+            .add_systems(
+                Update,
+                (
+                    select_mcts_pop_exp.run_if(in_state(GameActivity::PopulationExpansion)),
+                    select_mcts_movement.run_if(in_state(GameActivity::Movement)),
+                    select_mcts_attacker.run_if(in_state(GameActivity::Conflict)),
+                    select_mcts_city_building.run_if(in_state(GameActivity::CityConstruction)),
+                    select_mcts_city_elimination.run_if(in_state(GameActivity::CheckCitySupport)),
+                    delegate_mcts_trade_move.run_if(in_state(GameActivity::Trade)),
+                ),
+            )
+            .add_observer(on_add_available_moves_for_mcts_ai);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use bevy::prelude::{Reflect, Resource};
+
+/// How hard the MCTS AI thinks about every move. The search stops at whichever limit it hits first.
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct MctsSettings {
+    /// Rounds played out per move
+    pub iterations: usize,
+    /// Time the search may take per move. The search runs inside the frame, so in the game it has
+    /// to stay well below a frame's time.
+    pub time_budget_ms: u64,
+    /// How eagerly the search tries moves it knows little about
+    pub exploration: f32,
+    /// Worth of a city in tokens when scoring a played out round
+    pub city_value: f32,
+    /// How much the points of the other players count against ours
+    pub opponent_weight: f32,
+}
+
+impl MctsSettings {
+    /// The settings as seen by a player with the given personality.
+    pub fn with_personality(&self, personality: Option<&AiPersonality>) -> Self {
+        let Some(personality) = personality else {
+            return self.clone();
+        };
+        MctsSettings {
+            exploration: self.exploration * personality.risk_tolerance,
+            city_value: self.city_value * personality.city_building,
+            opponent_weight: self.opponent_weight * personality.attack,
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for MctsSettings {
+    fn default() -> Self {
+        MctsSettings {
+            iterations: 2000,
+            time_budget_ms: 8,
+            exploration: 1.4,
+            city_value: 6.0,
+            opponent_weight: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_personality_scales_the_search() {
+        let settings = MctsSettings::default();
+
+        let adjusted =
+            settings.with_personality(Some(&AiPersonality::new("test", 2.0, 3.0, 1.0, 0.5)));
+
+        assert_eq!(adjusted.exploration, settings.exploration * 0.5);
+        assert_eq!(adjusted.city_value, settings.city_value * 3.0);
+        assert_eq!(adjusted.opponent_weight, settings.opponent_weight * 2.0);
+    }
+
+    #[test]
+    fn test_personality_leaves_the_budget_alone() {
+        let settings = MctsSettings::default();
+
+        let adjusted =
+            settings.with_personality(Some(&AiPersonality::new("test", 2.0, 3.0, 1.0, 0.5)));
+
+        assert_eq!(adjusted.iterations, settings.iterations);
+        assert_eq!(adjusted.time_budget_ms, settings.time_budget_ms);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::*;
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::census::census_functions::movement_order;
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::check_city_support::check_city_support_events::EliminateCity;
+use crate::civilization::concepts::city_construction::city_construction_events::{
+    BuildCityCommand, EndPlayerCityConstruction,
+};
+use crate::civilization::concepts::conflict::conflict_events::FightAttackerFirst;
+use crate::civilization::concepts::movement::movement_components::{
+    PerformingMovement, TokenHasMoved,
+};
+use crate::civilization::concepts::movement::movement_events::{
+    MoveTokenFromAreaToAreaCommand, PlayerMovementEnded,
+};
+use crate::civilization::concepts::population_expansion::population_expansion_components::NeedsExpansion;
+use crate::civilization::concepts::population_expansion::population_expansion_events::ExpandPopulationManuallyCommand;
+use crate::civilization::concepts::rules::rules_resources::{GameRng, RuleSet};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move};
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::mcts_ai::mcts_ai_components::{GameSnapshot, SimAction, SimArea, SimPlayer};
+use crate::mcts_ai::mcts_ai_events::SelectMctsMove;
+use crate::mcts_ai::mcts_ai_functions::search;
+use crate::mcts_ai::mcts_ai_resources::MctsSettings;
+use crate::trade_ai::trade_ai_events::SelectTradeMove;
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{
+    debug, Entity, Has, MessageReader, MessageWriter, Name, Query, Res, ResMut, With,
+};
+use rand::rngs::StdRng;
+
+/// Everything on the board the MCTS AI copies into a [`GameSnapshot`] before searching.
+#[derive(SystemParam)]
+pub struct MctsBoard<'w, 's> {
+    areas: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static LandPassage,
+            Has<CitySite>,
+            Option<&'static BuiltCity>,
+        ),
+    >,
+    players: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static TokenStock,
+            &'static CityTokenStock,
+            &'static PlayerCities,
+            Option<&'static NeedsExpansion>,
+        ),
+    >,
+    populations: Populations<'w, 's>,
+    tokens: Query<'w, 's, (Entity, &'static Token, Has<TokenHasMoved>)>,
+    performing_movement: Query<'w, 's, Entity, With<PerformingMovement>>,
+    game_info: Res<'w, GameInfoAndStuff>,
+    rules: Res<'w, RuleSet>,
+}
+
+impl MctsBoard<'_, '_> {
+    pub fn snapshot(&self, phase: GameActivity) -> GameSnapshot {
+        let areas = self
+            .areas
+            .iter()
+            .map(|(area, passage, city_site, built_city)| {
+                (
+                    area,
+                    SimArea {
+                        population: self.populations.get(area).unwrap_or_default(),
+                        connections: passage.to_areas.clone(),
+                        city_site,
+                        city: built_city.map(|city| (city.player, city.city)),
+                    },
+                )
+            })
+            .collect();
+
+        let mut needs_expansion = HashMap::default();
+        let players = self
+            .players
+            .iter()
+            .map(
+                |(player, token_stock, city_stock, player_cities, expansion)| {
+                    if let Some(expansion) = expansion {
+                        needs_expansion.insert(player, expansion.areas_that_need_expansion.clone());
+                    }
+                    (
+                        player,
+                        SimPlayer {
+                            token_stock: token_stock.clone(),
+                            city_stock: city_stock.clone(),
+                            player_cities: player_cities.clone(),
+                            player_areas: self.populations.player_areas(player),
+                        },
+                    )
+                },
+            )
+            .collect();
+
+        let movement_order = if phase == GameActivity::Movement {
+            self.performing_movement
+                .iter()
+                .chain(self.game_info.left_to_move.iter().rev().copied())
+                .collect()
+        } else {
+            movement_order(&self.game_info.census_order, self.rules.movement_order)
+        };
+
+        GameSnapshot {
+            areas,
+            players,
+            token_owners: self
+                .tokens
+                .iter()
+                .map(|(token, owner, _)| (token, owner.player()))
+                .collect(),
+            phase,
+            movement_order,
+            moved_tokens: self
+                .tokens
+                .iter()
+                .filter(|(_, _, has_moved)| *has_moved)
+                .map(|(token, _, _)| token)
+                .collect(),
+            needs_expansion,
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+/// The available moves as the search sees them, with every number of tokens a move allows.
+pub fn root_actions(available_moves: &AvailableMoves) -> Vec<SimAction> {
+    let mut actions = vec![];
+    for m in available_moves.moves.values() {
+        match m {
+            Move::PopulationExpansion(pop_exp_move) => actions.push(SimAction::Expand {
+                area: pop_exp_move.area,
+                tokens: pop_exp_move.max_tokens,
+            }),
+            Move::Movement(movement_move)
+            | Move::AttackArea(movement_move)
+            | Move::AttackCity(movement_move) => actions.extend(
+                (1..=movement_move.max_tokens).map(|tokens| SimAction::Move {
+                    source: movement_move.source,
+                    target: movement_move.target,
+                    tokens,
+                }),
+            ),
+            Move::EndMovement => actions.push(SimAction::EndMovement),
+            Move::ChooseAttacker(_) => {}
+            Move::CityConstruction(build_city_move) => actions.push(SimAction::BuildCity {
+                area: build_city_move.target,
+            }),
+            Move::EndCityConstruction => actions.push(SimAction::EndCityConstruction),
+            Move::EliminateCity(el_move) => actions.push(SimAction::EliminateCity {
+                area: el_move.area,
+                city: el_move.city,
+            }),
+            Move::Trade(_) => {}
+        }
+    }
+    actions
+}
+
+fn search_move(
+    board: &MctsBoard,
+    phase: GameActivity,
+    player: Entity,
+    available_moves: &AvailableMoves,
+    settings: &MctsSettings,
+    rng: &mut StdRng,
+) -> Option<SimAction> {
+    search(
+        &board.snapshot(phase),
+        player,
+        root_actions(available_moves),
+        settings,
+        rng,
+    )
+}
+
+pub fn select_mcts_pop_exp(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    player_moves: Query<(&Name, &AvailableMoves, Option<&AiPersonality>)>,
+    board: MctsBoard,
+    mut expand_writer: MessageWriter<ExpandPopulationManuallyCommand>,
+    settings: Res<MctsSettings>,
+    debug_options: Res<DebugOptions>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, personality)) = player_moves.get(event.player) {
+            let settings = settings.with_personality(personality);
+            let best_move = search_move(
+                &board,
+                GameActivity::PopulationExpansion,
+                event.player,
+                available_moves,
+                &settings,
+                &mut game_rng.0,
+            );
+            if let Some(SimAction::Expand { area, tokens }) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!("{} expands into {:?}", player_name, area);
+                }
+                expand_writer.write(ExpandPopulationManuallyCommand::new(
+                    event.player,
+                    area,
+                    tokens,
+                ));
+            }
+        }
+    }
+}
+
+pub fn select_mcts_movement(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    player_moves: Query<(&Name, &AvailableMoves, Option<&AiPersonality>)>,
+    board: MctsBoard,
+    mut move_tokens_writer: MessageWriter<MoveTokenFromAreaToAreaCommand>,
+    mut end_movement_writer: MessageWriter<PlayerMovementEnded>,
+    (settings, debug_options): (Res<MctsSettings>, Res<DebugOptions>),
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, personality)) = player_moves.get(event.player) {
+            let settings = settings.with_personality(personality);
+            let best_move = search_move(
+                &board,
+                GameActivity::Movement,
+                event.player,
+                available_moves,
+                &settings,
+                &mut game_rng.0,
+            );
+            if let Some(SimAction::Move {
+                source,
+                target,
+                tokens,
+            }) = best_move
+            {
+                if debug_options.print_selected_moves {
+                    debug!(
+                        "{} moves {} tokens from {:?} to {:?}",
+                        player_name, tokens, source, target
+                    );
+                }
+                move_tokens_writer.write(MoveTokenFromAreaToAreaCommand::new(
+                    source,
+                    target,
+                    tokens,
+                    event.player,
+                ));
+            } else {
+                end_movement_writer.write(PlayerMovementEnded::new(event.player));
+            }
+        }
+    }
+}
+
+pub fn select_mcts_city_building(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    player_moves: Query<(&Name, &AvailableMoves, Option<&AiPersonality>)>,
+    board: MctsBoard,
+    mut build_city_writer: MessageWriter<BuildCityCommand>,
+    mut end_player_city_construction: MessageWriter<EndPlayerCityConstruction>,
+    (settings, debug_options): (Res<MctsSettings>, Res<DebugOptions>),
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, personality)) = player_moves.get(event.player) {
+            let settings = settings.with_personality(personality);
+            let best_move = search_move(
+                &board,
+                GameActivity::CityConstruction,
+                event.player,
+                available_moves,
+                &settings,
+                &mut game_rng.0,
+            );
+            if let Some(SimAction::BuildCity { area }) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!("{} builds a city in {:?}", player_name, area);
+                }
+                build_city_writer.write(BuildCityCommand::new(event.player, area));
+            } else {
+                end_player_city_construction.write(EndPlayerCityConstruction::new(event.player));
+            }
+        }
+    }
+}
+
+pub fn select_mcts_city_elimination(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    player_moves: Query<(&Name, &AvailableMoves, Option<&AiPersonality>)>,
+    board: MctsBoard,
+    mut eliminate_city: MessageWriter<EliminateCity>,
+    settings: Res<MctsSettings>,
+    debug_options: Res<DebugOptions>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves, personality)) = player_moves.get(event.player) {
+            let settings = settings.with_personality(personality);
+            let best_move = search_move(
+                &board,
+                GameActivity::CheckCitySupport,
+                event.player,
+                available_moves,
+                &settings,
+                &mut game_rng.0,
+            );
+            if let Some(SimAction::EliminateCity { area, city }) = best_move {
+                if debug_options.print_selected_moves {
+                    debug!("{} eliminates the city in {:?}", player_name, area);
+                }
+                eliminate_city.write(EliminateCity::new(event.player, city, area, false));
+            }
+        }
+    }
+}
+
+/// The search resolves conflicts with the weakest attacker fought first, so the MCTS AI does
+/// the same.
+pub fn select_mcts_attacker(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    player_moves: Query<(&Name, &AvailableMoves)>,
+    mut fight_attacker: MessageWriter<FightAttackerFirst>,
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        if let Ok((player_name, available_moves)) = player_moves.get(event.player) {
+            let weakest = available_moves
+                .moves
+                .values()
+                .filter_map(|m| match m {
+                    Move::ChooseAttacker(choose_move) => Some(choose_move),
+                    _ => None,
+                })
+                .min_by_key(|choose_move| (choose_move.attacker_tokens, choose_move.attacker));
+
+            if let Some(choose_move) = weakest {
+                if debug_options.print_selected_moves {
+                    debug!("{} fights {:?} first", player_name, choose_move.attacker);
+                }
+                fight_attacker.write(FightAttackerFirst::new(
+                    event.player,
+                    choose_move.area,
+                    choose_move.attacker,
+                ));
+            }
+        }
+    }
+}
+
+/// Trading is not part of the search, so the trade AI trades for the MCTS AI.
+pub fn delegate_mcts_trade_move(
+    mut event_reader: MessageReader<SelectMctsMove>,
+    mut trade_writer: MessageWriter<SelectTradeMove>,
+) {
+    for event in event_reader.read() {
+        trade_writer.write(SelectTradeMove::new(event.player));
+    }
+}
@@ -0,0 +1,16 @@
+use crate::civilization::concepts::spectator::spectator_systems::SpectatorGate;
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::mcts_ai::mcts_ai_components::MctsAi;
+use crate::mcts_ai::mcts_ai_events::SelectMctsMove;
+use bevy::prelude::{Add, MessageWriter, On, Query};
+
+pub fn on_add_available_moves_for_mcts_ai(
+    trigger: On<Add, AvailableMoves>,
+    is_mcts_ai: Query<&MctsAi>,
+    mut event_writer: MessageWriter<SelectMctsMove>,
+    mut gate: SpectatorGate,
+) {
+    if is_mcts_ai.contains(trigger.event().entity) && !gate.hold_back(trigger.event().entity) {
+        event_writer.write(SelectMctsMove::new(trigger.event().entity));
+    }
+}
@@ -0,0 +1,16 @@
+pub mod mcts_ai_components;
+pub mod mcts_ai_events;
+pub mod mcts_ai_functions;
+pub mod mcts_ai_plugin;
+pub mod mcts_ai_resources;
+pub mod mcts_ai_systems;
+pub mod mcts_ai_triggers;
+
+pub mod prelude {
+    pub use super::mcts_ai_components::*;
+    pub use super::mcts_ai_events::*;
+    pub use super::mcts_ai_plugin::*;
+    pub use super::mcts_ai_resources::*;
+    pub use super::mcts_ai_systems::*;
+    pub use super::mcts_ai_triggers::*;
+}
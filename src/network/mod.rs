@@ -0,0 +1,39 @@
+//! Plays a game with remote players over TCP.
+//!
+//! The server runs the game on the rule plugins, like the tournament does, and remote players
+//! take the first seats. Every message is one line of JSON with a `type` field. A client opens
+//! with `{"type": "hello", "version": 3, "name": "..."}` and gets a `welcome` with its seat,
+//! or an `error` if it speaks another version of the protocol. From then on the server sends:
+//!
+//! - `state`: the board as the client's seat may see it. Only its own trade cards are listed,
+//!   other players' cards are just counted, and of the offers it is part of only the promised
+//!   cards are shown. Areas carry their `map_id` from the map file, so clients with the map
+//!   can draw the board on it.
+//! - `move_request`: the moves the seat can make, answered with
+//!   `{"type": "play_move", "request_id": 3, "move_id": 0}`. Moves with tokens take an
+//!   optional `"tokens": n`, trade offers and settlements the cards in `"gives"` and `"asks"`,
+//!   like `{"Ochre": 2, "Salt": 1}`.
+//! - `error`: a bad answer, the client may try again until its time is up and an AI moves
+//!   for it.
+//! - `game_over`: the final scores.
+//!
+//! The game waits while a remote player thinks. Besides the terminal client, the game itself
+//! plays a seat on the map with `cargo run -- --connect 127.0.0.1:7878 --name Ada`.
+
+pub mod network_components;
+pub mod network_events;
+pub mod network_functions;
+pub mod network_plugin;
+pub mod network_resources;
+pub mod network_systems;
+pub mod network_triggers;
+
+pub mod prelude {
+    pub use super::network_components::*;
+    pub use super::network_events::*;
+    pub use super::network_functions::*;
+    pub use super::network_plugin::*;
+    pub use super::network_resources::*;
+    pub use super::network_systems::*;
+    pub use super::network_triggers::*;
+}
@@ -0,0 +1,297 @@
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::civilization::game_moves::game_moves_components::Move;
+use crate::tournament::tournament_components::Controller;
+use bevy::platform::time::Instant;
+use bevy::prelude::{Component, Entity, Reflect};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+
+/// Clients speaking another version are turned away.
+pub const PROTOCOL_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        version: u32,
+        name: String,
+    },
+    PlayMove {
+        request_id: u64,
+        #[serde(flatten)]
+        choice: MoveChoice,
+    },
+}
+
+/// The move a client picks, with the tokens and cards it plays it with.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MoveChoice {
+    pub move_id: usize,
+    /// For moves that take tokens, as many as the move allows when left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<usize>,
+    /// The cards offered in a trade, or handed over when settling one
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub gives: BTreeMap<String, usize>,
+    /// The cards asked for in a trade
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub asks: BTreeMap<String, usize>,
+}
+
+impl MoveChoice {
+    pub fn new(move_id: usize) -> Self {
+        MoveChoice {
+            move_id,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Welcome {
+        version: u32,
+        seat: usize,
+        player: u64,
+        faction: String,
+    },
+    State {
+        round: usize,
+        phase: String,
+        view: GameView,
+    },
+    MoveRequest {
+        request_id: u64,
+        moves: Vec<MoveView>,
+    },
+    Error {
+        request_id: Option<u64>,
+        message: String,
+    },
+    GameOver {
+        scores: Vec<SeatScore>,
+    },
+}
+
+/// The board as one seat sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameView {
+    pub areas: Vec<AreaView>,
+    pub players: Vec<PlayerView>,
+    /// The trade offers the seat makes or gets
+    pub offers: Vec<OfferView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AreaView {
+    pub id: u64,
+    /// The id of the area in the map file, for clients that draw the map themselves
+    pub map_id: i32,
+    pub name: String,
+    pub max_population: usize,
+    pub city_site: bool,
+    pub city_owner: Option<u64>,
+    pub population: Vec<PlayerTokens>,
+    pub connections: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerTokens {
+    pub player: u64,
+    pub tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub id: u64,
+    pub faction: String,
+    pub tokens_in_stock: usize,
+    pub cities_in_stock: usize,
+    pub cities: usize,
+    pub trade_cards: usize,
+    /// Only filled in for the seat the view is for
+    pub hand: Option<BTreeMap<String, usize>>,
+}
+
+/// A trade offer with only the promised cards in view, the rest are just counted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfferView {
+    pub id: u64,
+    pub initiator: u64,
+    pub receiver: u64,
+    pub gives: BTreeMap<String, usize>,
+    pub gives_count: usize,
+    pub asks: BTreeMap<String, usize>,
+    pub asks_count: usize,
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveView {
+    pub id: usize,
+    #[serde(flatten)]
+    pub action: ActionView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActionView {
+    Expand {
+        area: u64,
+        max_tokens: usize,
+    },
+    Move {
+        source: u64,
+        target: u64,
+        max_tokens: usize,
+    },
+    AttackArea {
+        source: u64,
+        target: u64,
+        max_tokens: usize,
+    },
+    AttackCity {
+        source: u64,
+        target: u64,
+        max_tokens: usize,
+    },
+    EndMovement,
+    ChooseAttacker {
+        area: u64,
+        attacker: u64,
+        attacker_tokens: usize,
+    },
+    BuildCity {
+        area: u64,
+    },
+    EndCityConstruction,
+    EliminateCity {
+        area: u64,
+        tokens_gained: usize,
+    },
+    /// Answered with the cards in `gives` and `asks`, at least three of each. The first two of
+    /// each, in name order, are the promised ones.
+    ProposeTrade {
+        receiver: u64,
+    },
+    AcceptOffer {
+        offer: u64,
+    },
+    DeclineOffer {
+        offer: u64,
+    },
+    WithdrawOffer {
+        offer: u64,
+    },
+    /// Answered with the `cards` to hand over in `gives`, the promised ones among them.
+    SettleTrade {
+        offer: u64,
+        cards: usize,
+        promised: BTreeMap<String, usize>,
+    },
+    BackOutOfTrade {
+        offer: u64,
+    },
+    StopTrading,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatScore {
+    pub seat: usize,
+    pub name: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Seats for remote players, they come first
+    pub remote_seats: usize,
+    /// The AIs filling the other seats
+    pub ai_seats: Vec<Controller>,
+    pub rules: RuleSet,
+    pub max_rounds: usize,
+    /// How long a remote player gets for a move before an AI makes it
+    pub timeout_ms: u64,
+    pub mcts_iterations: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            remote_seats: 2,
+            ai_seats: vec![Controller::Heuristic],
+            rules: RuleSet::default(),
+            max_rounds: 20,
+            timeout_ms: 60_000,
+            mcts_iterations: 300,
+        }
+    }
+}
+
+/// A player whose moves are made by a client connected to the server.
+#[derive(Component, Debug, Reflect, Default)]
+pub struct RemotePlayer;
+
+/// The connection of a remote player. A reader thread hands over the lines it sends.
+#[derive(Component)]
+pub struct ClientConnection {
+    pub name: String,
+    pub stream: TcpStream,
+    pub replies: Mutex<Receiver<String>>,
+}
+
+impl ClientConnection {
+    pub fn send(&mut self, message: &ServerMessage) -> std::io::Result<()> {
+        let line = serde_json::to_string(message)?;
+        writeln!(self.stream, "{line}")?;
+        self.stream.flush()
+    }
+
+    /// The next line the client sent, if any. Err once the client has hung up.
+    pub fn next_reply(&self) -> Result<Option<String>, String> {
+        let replies = self.replies.lock().map_err(|err| err.to_string())?;
+        match replies.try_recv() {
+            Ok(line) => Ok(Some(line)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err("the client hung up".to_string()),
+        }
+    }
+}
+
+/// What playing one of the moves sent to a remote player does.
+#[derive(Debug, Clone)]
+pub enum RemoteMove {
+    /// One of the player's available moves, trade moves are played by the other variants
+    Play(Move),
+    ProposeTrade(Entity),
+    AcceptOffer(Entity),
+    DeclineOffer(Entity),
+    WithdrawOffer(Entity),
+    SettleTrade(Entity),
+    BackOutOfTrade(Entity),
+    StopTrading,
+}
+
+/// The remote player has been sent its moves and we are waiting for its answer. Move ids are
+/// indexes into `moves`.
+#[derive(Component, Debug)]
+pub struct PendingRemoteMove {
+    pub request_id: u64,
+    pub sent_at: Instant,
+    pub moves: Vec<RemoteMove>,
+}
+
+impl PendingRemoteMove {
+    pub fn new(request_id: u64, moves: Vec<RemoteMove>) -> Self {
+        PendingRemoteMove {
+            request_id,
+            sent_at: Instant::now(),
+            moves,
+        }
+    }
+}
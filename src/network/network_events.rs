@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct SelectRemoteMove {
+    pub player: Entity,
+}
+
+impl SelectRemoteMove {
+    pub fn new(player: Entity) -> Self {
+        SelectRemoteMove { player }
+    }
+}
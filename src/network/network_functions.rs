@@ -0,0 +1,964 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::{
+    TradeCard, TradeCardTrait,
+};
+use crate::civilization::concepts::hot_seat::hot_seat_functions::{can_pay, max_tokens};
+use crate::civilization::concepts::hot_seat::hot_seat_resources::Settlement;
+use crate::civilization::concepts::map::map_plugin::Map;
+use crate::civilization::concepts::trade::trade_components::TradeOffer;
+use crate::civilization::console::console_functions::parse_trade_card;
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move, TradeMove};
+use crate::external_bot::external_bot_functions::entity_id;
+use crate::heuristic_ai::heuristic_ai_components::HeuristicAi;
+use crate::network::network_components::{
+    ActionView, ClientConnection, ClientMessage, GameView, MoveChoice, MoveView,
+    PendingRemoteMove, RemoteMove, RemotePlayer, SeatScore, ServerConfig, ServerMessage,
+    PROTOCOL_VERSION,
+};
+use crate::network::network_plugin::RemotePlayerPlugin;
+use crate::network::network_resources::RemotePlayerSettings;
+use crate::network::network_systems::{receive_remote_moves, RemoteObservation};
+use crate::tournament::headless_game::HeadlessGame;
+use crate::tournament::tournament_components::{Controller, TournamentConfig};
+use crate::tournament::tournament_functions::{map_factions, ControllerSettings};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{App, Entity, In, With, World};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+/// How long a new connection has to say hello.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often the server looks for answers while a remote player thinks.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+pub fn card_names(cards: &HashMap<TradeCard, usize>) -> BTreeMap<String, usize> {
+    cards
+        .iter()
+        .map(|(card, count)| (card.to_string(), *count))
+        .collect()
+}
+
+pub fn parse_cards(cards: &BTreeMap<String, usize>) -> Result<HashMap<TradeCard, usize>, String> {
+    let mut parsed = HashMap::default();
+    for (name, count) in cards.iter().filter(|(_, count)| **count > 0) {
+        *parsed.entry(parse_trade_card(name)?).or_default() += *count;
+    }
+    Ok(parsed)
+}
+
+/// The cards a player owes for a trade: the promised ones and how many in all.
+pub fn owed_cards(me: Entity, offer: &TradeOffer) -> (HashMap<TradeCard, usize>, usize) {
+    if offer.am_i_the_initiator(me) {
+        (
+            offer.initiator_pays_guaranteed.clone(),
+            offer.pays_number_of_cards(),
+        )
+    } else {
+        (
+            offer.initiator_gets_guaranteed.clone(),
+            offer.gets_number_of_cards(),
+        )
+    }
+}
+
+/// The moves a remote player picks from, in move order, with what the client is told about
+/// them. Offers made to the player can be accepted or declined, its own offers withdrawn and
+/// a trade it cannot pay for backed out of.
+pub fn remote_moves(
+    me: Entity,
+    available_moves: &AvailableMoves,
+    offers: &HashMap<Entity, TradeOffer>,
+) -> Vec<(RemoteMove, ActionView)> {
+    let mut ids = available_moves.moves.keys().copied().collect::<Vec<_>>();
+    ids.sort();
+    let mut moves = vec![];
+    for id in ids {
+        let game_move = &available_moves.moves[&id];
+        let action = match game_move {
+            Move::PopulationExpansion(pop_exp_move) => ActionView::Expand {
+                area: entity_id(pop_exp_move.area),
+                max_tokens: pop_exp_move.max_tokens,
+            },
+            Move::Movement(movement_move) => ActionView::Move {
+                source: entity_id(movement_move.source),
+                target: entity_id(movement_move.target),
+                max_tokens: movement_move.max_tokens,
+            },
+            Move::AttackArea(movement_move) => ActionView::AttackArea {
+                source: entity_id(movement_move.source),
+                target: entity_id(movement_move.target),
+                max_tokens: movement_move.max_tokens,
+            },
+            Move::AttackCity(movement_move) => ActionView::AttackCity {
+                source: entity_id(movement_move.source),
+                target: entity_id(movement_move.target),
+                max_tokens: movement_move.max_tokens,
+            },
+            Move::EndMovement => ActionView::EndMovement,
+            Move::ChooseAttacker(choose_move) => ActionView::ChooseAttacker {
+                area: entity_id(choose_move.area),
+                attacker: entity_id(choose_move.attacker),
+                attacker_tokens: choose_move.attacker_tokens,
+            },
+            Move::CityConstruction(build_city_move) => ActionView::BuildCity {
+                area: entity_id(build_city_move.target),
+            },
+            Move::EndCityConstruction => ActionView::EndCityConstruction,
+            Move::EliminateCity(el_move) => ActionView::EliminateCity {
+                area: entity_id(el_move.area),
+                tokens_gained: el_move.tokens_gained,
+            },
+            Move::Trade(trade_move) => {
+                moves.extend(trade_moves(me, trade_move, offers));
+                continue;
+            }
+        };
+        moves.push((RemoteMove::Play(game_move.clone()), action));
+    }
+    moves
+}
+
+fn trade_moves(
+    me: Entity,
+    trade_move: &TradeMove,
+    offers: &HashMap<Entity, TradeOffer>,
+) -> Vec<(RemoteMove, ActionView)> {
+    match trade_move {
+        TradeMove::ProposeTrade(receiver, _) => vec![(
+            RemoteMove::ProposeTrade(*receiver),
+            ActionView::ProposeTrade {
+                receiver: entity_id(*receiver),
+            },
+        )],
+        TradeMove::AcceptOrDeclineTrade(trade) => {
+            let offer = entity_id(*trade);
+            match offers.get(trade) {
+                Some(trade_offer) if trade_offer.am_i_the_receiver(me) => vec![
+                    (
+                        RemoteMove::AcceptOffer(*trade),
+                        ActionView::AcceptOffer { offer },
+                    ),
+                    (
+                        RemoteMove::DeclineOffer(*trade),
+                        ActionView::DeclineOffer { offer },
+                    ),
+                ],
+                Some(_) => vec![(
+                    RemoteMove::WithdrawOffer(*trade),
+                    ActionView::WithdrawOffer { offer },
+                )],
+                None => vec![],
+            }
+        }
+        TradeMove::AutoDeclineTrade(trade) => vec![(
+            RemoteMove::DeclineOffer(*trade),
+            ActionView::DeclineOffer {
+                offer: entity_id(*trade),
+            },
+        )],
+        TradeMove::SettleTrade(trade) => {
+            let Some(trade_offer) = offers.get(trade) else {
+                return vec![];
+            };
+            let offer = entity_id(*trade);
+            let (promised, cards) = owed_cards(me, trade_offer);
+            vec![
+                (
+                    RemoteMove::SettleTrade(*trade),
+                    ActionView::SettleTrade {
+                        offer,
+                        cards,
+                        promised: card_names(&promised),
+                    },
+                ),
+                (
+                    RemoteMove::BackOutOfTrade(*trade),
+                    ActionView::BackOutOfTrade { offer },
+                ),
+            ]
+        }
+        TradeMove::StopTrading => vec![(RemoteMove::StopTrading, ActionView::StopTrading)],
+    }
+}
+
+/// Moves are numbered in the order they are listed.
+pub fn move_views(actions: Vec<ActionView>) -> Vec<MoveView> {
+    actions
+        .into_iter()
+        .enumerate()
+        .map(|(id, action)| MoveView { id, action })
+        .collect()
+}
+
+/// The tokens to play a move with, as many as it allows when the client left them out.
+pub fn tokens_for(game_move: &Move, tokens: Option<usize>) -> Result<usize, String> {
+    let max = max_tokens(game_move);
+    if max == 0 {
+        return Ok(0);
+    }
+    let tokens = tokens.unwrap_or(max);
+    if tokens == 0 || tokens > max {
+        return Err(format!(
+            "the move takes between 1 and {max} tokens, not {tokens}"
+        ));
+    }
+    Ok(tokens)
+}
+
+/// The offer a client makes. It cannot promise cards it does not have.
+pub fn build_offer(
+    offer: TradeOffer,
+    choice: &MoveChoice,
+    hand: &PlayerTradeCards,
+) -> Result<TradeOffer, String> {
+    let mut offer = offer;
+    for (card, count) in parse_cards(&choice.gives)? {
+        if !card.is_tradeable() || hand.number_of_cards_for_trade_card(card) < count {
+            return Err(format!("you cannot give {count} {card}"));
+        }
+    }
+    // Name order decides which cards are promised
+    for (name, count) in choice.gives.iter() {
+        offer.pay_even_more(parse_trade_card(name)?, *count);
+    }
+    for (name, count) in choice.asks.iter() {
+        offer.get_even_more(parse_trade_card(name)?, *count);
+    }
+    if !offer.can_be_accepted() {
+        return Err("an offer takes at least three cards each way".to_string());
+    }
+    Ok(offer)
+}
+
+/// The cards a client hands over for a trade: all the promised ones and as many as it owes.
+pub fn settlement_cards(
+    me: Entity,
+    trade: Entity,
+    offer: &TradeOffer,
+    choice: &MoveChoice,
+    hand: &PlayerTradeCards,
+) -> Result<HashMap<TradeCard, usize>, String> {
+    let (promised, total) = owed_cards(me, offer);
+    if promised
+        .iter()
+        .any(|(card, count)| hand.number_of_cards_for_trade_card(*card) < *count)
+    {
+        return Err("the promised cards are no longer in your hand, back out of the trade".into());
+    }
+    let cards = parse_cards(&choice.gives)?;
+    if cards.values().sum::<usize>() != total {
+        return Err(format!("the trade takes {total} cards"));
+    }
+    if let Some((card, count)) = promised
+        .iter()
+        .find(|(card, count)| cards.get(*card).copied().unwrap_or_default() < **count)
+    {
+        return Err(format!("you promised {count} {card}"));
+    }
+    let mut settlement = Settlement::new(trade, promised.clone(), total);
+    for (card, count) in cards.iter() {
+        let promised_count = promised.get(card).copied().unwrap_or_default();
+        for _ in promised_count..*count {
+            if !can_pay(&settlement, hand.cards(), *card) {
+                return Err(format!("you cannot hand over {count} {card}"));
+            }
+            settlement.pay(*card);
+        }
+    }
+    Ok(settlement.cards)
+}
+
+/// The move a client answered request `request_id` with, or what is wrong with the answer.
+pub fn read_choice(line: &str, request_id: u64) -> Result<MoveChoice, String> {
+    match serde_json::from_str::<ClientMessage>(line.trim()) {
+        Ok(ClientMessage::PlayMove {
+            request_id: reply_id,
+            choice,
+        }) if reply_id == request_id => Ok(choice),
+        Ok(ClientMessage::PlayMove {
+            request_id: reply_id,
+            ..
+        }) => Err(format!(
+            "request {reply_id} is no longer open, answer request {request_id}"
+        )),
+        Ok(ClientMessage::Hello { .. }) => Err("you already said hello".to_string()),
+        Err(err) => Err(format!("could not read the message: {err}")),
+    }
+}
+
+/// The board as `player` may see it, with only its own trade cards in view.
+pub fn view_for(world: &mut World, player: Entity) -> GameView {
+    world
+        .run_system_once_with(
+            |In(player): In<Entity>, observation: RemoteObservation| observation.view(player),
+            player,
+        )
+        .unwrap()
+}
+
+pub fn state_message(world: &mut World, player: Entity) -> ServerMessage {
+    world
+        .run_system_once_with(
+            |In(player): In<Entity>, observation: RemoteObservation| {
+                observation.state_message(player)
+            },
+            player,
+        )
+        .unwrap()
+}
+
+/// The name a client says hello with, or why it cannot join.
+pub fn parse_hello(line: &str) -> Result<String, String> {
+    match serde_json::from_str::<ClientMessage>(line.trim()) {
+        Ok(ClientMessage::Hello { version, name }) if version == PROTOCOL_VERSION => Ok(name),
+        Ok(ClientMessage::Hello { version, .. }) => Err(format!(
+            "this server speaks protocol version {PROTOCOL_VERSION}, not {version}"
+        )),
+        Ok(_) => Err("say hello first".to_string()),
+        Err(err) => Err(format!("could not read the message: {err}")),
+    }
+}
+
+fn send_line(stream: &mut TcpStream, message: &ServerMessage) -> std::io::Result<()> {
+    let line = serde_json::to_string(message)?;
+    writeln!(stream, "{line}")?;
+    stream.flush()
+}
+
+/// Reads the hello of a new connection and starts listening to it.
+pub fn handshake(stream: TcpStream) -> Result<ClientConnection, String> {
+    let mut writer = stream.try_clone().map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(Some(HELLO_TIMEOUT))
+        .map_err(|err| err.to_string())?;
+    let mut reader = BufReader::new(stream);
+    let mut hello = String::new();
+    reader
+        .read_line(&mut hello)
+        .map_err(|err| format!("no hello: {err}"))?;
+    let name = match parse_hello(&hello) {
+        Ok(name) => name,
+        Err(message) => {
+            let _ = send_line(
+                &mut writer,
+                &ServerMessage::Error {
+                    request_id: None,
+                    message: message.clone(),
+                },
+            );
+            return Err(message);
+        }
+    };
+    reader
+        .get_ref()
+        .set_read_timeout(None)
+        .map_err(|err| err.to_string())?;
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(ClientConnection {
+        name,
+        stream: writer,
+        replies: Mutex::new(receiver),
+    })
+}
+
+/// Waits until every remote seat is taken. Connections that fail the handshake are dropped.
+pub fn accept_clients(
+    listener: &TcpListener,
+    count: usize,
+) -> Result<Vec<ClientConnection>, String> {
+    let mut clients = vec![];
+    while clients.len() < count {
+        let (stream, _) = listener.accept().map_err(|err| err.to_string())?;
+        if let Ok(client) = handshake(stream) {
+            clients.push(client);
+        }
+    }
+    Ok(clients)
+}
+
+/// Sends every connected remote player its own copy of a message.
+fn tell_remote_players(world: &mut World, message: impl Fn(&mut World, Entity) -> ServerMessage) {
+    let players = world
+        .query_filtered::<Entity, (With<RemotePlayer>, With<ClientConnection>)>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for player in players {
+        let message = message(world, player);
+        if let Some(mut connection) = world.get_mut::<ClientConnection>(player) {
+            let _ = connection.send(&message);
+        }
+    }
+}
+
+/// Holds the game while a remote player thinks, so the moves it was sent stay the ones it can
+/// make and the trade countdown does not run out on it.
+fn wait_for_remote_players(app: &mut App) {
+    let world = app.world_mut();
+    let mut pending = world.query_filtered::<(), With<PendingRemoteMove>>();
+    while pending.iter(world).next().is_some() {
+        let _ = world.run_system_once(receive_remote_moves);
+        if pending.iter(world).next().is_some() {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Plays a whole game on the rule plugins with remote players in the first seats and AIs in
+/// the rest, and tells everyone the final scores.
+pub fn run_server(
+    listener: &TcpListener,
+    map: &Map,
+    config: &ServerConfig,
+) -> Result<Vec<SeatScore>, String> {
+    let factions = map_factions(map);
+    // Remote seats are set up for the heuristic AI, which stands in for players who time out
+    let controllers = std::iter::repeat_n(Controller::Heuristic, config.remote_seats)
+        .chain(config.ai_seats.iter().copied())
+        .collect::<Vec<_>>();
+    if controllers.len() > factions.len() {
+        return Err(format!(
+            "the map has start areas for {} players, not {}",
+            factions.len(),
+            controllers.len()
+        ));
+    }
+    let clients = accept_clients(listener, config.remote_seats)?;
+
+    let seats = controllers
+        .iter()
+        .zip(factions)
+        .map(|(controller, faction)| (*controller, faction, None))
+        .collect::<Vec<_>>();
+    let settings = ControllerSettings::new(&TournamentConfig {
+        mcts_iterations: config.mcts_iterations,
+        ..TournamentConfig::default()
+    });
+    let mut game = HeadlessGame::set_up(
+        map,
+        &seats,
+        &settings,
+        config.rules.clone(),
+        rand::random(),
+    );
+    game.app
+        .add_plugins(RemotePlayerPlugin)
+        .insert_resource(RemotePlayerSettings {
+            timeout_ms: config.timeout_ms,
+        });
+    let players = game.players().to_vec();
+    let names = clients
+        .iter()
+        .map(|client| client.name.clone())
+        .chain(
+            config
+                .ai_seats
+                .iter()
+                .map(|controller| format!("{controller} AI")),
+        )
+        .collect::<Vec<_>>();
+    for (seat, mut client) in clients.into_iter().enumerate() {
+        let _ = client.send(&ServerMessage::Welcome {
+            version: PROTOCOL_VERSION,
+            seat,
+            player: entity_id(players[seat]),
+            faction: format!("{:?}", seats[seat].1),
+        });
+        game.app
+            .world_mut()
+            .entity_mut(players[seat])
+            .remove::<HeuristicAi>()
+            .insert((RemotePlayer, client));
+    }
+
+    game.start();
+    while !game.is_over(config.max_rounds) {
+        tell_remote_players(game.app.world_mut(), state_message);
+        game.play_round_with(wait_for_remote_players);
+    }
+
+    let scores = players
+        .iter()
+        .zip(names)
+        .enumerate()
+        .map(|(seat, (player, name))| SeatScore {
+            seat,
+            name,
+            score: game.score(*player),
+        })
+        .collect::<Vec<_>>();
+    tell_remote_players(game.app.world_mut(), state_message);
+    tell_remote_players(game.app.world_mut(), |_, _| ServerMessage::GameOver {
+        scores: scores.clone(),
+    });
+    Ok(scores)
+}
+
+/// Plays the seat the server hands out until the game is over. `on_message` sees everything
+/// the server sends, `choose` picks the move to make.
+pub fn run_client(
+    stream: TcpStream,
+    name: &str,
+    mut on_message: impl FnMut(&ServerMessage),
+    mut choose: impl FnMut(Option<&GameView>, &[MoveView]) -> MoveChoice,
+) -> Result<Vec<SeatScore>, String> {
+    let mut writer = stream.try_clone().map_err(|err| err.to_string())?;
+    let mut send = |message: &ClientMessage| -> Result<(), String> {
+        let line = serde_json::to_string(message).map_err(|err| err.to_string())?;
+        writeln!(writer, "{line}").map_err(|err| err.to_string())
+    };
+    send(&ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+        name: name.to_string(),
+    })?;
+
+    let mut view = None;
+    for line in BufReader::new(stream).lines() {
+        let line = line.map_err(|err| err.to_string())?;
+        let message = serde_json::from_str::<ServerMessage>(&line)
+            .map_err(|err| format!("could not read the message: {err}"))?;
+        on_message(&message);
+        match message {
+            ServerMessage::State { view: new_view, .. } => view = Some(new_view),
+            ServerMessage::MoveRequest { request_id, moves } => {
+                let choice = choose(view.as_ref(), &moves);
+                send(&ClientMessage::PlayMove { request_id, choice })?;
+            }
+            ServerMessage::Error {
+                request_id: None,
+                message,
+            } => return Err(message),
+            ServerMessage::GameOver { scores } => return Ok(scores),
+            ServerMessage::Welcome { .. } | ServerMessage::Error { .. } => {}
+        }
+    }
+    Err("the server closed the connection".to_string())
+}
+
+/// Cards typed like `Ochre 2, Salt`, a card without a number counts once.
+pub fn parse_card_list(typed: &str) -> Result<BTreeMap<String, usize>, String> {
+    let mut cards = BTreeMap::new();
+    for entry in typed.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (name, count) = entry
+            .rsplit_once(' ')
+            .and_then(|(name, count)| Some((name.trim(), count.parse::<usize>().ok()?)))
+            .unwrap_or((entry, 1));
+        let card = parse_trade_card(name)?;
+        *cards.entry(card.to_string()).or_default() += count;
+    }
+    Ok(cards)
+}
+
+fn area_name(view: Option<&GameView>, id: u64) -> String {
+    view.and_then(|view| view.areas.iter().find(|area| area.id == id))
+        .map_or_else(|| format!("area {id}"), |area| area.name.clone())
+}
+
+fn player_name(view: Option<&GameView>, id: u64) -> String {
+    view.and_then(|view| view.players.iter().find(|player| player.id == id))
+        .map_or_else(|| format!("player {id}"), |player| player.faction.clone())
+}
+
+fn describe_cards(cards: &BTreeMap<String, usize>) -> String {
+    cards
+        .iter()
+        .map(|(card, count)| format!("{count} {card}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_offer(view: Option<&GameView>, id: u64) -> String {
+    view.and_then(|view| view.offers.iter().find(|offer| offer.id == id))
+        .map_or_else(
+            || format!("offer {id}"),
+            |offer| {
+                format!(
+                    "the offer of {} from {} for {} from {}",
+                    offer.gives_count,
+                    player_name(view, offer.initiator),
+                    offer.asks_count,
+                    player_name(view, offer.receiver)
+                )
+            },
+        )
+}
+
+pub fn describe_action(action: &ActionView, view: Option<&GameView>) -> String {
+    match action {
+        ActionView::Expand { area, max_tokens } => {
+            format!("expand into {} (up to {max_tokens})", area_name(view, *area))
+        }
+        ActionView::Move {
+            source,
+            target,
+            max_tokens,
+        } => format!(
+            "move from {} to {} (up to {max_tokens})",
+            area_name(view, *source),
+            area_name(view, *target)
+        ),
+        ActionView::AttackArea {
+            source,
+            target,
+            max_tokens,
+        } => format!(
+            "attack {} from {} (up to {max_tokens})",
+            area_name(view, *target),
+            area_name(view, *source)
+        ),
+        ActionView::AttackCity {
+            source,
+            target,
+            max_tokens,
+        } => format!(
+            "attack the city in {} from {} (up to {max_tokens})",
+            area_name(view, *target),
+            area_name(view, *source)
+        ),
+        ActionView::EndMovement => "end movement".to_string(),
+        ActionView::ChooseAttacker {
+            area,
+            attacker,
+            attacker_tokens,
+        } => format!(
+            "fight {} first in {} ({attacker_tokens} tokens)",
+            player_name(view, *attacker),
+            area_name(view, *area)
+        ),
+        ActionView::BuildCity { area } => format!("build a city in {}", area_name(view, *area)),
+        ActionView::EndCityConstruction => "end city construction".to_string(),
+        ActionView::EliminateCity {
+            area,
+            tokens_gained,
+        } => format!(
+            "remove the city in {} ({tokens_gained} tokens back)",
+            area_name(view, *area)
+        ),
+        ActionView::ProposeTrade { receiver } => {
+            format!("offer a trade to {}", player_name(view, *receiver))
+        }
+        ActionView::AcceptOffer { offer } => format!("accept {}", describe_offer(view, *offer)),
+        ActionView::DeclineOffer { offer } => format!("decline {}", describe_offer(view, *offer)),
+        ActionView::WithdrawOffer { offer } => {
+            format!("withdraw {}", describe_offer(view, *offer))
+        }
+        ActionView::SettleTrade {
+            offer,
+            cards,
+            promised,
+        } => format!(
+            "hand over {cards} cards for {}, promised {}",
+            describe_offer(view, *offer),
+            describe_cards(promised)
+        ),
+        ActionView::BackOutOfTrade { offer } => {
+            format!("back out of {}", describe_offer(view, *offer))
+        }
+        ActionView::StopTrading => "stop trading".to_string(),
+    }
+}
+
+/// The board as text, for clients without graphics.
+pub fn render_view(view: &GameView) -> String {
+    let mut lines = vec![];
+    for player in view.players.iter() {
+        let mut line = format!(
+            "{} (player {}): {} tokens and {} cities in stock, {} cities, {} trade cards",
+            player.faction,
+            player.id,
+            player.tokens_in_stock,
+            player.cities_in_stock,
+            player.cities,
+            player.trade_cards
+        );
+        if let Some(hand) = player.hand.as_ref() {
+            line.push_str(&format!(" [{}]", describe_cards(hand)));
+        }
+        lines.push(line);
+    }
+    for area in view.areas.iter().filter(|area| !area.population.is_empty()) {
+        let population = area
+            .population
+            .iter()
+            .map(|tokens| format!("{} {}", tokens.tokens, player_name(Some(view), tokens.player)))
+            .collect::<Vec<_>>();
+        let city = area
+            .city_owner
+            .map(|owner| format!(", city of {}", player_name(Some(view), owner)))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{} (max {}){}: {}",
+            area.name,
+            area.max_population,
+            city,
+            population.join(", ")
+        ));
+    }
+    for offer in view.offers.iter() {
+        lines.push(format!(
+            "{}{}, promised {} for {}",
+            describe_offer(Some(view), offer.id),
+            if offer.accepted { " (accepted)" } else { "" },
+            describe_cards(&offer.gives),
+            describe_cards(&offer.asks)
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::map::map_plugin::Area;
+    use crate::civilization::concepts::rules::rules_resources::RuleSet;
+    use crate::civilization::enums::GameFaction;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn area(id: i32, connections: Vec<i32>, start_area: Option<GameFaction>) -> Area {
+        Area {
+            id,
+            x: 0.0,
+            y: 0.0,
+            max_population: 3,
+            land_connections: connections,
+            sea_connections: vec![],
+            start_area,
+            city_site: id % 2 == 0,
+            flood_plain: false,
+            city_flood: false,
+            volcano: false,
+        }
+    }
+
+    fn small_map() -> Map {
+        Map {
+            areas: vec![
+                area(1, vec![2], Some(GameFaction::Egypt)),
+                area(2, vec![1, 3], None),
+                area(3, vec![2, 4], None),
+                area(4, vec![3], Some(GameFaction::Babylon)),
+            ],
+        }
+    }
+
+    fn hand(cards: &[(TradeCard, usize)]) -> PlayerTradeCards {
+        let mut hand = PlayerTradeCards::default();
+        for (card, count) in cards {
+            hand.add_trade_cards(*card, *count);
+        }
+        hand
+    }
+
+    fn cards(cards: &[(TradeCard, usize)]) -> BTreeMap<String, usize> {
+        cards
+            .iter()
+            .map(|(card, count)| (card.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_view_only_shows_own_hand() {
+        let seats = [
+            (Controller::Random, GameFaction::Egypt, None),
+            (Controller::Random, GameFaction::Babylon, None),
+        ];
+        let mut game = HeadlessGame::set_up(
+            &small_map(),
+            &seats,
+            &ControllerSettings::new(&TournamentConfig::default()),
+            RuleSet::default(),
+            0,
+        );
+        let players = game.players().to_vec();
+        for player in players.iter() {
+            if let Some(mut hand) = game.app.world_mut().get_mut::<PlayerTradeCards>(*player) {
+                hand.add_trade_card(TradeCard::Ochre);
+            }
+        }
+
+        let view = view_for(game.app.world_mut(), players[1]);
+
+        let other = view
+            .players
+            .iter()
+            .find(|player| player.id == entity_id(players[0]))
+            .unwrap();
+        let me = view
+            .players
+            .iter()
+            .find(|player| player.id == entity_id(players[1]))
+            .unwrap();
+        assert_eq!(other.trade_cards, 1);
+        assert_eq!(other.hand, None);
+        assert_eq!(me.hand, Some(cards(&[(TradeCard::Ochre, 1)])));
+    }
+
+    #[test]
+    fn test_other_protocol_versions_are_turned_away() {
+        let hello = |version: u32| {
+            serde_json::to_string(&ClientMessage::Hello {
+                version,
+                name: "Ada".to_string(),
+            })
+            .unwrap()
+        };
+
+        assert_eq!(parse_hello(&hello(PROTOCOL_VERSION)), Ok("Ada".to_string()));
+        assert!(parse_hello(&hello(PROTOCOL_VERSION + 1)).is_err());
+        assert!(parse_hello(r#"{"type": "play_move", "request_id": 1, "move_id": 0}"#).is_err());
+    }
+
+    #[test]
+    fn test_offers_made_to_us_can_be_accepted_or_declined() {
+        let me = create_entity();
+        let other = create_entity();
+        let trade = create_entity();
+        let available_moves = AvailableMoves::new(HashMap::from([
+            (1, Move::Trade(TradeMove::AcceptOrDeclineTrade(trade))),
+            (2, Move::Trade(TradeMove::StopTrading)),
+        ]));
+        let offers = HashMap::from([(trade, TradeOffer::propose_trade(other, "", me, ""))]);
+
+        let moves = remote_moves(me, &available_moves, &offers);
+        let json = serde_json::to_value(move_views(
+            moves.into_iter().map(|(_, action)| action).collect(),
+        ))
+        .unwrap();
+
+        assert_eq!(json[0]["kind"], "accept_offer");
+        assert_eq!(json[0]["offer"], entity_id(trade));
+        assert_eq!(json[1]["kind"], "decline_offer");
+        assert_eq!(json[2]["id"], 2);
+        assert_eq!(json[2]["kind"], "stop_trading");
+    }
+
+    #[test]
+    fn test_offers_take_three_cards_each_way_from_the_hand() {
+        let offer = TradeOffer::propose_trade(create_entity(), "", create_entity(), "");
+        let my_hand = hand(&[(TradeCard::Ochre, 2), (TradeCard::Salt, 1)]);
+        let mut choice = MoveChoice {
+            gives: cards(&[(TradeCard::Ochre, 2), (TradeCard::Salt, 1)]),
+            asks: cards(&[(TradeCard::Iron, 3)]),
+            ..MoveChoice::new(0)
+        };
+
+        let built = build_offer(offer.clone(), &choice, &my_hand).unwrap();
+
+        assert_eq!(
+            built.initiator_pays_guaranteed,
+            HashMap::from([(TradeCard::Ochre, 2)])
+        );
+        choice.gives = cards(&[(TradeCard::Ochre, 3)]);
+        assert!(build_offer(offer.clone(), &choice, &my_hand).is_err());
+        choice.gives = cards(&[(TradeCard::Ochre, 2)]);
+        assert!(build_offer(offer, &choice, &my_hand).is_err());
+    }
+
+    #[test]
+    fn test_settling_hands_over_the_promised_cards() {
+        let me = create_entity();
+        let trade = create_entity();
+        let mut offer = TradeOffer::propose_trade(me, "", create_entity(), "");
+        offer.pay_even_more(TradeCard::Ochre, 3);
+        let my_hand = hand(&[(TradeCard::Ochre, 2), (TradeCard::Salt, 2)]);
+        let mut choice = MoveChoice {
+            gives: cards(&[(TradeCard::Salt, 3)]),
+            ..MoveChoice::new(0)
+        };
+
+        assert!(settlement_cards(me, trade, &offer, &choice, &my_hand).is_err());
+
+        choice.gives = cards(&[(TradeCard::Ochre, 2), (TradeCard::Salt, 1)]);
+        let paid = settlement_cards(me, trade, &offer, &choice, &my_hand).unwrap();
+
+        assert_eq!(
+            paid,
+            HashMap::from([(TradeCard::Ochre, 2), (TradeCard::Salt, 1)])
+        );
+    }
+
+    #[test]
+    fn test_typed_cards_count_once_without_a_number() {
+        assert_eq!(
+            parse_card_list("ochre 2, Salt"),
+            Ok(cards(&[(TradeCard::Ochre, 2), (TradeCard::Salt, 1)]))
+        );
+        assert!(parse_card_list("Sand").is_err());
+    }
+
+    #[test]
+    fn test_loopback_clients_play_a_game() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let config = ServerConfig {
+            remote_seats: 2,
+            ai_seats: vec![],
+            max_rounds: 2,
+            timeout_ms: 5_000,
+            ..ServerConfig::default()
+        };
+        let server = std::thread::spawn(move || run_server(&listener, &small_map(), &config));
+
+        let clients = ["Ada", "Grace"].map(|name| {
+            std::thread::spawn(move || {
+                let stream = TcpStream::connect(address).unwrap();
+                let mut me = None;
+                let mut saw_other_hand = false;
+                let mut errors = 0;
+                let scores = run_client(
+                    stream,
+                    name,
+                    |message| match message {
+                        ServerMessage::Welcome { player, .. } => me = Some(*player),
+                        ServerMessage::State { view, .. } => {
+                            saw_other_hand |= view
+                                .players
+                                .iter()
+                                .any(|player| Some(player.id) != me && player.hand.is_some());
+                        }
+                        ServerMessage::Error { .. } => errors += 1,
+                        _ => {}
+                    },
+                    |_, moves| MoveChoice::new(moves.len() - 1),
+                );
+                (scores, saw_other_hand, errors)
+            })
+        });
+
+        let server_scores = server.join().unwrap().unwrap();
+        for client in clients {
+            let (scores, saw_other_hand, errors) = client.join().unwrap();
+            assert_eq!(scores.unwrap(), server_scores);
+            assert!(!saw_other_hand);
+            assert_eq!(errors, 0);
+        }
+        assert_eq!(server_scores.len(), 2);
+        assert!(server_scores.iter().all(|seat_score| seat_score.score > 0.0));
+    }
+}
@@ -0,0 +1,23 @@
+use crate::network::prelude::*;
+use crate::GameState;
+use bevy::app::{Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs};
+
+pub struct RemotePlayerPlugin;
+
+impl Plugin for RemotePlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RemotePlayer>()
+            .register_type::<RemotePlayerSettings>()
+            .init_resource::<RemotePlayerSettings>()
+            .add_message::<SelectRemoteMove>()
+            .add_systems(
+                Update,
+                (request_remote_moves, receive_remote_moves)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_observer(on_add_available_moves_for_remote_player)
+            .add_observer(on_remove_available_moves_for_remote_player);
+    }
+}
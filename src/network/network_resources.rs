@@ -0,0 +1,13 @@
+use bevy::prelude::{Reflect, Resource};
+
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct RemotePlayerSettings {
+    /// Time a remote player has to answer before an AI moves for it
+    pub timeout_ms: u64,
+}
+
+impl Default for RemotePlayerSettings {
+    fn default() -> Self {
+        RemotePlayerSettings { timeout_ms: 60_000 }
+    }
+}
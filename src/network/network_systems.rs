@@ -0,0 +1,359 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::*;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::trade::trade_components::{
+    CanTrade, NeedsTradeMove, PublishedOffer, TradeOffer,
+};
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::civilization::game_moves::game_moves_systems::GameMoveWriters;
+use crate::external_bot::external_bot_functions::entity_id;
+use crate::external_bot::external_bot_systems::BotFallback;
+use crate::network::network_components::{
+    AreaView, ClientConnection, GameView, MoveChoice, OfferView, PendingRemoteMove, PlayerTokens,
+    PlayerView, RemoteMove, ServerMessage,
+};
+use crate::network::network_events::SelectRemoteMove;
+use crate::network::network_functions::{
+    build_offer, card_names, move_views, read_choice, remote_moves, settlement_cards, tokens_for,
+};
+use crate::network::network_resources::RemotePlayerSettings;
+use crate::GameActivity;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{
+    debug, warn, Commands, Entity, Has, Local, MessageReader, Name, Query, Res, State,
+};
+use std::time::Duration;
+
+/// Everything a remote player or an external bot gets to see of the game. Other players' trade cards are only
+/// counted and only the promised cards of an offer are shown.
+#[derive(SystemParam)]
+pub struct RemoteObservation<'w, 's> {
+    areas: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GameArea,
+            &'static Name,
+            &'static LandPassage,
+            Has<CitySite>,
+            Option<&'static BuiltCity>,
+        ),
+    >,
+    players: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Faction,
+            &'static TokenStock,
+            &'static CityTokenStock,
+            &'static PlayerCities,
+            &'static PlayerTradeCards,
+        ),
+    >,
+    offers: Query<'w, 's, (Entity, &'static TradeOffer)>,
+    populations: Populations<'w, 's>,
+    game_info: Res<'w, GameInfoAndStuff>,
+    phase: Res<'w, State<GameActivity>>,
+}
+
+impl RemoteObservation<'_, '_> {
+    pub fn phase(&self) -> &GameActivity {
+        self.phase.get()
+    }
+
+    pub fn offers(&self) -> HashMap<Entity, TradeOffer> {
+        self.offers
+            .iter()
+            .map(|(entity, offer)| (entity, offer.clone()))
+            .collect()
+    }
+
+    pub fn view(&self, me: Entity) -> GameView {
+        let mut areas = self
+            .areas
+            .iter()
+            .map(|(area, game_area, name, passage, city_site, built_city)| {
+                let population = self.populations.get(area).unwrap_or_default();
+                let mut tokens = population
+                    .player_tokens()
+                    .iter()
+                    .map(|(player, tokens)| PlayerTokens {
+                        player: entity_id(*player),
+                        tokens: tokens.len(),
+                    })
+                    .collect::<Vec<_>>();
+                tokens.sort_by_key(|tokens| tokens.player);
+                AreaView {
+                    id: entity_id(area),
+                    map_id: game_area.id,
+                    name: name.to_string(),
+                    max_population: population.max_population,
+                    city_site,
+                    city_owner: built_city.map(|city| entity_id(city.player)),
+                    population: tokens,
+                    connections: passage
+                        .to_areas
+                        .iter()
+                        .map(|area| entity_id(*area))
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        areas.sort_by_key(|area| area.id);
+
+        let mut players = self
+            .players
+            .iter()
+            .map(
+                |(player, faction, token_stock, city_stock, cities, trade_cards)| PlayerView {
+                    id: entity_id(player),
+                    faction: format!("{:?}", faction.faction),
+                    tokens_in_stock: token_stock.tokens_in_stock(),
+                    cities_in_stock: city_stock.tokens_in_stock(),
+                    cities: cities.number_of_cities(),
+                    trade_cards: trade_cards.number_of_trade_cards(),
+                    hand: (player == me).then(|| card_names(trade_cards.cards())),
+                },
+            )
+            .collect::<Vec<_>>();
+        players.sort_by_key(|player| player.id);
+
+        let mut offers = self
+            .offers
+            .iter()
+            .filter(|(_, offer)| offer.am_i_involved_in_this_trade(me))
+            .map(|(trade, offer)| OfferView {
+                id: entity_id(trade),
+                initiator: entity_id(offer.initiator),
+                receiver: entity_id(offer.receiver),
+                gives: card_names(&offer.initiator_pays_guaranteed),
+                gives_count: offer.pays_number_of_cards(),
+                asks: card_names(&offer.initiator_gets_guaranteed),
+                asks_count: offer.gets_number_of_cards(),
+                accepted: offer.trade_accepted(),
+            })
+            .collect::<Vec<_>>();
+        offers.sort_by_key(|offer| offer.id);
+
+        GameView {
+            areas,
+            players,
+            offers,
+        }
+    }
+
+    pub fn state_message(&self, me: Entity) -> ServerMessage {
+        ServerMessage::State {
+            round: self.game_info.round,
+            phase: format!("{:?}", self.phase.get()),
+            view: self.view(me),
+        }
+    }
+}
+
+/// Plays the moves of remote players and external bots, trade moves the way the hot seat plays
+/// the humans' clicks.
+#[derive(SystemParam)]
+pub struct RemoteTrading<'w, 's> {
+    names: Query<'w, 's, &'static Name>,
+    offers: Query<'w, 's, &'static mut TradeOffer>,
+    commands: Commands<'w, 's>,
+}
+
+impl RemoteTrading<'_, '_> {
+    fn name_of(&self, entity: Entity) -> String {
+        self.names
+            .get(entity)
+            .map(|name| name.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn play(
+        &mut self,
+        player: Entity,
+        remote_move: &RemoteMove,
+        choice: &MoveChoice,
+        hand: &PlayerTradeCards,
+        writers: &mut GameMoveWriters,
+    ) -> Result<(), String> {
+        match remote_move {
+            RemoteMove::Play(game_move) => {
+                let tokens = tokens_for(game_move, choice.tokens)?;
+                writers.write_move(player, game_move, tokens);
+                return Ok(());
+            }
+            RemoteMove::StopTrading => {
+                self.commands.entity(player).remove::<CanTrade>();
+                return Ok(());
+            }
+            RemoteMove::ProposeTrade(receiver) => {
+                let offer = TradeOffer::propose_trade(
+                    player,
+                    self.name_of(player),
+                    *receiver,
+                    self.name_of(*receiver),
+                );
+                let offer = build_offer(offer, choice, hand)?;
+                self.commands.spawn((offer, PublishedOffer));
+            }
+            RemoteMove::AcceptOffer(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.accept(&player);
+                }
+            }
+            RemoteMove::DeclineOffer(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.reject(player);
+                }
+            }
+            RemoteMove::WithdrawOffer(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.withdraw(player);
+                }
+            }
+            RemoteMove::SettleTrade(trade) => {
+                let mut offer = self
+                    .offers
+                    .get_mut(*trade)
+                    .map_err(|_| "the trade has been called off".to_string())?;
+                let cards = settlement_cards(player, *trade, &offer, choice, hand)?;
+                offer.settle(player, cards);
+            }
+            RemoteMove::BackOutOfTrade(trade) => {
+                if let Ok(mut offer) = self.offers.get_mut(*trade) {
+                    offer.reject(player);
+                    offer.withdraw(player);
+                }
+            }
+        }
+        // The next trade move is asked for right away instead of after the countdown
+        self.commands
+            .entity(player)
+            .remove::<NeedsTradeMove>()
+            .insert(NeedsTradeMove);
+        Ok(())
+    }
+}
+
+/// Sends the board and the moves to the client of every remote player that has to move.
+pub fn request_remote_moves(
+    mut event_reader: MessageReader<SelectRemoteMove>,
+    mut players: Query<(&AvailableMoves, Option<&mut ClientConnection>)>,
+    observation: RemoteObservation,
+    mut fallback: BotFallback,
+    mut next_request_id: Local<u64>,
+    mut commands: Commands,
+) {
+    for event in event_reader.read() {
+        let Ok((available_moves, connection)) = players.get_mut(event.player) else {
+            continue;
+        };
+        let Some(mut connection) = connection else {
+            fallback.hand_over(event.player, observation.phase());
+            continue;
+        };
+        let (moves, actions): (Vec<_>, Vec<_>) =
+            remote_moves(event.player, available_moves, &observation.offers())
+                .into_iter()
+                .unzip();
+        if moves.is_empty() {
+            fallback.hand_over(event.player, observation.phase());
+            continue;
+        }
+        *next_request_id += 1;
+        let request = ServerMessage::MoveRequest {
+            request_id: *next_request_id,
+            moves: move_views(actions),
+        };
+        let sent = connection
+            .send(&observation.state_message(event.player))
+            .and_then(|()| connection.send(&request));
+        match sent {
+            Ok(()) => {
+                commands
+                    .entity(event.player)
+                    .insert(PendingRemoteMove::new(*next_request_id, moves));
+            }
+            Err(err) => {
+                warn!("{} stopped listening: {}", connection.name, err);
+                commands.entity(event.player).remove::<ClientConnection>();
+                fallback.hand_over(event.player, observation.phase());
+            }
+        }
+    }
+}
+
+/// Plays the moves remote players answer with. Bad answers are reported back and the client
+/// may try again until its time is up, after which an AI moves instead. Players whose client
+/// hung up are played by the AIs from then on.
+pub fn receive_remote_moves(
+    mut players: Query<(
+        Entity,
+        &mut ClientConnection,
+        &PendingRemoteMove,
+        &PlayerTradeCards,
+    )>,
+    mut writers: GameMoveWriters,
+    mut trading: RemoteTrading,
+    mut fallback: BotFallback,
+    phase: Res<State<GameActivity>>,
+    settings: Res<RemotePlayerSettings>,
+) {
+    for (player, mut connection, pending, hand) in players.iter_mut() {
+        let mut answered = false;
+        loop {
+            let line = match connection.next_reply() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    warn!("{} left the game: {}", connection.name, err);
+                    trading.commands.entity(player).remove::<ClientConnection>();
+                    fallback.hand_over(player, phase.get());
+                    answered = true;
+                    break;
+                }
+            };
+            let played = read_choice(&line, pending.request_id).and_then(|choice| {
+                let remote_move = pending
+                    .moves
+                    .get(choice.move_id)
+                    .ok_or_else(|| format!("there is no move with id {}", choice.move_id))?;
+                trading.play(player, remote_move, &choice, hand, &mut writers)
+            });
+            match played {
+                Ok(()) => {
+                    answered = true;
+                    break;
+                }
+                Err(message) => {
+                    debug!("{} made a mistake: {}", connection.name, message);
+                    let _ = connection.send(&ServerMessage::Error {
+                        request_id: Some(pending.request_id),
+                        message,
+                    });
+                }
+            }
+        }
+
+        if !answered && pending.sent_at.elapsed() > Duration::from_millis(settings.timeout_ms) {
+            warn!("{} did not answer in time", connection.name);
+            let _ = connection.send(&ServerMessage::Error {
+                request_id: Some(pending.request_id),
+                message: "timed out, the AI moves for you".to_string(),
+            });
+            fallback.hand_over(player, phase.get());
+            answered = true;
+        }
+
+        if answered {
+            trading
+                .commands
+                .entity(player)
+                .remove::<PendingRemoteMove>();
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::civilization::game_moves::game_moves_components::AvailableMoves;
+use crate::network::network_components::{PendingRemoteMove, RemotePlayer};
+use crate::network::network_events::SelectRemoteMove;
+use bevy::prelude::{Add, Commands, MessageWriter, On, Query, Remove, With};
+
+/// Remote players are people, the spectator controls do not hold their moves back.
+pub fn on_add_available_moves_for_remote_player(
+    trigger: On<Add, AvailableMoves>,
+    is_remote_player: Query<&RemotePlayer>,
+    mut event_writer: MessageWriter<SelectRemoteMove>,
+) {
+    if is_remote_player.contains(trigger.event().entity) {
+        event_writer.write(SelectRemoteMove::new(trigger.event().entity));
+    }
+}
+
+/// An answer to moves that are gone can no longer be played.
+pub fn on_remove_available_moves_for_remote_player(
+    trigger: On<Remove, AvailableMoves>,
+    pending: Query<(), With<PendingRemoteMove>>,
+    mut commands: Commands,
+) {
+    if pending.contains(trigger.event().entity) {
+        commands
+            .entity(trigger.event().entity)
+            .try_remove::<PendingRemoteMove>();
+    }
+}
@@ -1,49 +1,5 @@
-use crate::GameState;
-use crate::actions::Actions;
-use crate::loading::TextureAssets;
 use bevy::prelude::*;
 
-pub struct PlayerPlugin;
-
+/// Marks an entity as one of the players (human or AI) taking part in the game.
 #[derive(Component)]
 pub struct Player;
-
-/// This plugin handles player related stuff like movement
-/// Player logic is only active during the State `GameState::Playing`
-impl Plugin for PlayerPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), spawn_player)
-            .add_systems(Update, move_player.run_if(in_state(GameState::Playing)));
-    }
-}
-
-fn spawn_player(mut commands: Commands, textures: Res<TextureAssets>) {
-    commands
-        .spawn((
-            Sprite {
-                image: textures.bevy.clone(),
-                ..Default::default()
-            },
-            Transform::from_translation(Vec3::new(0., 0., 1.)),
-        ))
-        .insert(Player);
-}
-
-fn move_player(
-    time: Res<Time>,
-    actions: Res<Actions>,
-    mut player_query: Query<&mut Transform, With<Player>>,
-) {
-    if actions.player_movement.is_none() {
-        return;
-    }
-    let speed = 150.;
-    let movement = Vec3::new(
-        actions.player_movement.unwrap().x * speed * time.delta_secs(),
-        actions.player_movement.unwrap().y * speed * time.delta_secs(),
-        0.,
-    );
-    for mut player_transform in &mut player_query {
-        player_transform.translation += movement;
-    }
-}
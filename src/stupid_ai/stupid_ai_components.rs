@@ -1,5 +1,7 @@
 use bevy::prelude::{Component, Reflect};
 
+/// Picks its board moves at random, so an AiPersonality only shows in its trading, which the
+/// trade AI does for it.
 #[derive(Component, Debug, Reflect)]
 pub struct StupidAi;
 
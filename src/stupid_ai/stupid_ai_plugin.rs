@@ -15,6 +15,7 @@ impl Plugin for StupidAiPlugin {
                     setup_stupid_ai.run_if(in_state(GameState::Playing)),
                     select_stupid_pop_exp.run_if(in_state(GameActivity::PopulationExpansion)),
                     select_stupid_movement.run_if(in_state(GameActivity::Movement)),
+                    select_stupid_attacker.run_if(in_state(GameActivity::Conflict)),
                     select_stupid_city_building.run_if(in_state(GameActivity::CityConstruction)),
                     select_stupid_city_elimination.run_if(in_state(GameActivity::CheckCitySupport)),
                     select_stupid_trade_move.run_if(in_state(GameActivity::Trade)),
@@ -1,3 +1,4 @@
+use crate::civilization::concepts::spectator::spectator_systems::SpectatorGate;
 use crate::civilization::game_moves::game_moves_components::AvailableMoves;
 use crate::stupid_ai::stupid_ai_components::StupidAi;
 use crate::stupid_ai::stupid_ai_events::SelectStupidMove;
@@ -7,8 +8,9 @@ pub fn on_add_available_moves(
     trigger: On<Add, AvailableMoves>,
     is_stupid_ai: Query<&StupidAi>,
     mut event_writer: MessageWriter<SelectStupidMove>,
+    mut gate: SpectatorGate,
 ) {
-    if is_stupid_ai.contains(trigger.event().entity) {
+    if is_stupid_ai.contains(trigger.event().entity) && !gate.hold_back(trigger.event().entity) {
         // //debug!("Stupid AI detected");
         event_writer.write(SelectStupidMove::new(trigger.event().entity));
     } else {
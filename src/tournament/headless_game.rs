@@ -0,0 +1,298 @@
+use crate::civilization::components::population::Populations;
+use crate::civilization::components::{BuiltCity, CityTokenStock, PlayerCities};
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::census::census_resources::GameInfoAndStuff;
+use crate::civilization::concepts::map::map_plugin::{spawn_area, Area, AvailableFactions, Map};
+use crate::civilization::concepts::rules::rules_resources::{GameRng, RuleSet};
+use crate::civilization::concepts::spectator::spectator_resources::SpectatorControl;
+use crate::civilization::concepts::token_animation::token_animation_resources::TokenAnimationSettings;
+use crate::civilization::concepts::trade::trade_events::SendTradingCardsCommand;
+use crate::civilization::concepts::trade::trade_plugin::TradePlugin;
+use crate::civilization::enums::{AiDifficulty, GameFaction};
+use crate::civilization::events::MoveTokensFromStockToAreaCommand;
+use crate::civilization::general_systems::spawn_player;
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::civilization::plugins::game_rules_plugin::GameRulesPlugin;
+use crate::civilization::ui::ui_builder::UiBuilderDefaults;
+use crate::heuristic_ai::prelude::{HeuristicAi, HeuristicAiPlugin};
+use crate::mcts_ai::prelude::{MctsAi, MctsAiPlugin};
+use crate::stupid_ai::prelude::{StupidAi, StupidAiPlugin};
+use crate::tournament::tournament_components::Controller;
+use crate::tournament::tournament_functions::{ControllerSettings, CITY_SCORE};
+use crate::trade_ai::prelude::TradeAiPlugin;
+use crate::{GameActivity, GameState};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::{
+    default, Add, App, AppExtStates, Commands, Entity, Handle, In, MessageReader, MinimalPlugins,
+    NextState, On, Query, ResMut, Resource, Update,
+};
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+/// Every frame advances the clock this much, so the countdown before the AIs make their trade
+/// moves runs out after a few frames.
+const FRAME_TIME: Duration = Duration::from_millis(250);
+/// Frames a round may take before the game is considered stuck.
+const MAX_FRAMES_PER_ROUND: usize = 20_000;
+
+/// Cities built and trades made during the game, by player.
+#[derive(Resource, Default)]
+pub struct GameTally {
+    pub cities_built: HashMap<Entity, usize>,
+    pub trades_made: HashMap<Entity, usize>,
+}
+
+/// A game without graphics running the rule plugins, played by the AI plugins the way they play
+/// in the game itself.
+pub struct HeadlessGame {
+    pub app: App,
+    /// The players in seat order.
+    players: Vec<Entity>,
+    stalled: bool,
+}
+
+impl HeadlessGame {
+    /// Sets the board up from the map with every seat's first token in its start area and
+    /// starts the first round. Games set up from the same seed play out the same.
+    pub fn new(
+        map: &Map,
+        seats: &[(Controller, GameFaction, Option<AiPersonality>)],
+        settings: &ControllerSettings,
+        rules: RuleSet,
+        seed: u64,
+    ) -> Self {
+        let mut game = Self::set_up(map, seats, settings, rules, seed);
+        game.start();
+        game
+    }
+
+    /// Sets the board up without starting the first round, so seats can still be handed to
+    /// other controllers.
+    pub fn set_up(
+        map: &Map,
+        seats: &[(Controller, GameFaction, Option<AiPersonality>)],
+        settings: &ControllerSettings,
+        rules: RuleSet,
+        seed: u64,
+    ) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME))
+            .init_state::<GameState>()
+            .insert_resource(DebugOptions::new(
+                false,
+                GameFaction::Egypt,
+                false,
+                false,
+                false,
+                false,
+                false,
+                seats.len(),
+                AiDifficulty::Stupid,
+            ))
+            .insert_resource(TokenAnimationSettings {
+                skip_animations: true,
+                ..default()
+            })
+            .insert_resource(settings.heuristic.clone())
+            .insert_resource(settings.mcts.clone())
+            .insert_resource(settings.trade.clone())
+            .insert_resource(rules)
+            .insert_resource(GameRng::new(seed))
+            .init_resource::<AvailableFactions>()
+            .init_resource::<UiBuilderDefaults>()
+            .init_resource::<SpectatorControl>()
+            .init_resource::<GameTally>()
+            .add_plugins((
+                GameRulesPlugin,
+                TradePlugin,
+                StupidAiPlugin,
+                HeuristicAiPlugin,
+                MctsAiPlugin,
+                TradeAiPlugin,
+            ))
+            .add_systems(Update, count_trades)
+            .add_observer(count_built_city);
+
+        let areas = map
+            .areas
+            .iter()
+            .map(|area| {
+                let entity = app
+                    .world_mut()
+                    .run_system_once_with(
+                        |In(area): In<Area>, mut commands: Commands| {
+                            spawn_area(&mut commands, &area, "Tournament".to_string())
+                        },
+                        area.clone(),
+                    )
+                    .unwrap();
+                (area.id, entity)
+            })
+            .collect::<HashMap<_, _>>();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+
+        let mut players = vec![];
+        for (n, (controller, faction, personality)) in seats.iter().enumerate() {
+            let mut factions = app.world_mut().resource_mut::<AvailableFactions>();
+            factions.faction_icons.insert(*faction, Handle::default());
+            factions
+                .faction_city_icons
+                .insert(*faction, Handle::default());
+            let player = app
+                .world_mut()
+                .run_system_once_with(
+                    |In((faction, n, controller, personality)): In<(
+                        GameFaction,
+                        usize,
+                        Controller,
+                        Option<AiPersonality>,
+                    )>,
+                     mut commands: Commands| {
+                        let player = spawn_player(&mut commands, faction, n, 0);
+                        match controller {
+                            Controller::Random => commands.entity(player).insert(StupidAi),
+                            Controller::Heuristic => commands.entity(player).insert(HeuristicAi),
+                            Controller::Mcts => commands.entity(player).insert(MctsAi),
+                        };
+                        if let Some(personality) = personality {
+                            commands.entity(player).insert(personality);
+                        }
+                        player
+                    },
+                    (*faction, n + 1, *controller, personality.clone()),
+                )
+                .unwrap();
+            if let Some(area_entity) = map
+                .areas
+                .iter()
+                .find(|area| area.start_area == Some(*faction))
+                .map(|area| areas[&area.id])
+            {
+                app.world_mut()
+                    .write_message(MoveTokensFromStockToAreaCommand {
+                        area_entity,
+                        player_entity: player,
+                        number_of_tokens: 1,
+                    });
+            }
+            players.push(player);
+        }
+        app.update();
+        HeadlessGame {
+            app,
+            players,
+            stalled: false,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameActivity>>()
+            .set(GameActivity::PopulationExpansion);
+        self.app.update();
+    }
+
+    pub fn players(&self) -> &[Entity] {
+        &self.players
+    }
+
+    /// Rounds played to the end.
+    pub fn rounds(&self) -> usize {
+        self.app
+            .world()
+            .resource::<GameInfoAndStuff>()
+            .round
+            .saturating_sub(1)
+    }
+
+    /// True when a round did not end within the frame limit, the game cannot go on then.
+    pub fn stalled(&self) -> bool {
+        self.stalled
+    }
+
+    /// Runs the game until the next round starts.
+    pub fn play_round(&mut self) {
+        self.play_round_with(|_| {});
+    }
+
+    /// Runs the game until the next round starts, calling `before_frame` ahead of every frame.
+    pub fn play_round_with(&mut self, mut before_frame: impl FnMut(&mut App)) {
+        let rounds = self.rounds();
+        for _ in 0..MAX_FRAMES_PER_ROUND {
+            before_frame(&mut self.app);
+            self.app.update();
+            if self.rounds() > rounds {
+                return;
+            }
+        }
+        self.stalled = true;
+    }
+
+    /// The game ends after the last round, once a player has built all its cities or when it
+    /// got stuck.
+    pub fn is_over(&self, max_rounds: usize) -> bool {
+        self.stalled
+            || self.rounds() >= max_rounds
+            || self.players.iter().any(|player| {
+                self.app
+                    .world()
+                    .get::<CityTokenStock>(*player)
+                    .is_some_and(|stock| !stock.has_tokens())
+            })
+    }
+
+    pub fn population(&mut self, player: Entity) -> usize {
+        self.app
+            .world_mut()
+            .run_system_once_with(
+                |In(player): In<Entity>, populations: Populations| {
+                    populations.player_areas(player).total_population()
+                },
+                player,
+            )
+            .unwrap()
+    }
+
+    pub fn cities(&self, player: Entity) -> usize {
+        self.app
+            .world()
+            .get::<PlayerCities>(player)
+            .map_or(0, |cities| cities.number_of_cities())
+    }
+
+    pub fn score(&mut self, player: Entity) -> f32 {
+        self.population(player) as f32 + self.cities(player) as f32 * CITY_SCORE
+    }
+
+    pub fn tally(&self) -> &GameTally {
+        self.app.world().resource::<GameTally>()
+    }
+}
+
+fn count_built_city(
+    trigger: On<Add, BuiltCity>,
+    cities: Query<&BuiltCity>,
+    mut tally: ResMut<GameTally>,
+) {
+    if let Ok(built_city) = cities.get(trigger.event().entity) {
+        *tally.cities_built.entry(built_city.player).or_default() += 1;
+    }
+}
+
+/// Both sides send their cards when a trade is settled, every sending counts as a trade made.
+fn count_trades(
+    mut sent_cards: MessageReader<SendTradingCardsCommand>,
+    mut tally: ResMut<GameTally>,
+) {
+    for sent in sent_cards.read() {
+        *tally.trades_made.entry(sent.sending_player).or_default() += 1;
+    }
+}
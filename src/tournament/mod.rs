@@ -0,0 +1,9 @@
+pub mod headless_game;
+pub mod tournament_components;
+pub mod tournament_functions;
+
+pub mod prelude {
+    pub use super::headless_game::*;
+    pub use super::tournament_components::*;
+    pub use super::tournament_functions::*;
+}
@@ -0,0 +1,112 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::civilization::enums::GameFaction;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The AIs that can take part in a tournament.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub enum Controller {
+    /// Picks any legal move at random, like the stupid AI
+    Random,
+    /// Picks the move the heuristic AI's board evaluation likes best
+    Heuristic,
+    /// Searches for the move with the MCTS AI
+    Mcts,
+}
+
+impl FromStr for Controller {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "random" | "stupid" => Ok(Controller::Random),
+            "heuristic" => Ok(Controller::Heuristic),
+            "mcts" | "hard" => Ok(Controller::Mcts),
+            _ => Err(format!("unknown controller {s}")),
+        }
+    }
+}
+
+impl Display for Controller {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TournamentConfig {
+    /// One seat per entry, every game is played by all of them
+    pub controllers: Vec<Controller>,
+    pub games: usize,
+    pub threads: usize,
+    /// Games end after this many rounds unless someone built all their cities before that
+    pub max_rounds: usize,
+    pub mcts_iterations: usize,
+    pub rules: RuleSet,
+    /// Seat n plays with entry n, wrapping around. Without any every seat plays balanced
+    pub personalities: Vec<AiPersonality>,
+    /// Game n is played from this seed plus n
+    pub seed: u64,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        TournamentConfig {
+            controllers: vec![Controller::Random, Controller::Heuristic, Controller::Mcts],
+            games: 10,
+            threads: std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+            max_rounds: 20,
+            mcts_iterations: 300,
+            rules: RuleSet::default(),
+            personalities: vec![],
+            seed: rand::random(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PlayerResult {
+    pub seat: usize,
+    pub controller: Controller,
+    pub faction: GameFaction,
+    pub personality: Option<String>,
+    pub score: f32,
+    pub cities: usize,
+    pub cities_built: usize,
+    pub population: usize,
+    pub trades_made: usize,
+    pub winner: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GameResult {
+    pub game: usize,
+    /// Playing the game again from this seed gives the same result
+    pub seed: u64,
+    pub rounds: usize,
+    /// A round never ended, the game was scored where it got stuck
+    pub stalled: bool,
+    pub players: Vec<PlayerResult>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ControllerStats {
+    pub controller: Controller,
+    pub elo: f32,
+    pub games: usize,
+    /// Shared wins count as a fraction
+    pub wins: f32,
+    pub average_score: f32,
+    pub average_cities_built: f32,
+    pub average_trades_made: f32,
+    pub average_game_length: f32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TournamentReport {
+    pub games: Vec<GameResult>,
+    pub controllers: Vec<ControllerStats>,
+}
@@ -0,0 +1,475 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::ai_personality::ai_personality_resources::AiPersonalities;
+use crate::civilization::concepts::map::map_plugin::Map;
+use crate::civilization::concepts::rules::rules_resources::RuleSet;
+use crate::civilization::console::console_functions::parse_rule_variant;
+use crate::civilization::enums::GameFaction;
+use crate::heuristic_ai::heuristic_ai_resources::HeuristicAiWeights;
+use crate::mcts_ai::mcts_ai_resources::MctsSettings;
+use crate::tournament::headless_game::HeadlessGame;
+use crate::tournament::tournament_components::{
+    Controller, ControllerStats, GameResult, PlayerResult, TournamentConfig, TournamentReport,
+};
+use crate::trade_ai::trade_ai_resources::TradeAiSettings;
+use bevy::platform::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A city scores as much as the six tokens it takes to build one on a city site.
+pub const CITY_SCORE: f32 = 6.0;
+pub const STARTING_ELO: f32 = 1500.0;
+pub const ELO_K: f32 = 32.0;
+
+/// How the controllers play, shared by every game of a tournament.
+pub struct ControllerSettings {
+    pub heuristic: HeuristicAiWeights,
+    pub mcts: MctsSettings,
+    pub trade: TradeAiSettings,
+}
+
+impl ControllerSettings {
+    pub fn new(config: &TournamentConfig) -> Self {
+        ControllerSettings {
+            heuristic: HeuristicAiWeights::default(),
+            // The iteration limit decides, so games play the same on fast and slow machines
+            mcts: MctsSettings {
+                iterations: config.mcts_iterations,
+                time_budget_ms: 60_000,
+                ..MctsSettings::default()
+            },
+            trade: TradeAiSettings::default(),
+        }
+    }
+}
+
+/// One of the rule presets by name, or a rules file.
+pub fn parse_rules(typed: &str) -> Result<RuleSet, String> {
+    if !typed.ends_with(".ron") {
+        return parse_rule_variant(typed).map(RuleSet::new);
+    }
+    let text =
+        std::fs::read_to_string(typed).map_err(|err| format!("could not read {typed}: {err}"))?;
+    RuleSet::from_ron(&text).map_err(|err| format!("could not parse {typed}: {err}"))
+}
+
+/// The named personalities out of a personalities file, in the order they are named.
+pub fn load_personalities(path: &Path, names: &[&str]) -> Result<Vec<AiPersonality>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    let personalities: AiPersonalities = ron::from_str(&text)
+        .map_err(|err| format!("could not parse {}: {err}", path.display()))?;
+    names
+        .iter()
+        .map(|name| {
+            personalities
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("there is no personality called {name}"))
+        })
+        .collect()
+}
+
+pub fn load_map(path: &Path) -> Result<Map, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    ron::from_str(&text).map_err(|err| format!("could not parse {}: {err}", path.display()))
+}
+
+/// The factions the map has start areas for, in a fixed order.
+pub fn map_factions(map: &Map) -> Vec<GameFaction> {
+    let mut factions = map
+        .areas
+        .iter()
+        .filter_map(|area| area.start_area)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    factions.sort_by_key(|faction| faction.to_string());
+    factions
+}
+
+/// Plays game number `game` of the tournament on the rule plugins, every seat played by its
+/// controller's AI plugin. Seats keep the order of the configured controllers and the factions
+/// rotate one step every game. The game is played from the tournament seed plus its number, so
+/// any game of a report can be played again.
+pub fn play_game(
+    map: &Map,
+    config: &TournamentConfig,
+    settings: &ControllerSettings,
+    game: usize,
+) -> GameResult {
+    let factions = map_factions(map);
+    let seats = config
+        .controllers
+        .iter()
+        .enumerate()
+        .map(|(seat, controller)| {
+            (
+                *controller,
+                factions[(seat + game) % factions.len()],
+                personality_for_seat(&config.personalities, seat),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let seed = config.seed.wrapping_add(game as u64);
+    let mut headless_game =
+        HeadlessGame::new(map, &seats, settings, config.rules.clone(), seed);
+    while !headless_game.is_over(config.max_rounds) {
+        headless_game.play_round();
+    }
+
+    let players = headless_game.players().to_vec();
+    let scores = players
+        .iter()
+        .map(|player| headless_game.score(*player))
+        .collect::<Vec<_>>();
+    let best = scores.iter().copied().fold(f32::MIN, f32::max);
+    GameResult {
+        game,
+        seed,
+        rounds: headless_game.rounds(),
+        stalled: headless_game.stalled(),
+        players: seats
+            .into_iter()
+            .zip(players)
+            .zip(scores)
+            .enumerate()
+            .map(|(index, (((controller, faction, personality), player), score))| {
+                let population = headless_game.population(player);
+                let tally = headless_game.tally();
+                PlayerResult {
+                    seat: index,
+                    controller,
+                    faction,
+                    personality: personality.map(|personality| personality.name),
+                    score,
+                    cities: headless_game.cities(player),
+                    cities_built: tally.cities_built.get(&player).copied().unwrap_or(0),
+                    population,
+                    trades_made: tally.trades_made.get(&player).copied().unwrap_or(0),
+                    winner: score == best,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn personality_for_seat(personalities: &[AiPersonality], seat: usize) -> Option<AiPersonality> {
+    if personalities.is_empty() {
+        None
+    } else {
+        Some(personalities[seat % personalities.len()].clone())
+    }
+}
+
+/// Rates a game as a match between every pair of seats, the higher score winning it. All
+/// pairs are rated against the ratings from before the game.
+pub fn update_elo(ratings: &mut HashMap<Controller, f32>, players: &[PlayerResult]) {
+    if players.len() < 2 {
+        return;
+    }
+    let k = ELO_K / (players.len() - 1) as f32;
+    let mut changes: HashMap<Controller, f32> = HashMap::default();
+    for a in players.iter() {
+        for b in players.iter() {
+            if std::ptr::eq(a, b) {
+                continue;
+            }
+            let rating_a = ratings.get(&a.controller).copied().unwrap_or(STARTING_ELO);
+            let rating_b = ratings.get(&b.controller).copied().unwrap_or(STARTING_ELO);
+            let expected = 1.0 / (1.0 + 10f32.powf((rating_b - rating_a) / 400.0));
+            let actual = if a.score > b.score {
+                1.0
+            } else if a.score < b.score {
+                0.0
+            } else {
+                0.5
+            };
+            *changes.entry(a.controller).or_default() += k * (actual - expected);
+        }
+    }
+    for (controller, change) in changes {
+        *ratings.entry(controller).or_insert(STARTING_ELO) += change;
+    }
+}
+
+pub fn controller_stats(games: &[GameResult]) -> Vec<ControllerStats> {
+    let mut ratings: HashMap<Controller, f32> = HashMap::default();
+    for game in games.iter() {
+        update_elo(&mut ratings, &game.players);
+    }
+
+    let mut controllers = vec![];
+    for game in games.iter() {
+        for player in game.players.iter() {
+            if !controllers.contains(&player.controller) {
+                controllers.push(player.controller);
+            }
+        }
+    }
+
+    controllers
+        .into_iter()
+        .map(|controller| {
+            let mut stats = ControllerStats {
+                controller,
+                elo: ratings.get(&controller).copied().unwrap_or(STARTING_ELO),
+                games: 0,
+                wins: 0.0,
+                average_score: 0.0,
+                average_cities_built: 0.0,
+                average_trades_made: 0.0,
+                average_game_length: 0.0,
+            };
+            let mut seats = 0;
+            for game in games.iter() {
+                let mut played = false;
+                let winners = game.players.iter().filter(|player| player.winner).count();
+                for player in game.players.iter().filter(|p| p.controller == controller) {
+                    played = true;
+                    seats += 1;
+                    if player.winner {
+                        stats.wins += 1.0 / winners as f32;
+                    }
+                    stats.average_score += player.score;
+                    stats.average_cities_built += player.cities_built as f32;
+                    stats.average_trades_made += player.trades_made as f32;
+                }
+                if played {
+                    stats.games += 1;
+                    stats.average_game_length += game.rounds as f32;
+                }
+            }
+            let seats = seats.max(1) as f32;
+            stats.average_score /= seats;
+            stats.average_cities_built /= seats;
+            stats.average_trades_made /= seats;
+            stats.average_game_length /= stats.games.max(1) as f32;
+            stats
+        })
+        .collect()
+}
+
+/// Plays all games of the tournament, spread over the configured number of threads.
+pub fn run_tournament(map: &Map, config: &TournamentConfig) -> Result<TournamentReport, String> {
+    let factions = map_factions(map);
+    if config.controllers.len() < 2 {
+        return Err("a tournament needs at least two controllers".to_string());
+    }
+    if config.controllers.len() > factions.len() {
+        return Err(format!(
+            "the map only has start areas for {} players",
+            factions.len()
+        ));
+    }
+
+    let settings = ControllerSettings::new(config);
+    let next_game = AtomicUsize::new(0);
+    let results = Mutex::new(vec![]);
+    std::thread::scope(|scope| {
+        for _ in 0..config.threads.max(1) {
+            scope.spawn(|| loop {
+                let game = next_game.fetch_add(1, Ordering::Relaxed);
+                if game >= config.games {
+                    break;
+                }
+                let result = play_game(map, config, &settings, game);
+                results.lock().unwrap().push(result);
+            });
+        }
+    });
+
+    let mut games = results.into_inner().unwrap();
+    games.sort_by_key(|game| game.game);
+    Ok(TournamentReport {
+        controllers: controller_stats(&games),
+        games,
+    })
+}
+
+pub fn report_csv(report: &TournamentReport) -> String {
+    let mut csv = String::from(
+        "game,seed,rounds,stalled,seat,controller,faction,personality,score,cities,cities_built,population,trades_made,winner\n",
+    );
+    for game in report.games.iter() {
+        for player in game.players.iter() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                game.game,
+                game.seed,
+                game.rounds,
+                game.stalled,
+                player.seat,
+                player.controller,
+                player.faction,
+                player.personality.as_deref().unwrap_or(""),
+                player.score,
+                player.cities,
+                player.cities_built,
+                player.population,
+                player.trades_made,
+                player.winner
+            ));
+        }
+    }
+    csv
+}
+
+pub fn report_json(report: &TournamentReport) -> String {
+    serde_json::to_string_pretty(report).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::civilization::concepts::map::map_plugin::Area;
+
+    fn area(id: i32, connections: Vec<i32>, start_area: Option<GameFaction>) -> Area {
+        Area {
+            id,
+            x: 0.0,
+            y: 0.0,
+            max_population: 3,
+            land_connections: connections,
+            sea_connections: vec![],
+            start_area,
+            city_site: id % 2 == 0,
+            flood_plain: false,
+            city_flood: false,
+            volcano: false,
+        }
+    }
+
+    fn small_map() -> Map {
+        Map {
+            areas: vec![
+                area(1, vec![2], Some(GameFaction::Egypt)),
+                area(2, vec![1, 3], None),
+                area(3, vec![2, 4], None),
+                area(4, vec![3], Some(GameFaction::Babylon)),
+            ],
+        }
+    }
+
+    fn result(controller: Controller, score: f32) -> PlayerResult {
+        PlayerResult {
+            seat: 0,
+            controller,
+            faction: GameFaction::Egypt,
+            personality: None,
+            score,
+            cities: 0,
+            cities_built: 0,
+            population: 0,
+            trades_made: 0,
+            winner: false,
+        }
+    }
+
+    #[test]
+    fn test_winner_gains_what_the_loser_loses() {
+        let mut ratings = HashMap::default();
+
+        update_elo(
+            &mut ratings,
+            &[
+                result(Controller::Heuristic, 10.0),
+                result(Controller::Random, 2.0),
+            ],
+        );
+
+        assert_eq!(ratings[&Controller::Heuristic], STARTING_ELO + ELO_K / 2.0);
+        assert_eq!(ratings[&Controller::Random], STARTING_ELO - ELO_K / 2.0);
+    }
+
+    #[test]
+    fn test_game_is_played_to_the_round_limit() {
+        let map = small_map();
+        let config = TournamentConfig {
+            controllers: vec![Controller::Random, Controller::Heuristic],
+            games: 1,
+            max_rounds: 3,
+            ..TournamentConfig::default()
+        };
+        let settings = ControllerSettings::new(&config);
+
+        let result = play_game(&map, &config, &settings, 0);
+
+        assert!(!result.stalled);
+        assert_eq!(result.rounds, 3);
+        assert!(result.players.iter().all(|player| player.population > 0));
+    }
+
+    #[test]
+    fn test_seats_play_the_personalities_in_turn() {
+        let map = small_map();
+        let config = TournamentConfig {
+            controllers: vec![Controller::Heuristic; 2],
+            max_rounds: 1,
+            personalities: vec![
+                AiPersonality::new("aggressive", 1.0, 1.0, 1.0, 1.0),
+                AiPersonality::new("trader", 1.0, 1.0, 1.0, 1.0),
+            ],
+            ..TournamentConfig::default()
+        };
+        let settings = ControllerSettings::new(&config);
+
+        let result = play_game(&map, &config, &settings, 0);
+
+        assert_eq!(result.players[0].personality.as_deref(), Some("aggressive"));
+        assert_eq!(result.players[1].personality.as_deref(), Some("trader"));
+    }
+
+    #[test]
+    fn test_factions_rotate_between_games() {
+        let map = small_map();
+        let config = TournamentConfig {
+            controllers: vec![Controller::Random, Controller::Random],
+            max_rounds: 1,
+            ..TournamentConfig::default()
+        };
+        let settings = ControllerSettings::new(&config);
+
+        let first = play_game(&map, &config, &settings, 0);
+        let second = play_game(&map, &config, &settings, 1);
+
+        assert_eq!(first.players[0].faction, second.players[1].faction);
+    }
+
+    #[test]
+    fn test_game_plays_the_same_from_the_same_seed() {
+        let map = small_map();
+        let config = TournamentConfig {
+            controllers: vec![Controller::Random, Controller::Random],
+            max_rounds: 4,
+            seed: 7,
+            ..TournamentConfig::default()
+        };
+        let settings = ControllerSettings::new(&config);
+
+        let first = play_game(&map, &config, &settings, 1);
+        let second = play_game(&map, &config, &settings, 1);
+
+        assert_eq!(first.seed, 8);
+        assert_eq!(report_json(&report(first)), report_json(&report(second)));
+    }
+
+    fn report(game: GameResult) -> TournamentReport {
+        TournamentReport {
+            games: vec![game],
+            controllers: vec![],
+        }
+    }
+
+    #[test]
+    fn test_tournament_needs_start_areas_for_everyone() {
+        let config = TournamentConfig {
+            controllers: vec![Controller::Random; 3],
+            ..TournamentConfig::default()
+        };
+
+        assert!(run_tournament(&small_map(), &config).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+pub mod trade_ai_components;
+pub mod trade_ai_events;
+pub mod trade_ai_functions;
+pub mod trade_ai_plugin;
+pub mod trade_ai_resources;
+pub mod trade_ai_systems;
+
+pub mod prelude {
+    pub use super::trade_ai_components::*;
+    pub use super::trade_ai_events::*;
+    pub use super::trade_ai_plugin::*;
+    pub use super::trade_ai_resources::*;
+    pub use super::trade_ai_systems::*;
+}
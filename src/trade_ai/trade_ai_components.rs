@@ -0,0 +1,11 @@
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{Component, Entity, Reflect};
+
+/// What an AI remembers about its trading during the current trade phase.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+pub struct TradeAiMemory {
+    /// Players we have made an offer to
+    pub proposed_to: HashSet<Entity>,
+    /// Our offers that were still unanswered on our last move
+    pub unanswered_offers: HashSet<Entity>,
+}
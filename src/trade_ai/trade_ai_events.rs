@@ -0,0 +1,12 @@
+use bevy::prelude::{Entity, Message, Reflect};
+
+#[derive(Message, Debug, Reflect)]
+pub struct SelectTradeMove {
+    pub player: Entity,
+}
+
+impl SelectTradeMove {
+    pub fn new(player: Entity) -> Self {
+        SelectTradeMove { player }
+    }
+}
@@ -0,0 +1,289 @@
+use crate::civilization::concepts::acquire_trade_cards::trade_card_enums::{
+    TradeCard, TradeCardTrait,
+};
+use crate::civilization::concepts::trade::trade_components::TradeOffer;
+use crate::trade_ai::trade_ai_resources::TradeAiSettings;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::Entity;
+
+/// Number of cards each side names in the offers the AI makes, two of them guaranteed.
+pub const CARDS_PER_OFFER: usize = 3;
+
+/// What one more `card` is worth to a hand already holding `held` of them. A set of n
+/// commodities is worth n² times the card value, tradeable calamities count against us.
+pub fn card_worth(card: TradeCard, held: usize, settings: &TradeAiSettings) -> f32 {
+    if card.is_commodity() {
+        ((2 * held + 1) * card.value()) as f32
+    } else if card.is_tradeable() {
+        -settings.calamity_penalty * card.value() as f32
+    } else {
+        0.0
+    }
+}
+
+pub fn hand_value(hand: &HashMap<TradeCard, usize>, settings: &TradeAiSettings) -> f32 {
+    hand.iter()
+        .map(|(card, count)| {
+            (0..*count)
+                .map(|held| card_worth(*card, held, settings))
+                .sum::<f32>()
+        })
+        .sum()
+}
+
+/// Expected worth of a card the other side does not guarantee. Calamities hide among these, so
+/// the chance of getting one is taken from the tradeable cards in the game.
+pub fn hidden_card_value(settings: &TradeAiSettings) -> f32 {
+    let mut commodities = 0;
+    let mut calamities = 0;
+    let mut calamity_value = 0;
+    for card in TradeCard::iter().filter(|card| card.is_tradeable()) {
+        if card.is_commodity() {
+            commodities += card.number_of_cards();
+        } else {
+            calamities += card.number_of_cards();
+            calamity_value += card.number_of_cards() * card.value();
+        }
+    }
+    if commodities + calamities == 0 {
+        return 0.0;
+    }
+    let calamity_chance = calamities as f32 / (commodities + calamities) as f32;
+    let average_calamity = calamity_value as f32 / calamities.max(1) as f32;
+    (1.0 - calamity_chance) * settings.hidden_commodity_value
+        - calamity_chance * settings.calamity_penalty * average_calamity
+}
+
+fn take_card(hand: &mut HashMap<TradeCard, usize>, card: TradeCard) -> bool {
+    match hand.get_mut(&card) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            if *count == 0 {
+                hand.remove(&card);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The cards we hand over for a trade of `total` cards. The guaranteed cards are paid as
+/// promised, the rest are the cards we are happiest to be rid of, calamities first. None if
+/// the hand cannot cover it.
+pub fn cards_to_pay(
+    hand: &HashMap<TradeCard, usize>,
+    guaranteed: &HashMap<TradeCard, usize>,
+    total: usize,
+    settings: &TradeAiSettings,
+) -> Option<HashMap<TradeCard, usize>> {
+    let mut remaining = hand.clone();
+    let mut payment: HashMap<TradeCard, usize> = HashMap::default();
+    for (card, count) in guaranteed.iter() {
+        for _ in 0..*count {
+            if !take_card(&mut remaining, *card) {
+                return None;
+            }
+        }
+        *payment.entry(*card).or_default() += count;
+    }
+    let mut left = total.saturating_sub(guaranteed.values().sum());
+    while left > 0 {
+        let (card, _) = remaining
+            .iter()
+            .filter(|(card, _)| card.is_tradeable())
+            .map(|(card, count)| (*card, card_worth(*card, count - 1, settings)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        take_card(&mut remaining, card);
+        *payment.entry(card).or_default() += 1;
+        left -= 1;
+    }
+    Some(payment)
+}
+
+/// How much `me` gains from an offer, counting hidden cards at their expected worth. None if we
+/// cannot pay our side of it.
+pub fn offer_value(
+    offer: &TradeOffer,
+    me: Entity,
+    hand: &HashMap<TradeCard, usize>,
+    settings: &TradeAiSettings,
+) -> Option<f32> {
+    let (receive_guaranteed, receive_total, pay_guaranteed, pay_total) =
+        if offer.am_i_the_initiator(me) {
+            (
+                &offer.initiator_gets_guaranteed,
+                offer.gets_number_of_cards(),
+                &offer.initiator_pays_guaranteed,
+                offer.pays_number_of_cards(),
+            )
+        } else {
+            (
+                &offer.initiator_pays_guaranteed,
+                offer.pays_number_of_cards(),
+                &offer.initiator_gets_guaranteed,
+                offer.gets_number_of_cards(),
+            )
+        };
+    let payment = cards_to_pay(hand, pay_guaranteed, pay_total, settings)?;
+    let mut after = hand.clone();
+    for (card, count) in payment.iter() {
+        for _ in 0..*count {
+            take_card(&mut after, *card);
+        }
+    }
+    for (card, count) in receive_guaranteed.iter() {
+        *after.entry(*card).or_default() += count;
+    }
+    let hidden = receive_total.saturating_sub(receive_guaranteed.values().sum());
+    Some(
+        hand_value(&after, settings) - hand_value(hand, settings)
+            + hidden as f32 * hidden_card_value(settings),
+    )
+}
+
+/// The cards to offer and ask for when trading with a player that wants `receiver_wants`, with
+/// the guaranteed cards first. We give away what they want and costs us the least, and ask for
+/// more of the commodity that grows our best set.
+pub fn build_proposal(
+    hand: &HashMap<TradeCard, usize>,
+    receiver_wants: &[TradeCard],
+    settings: &TradeAiSettings,
+) -> Option<(Vec<TradeCard>, Vec<TradeCard>)> {
+    let mut remaining = hand.clone();
+    let mut guaranteed: HashMap<TradeCard, usize> = HashMap::default();
+    for _ in 0..2 {
+        let (card, _) = remaining
+            .iter()
+            .filter(|(card, _)| card.is_commodity())
+            .map(|(card, count)| {
+                let wanted = receiver_wants.contains(card);
+                (*card, (!wanted, card_worth(*card, count - 1, settings)))
+            })
+            .min_by(|(_, (a_unwanted, a)), (_, (b_unwanted, b))| {
+                a_unwanted.cmp(b_unwanted).then(a.total_cmp(b))
+            })?;
+        take_card(&mut remaining, card);
+        *guaranteed.entry(card).or_default() += 1;
+    }
+    let payment = cards_to_pay(hand, &guaranteed, CARDS_PER_OFFER, settings)?;
+
+    let mut pays = guaranteed
+        .iter()
+        .flat_map(|(card, count)| std::iter::repeat_n(*card, *count))
+        .collect::<Vec<_>>();
+    for (card, count) in payment.iter() {
+        let extra = count - guaranteed.get(card).copied().unwrap_or(0);
+        pays.extend(std::iter::repeat_n(*card, extra));
+    }
+
+    let wanted = remaining
+        .iter()
+        .filter(|(card, _)| {
+            card.is_commodity() && !receiver_wants.contains(card) && !payment.contains_key(*card)
+        })
+        .map(|(card, count)| (*card, card_worth(*card, *count, settings)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(card, _)| card)?;
+    Some((pays, vec![wanted; CARDS_PER_OFFER]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ENTITY_COUNTER: RefCell<u32> = const { RefCell::new(0) };
+    }
+
+    fn create_entity() -> Entity {
+        ENTITY_COUNTER.with(|counter| {
+            let index = *counter.borrow();
+            *counter.borrow_mut() += 1;
+            Entity::from_raw_u32(index).unwrap()
+        })
+    }
+
+    fn hand(cards: &[(TradeCard, usize)]) -> HashMap<TradeCard, usize> {
+        cards.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_hand_value_matches_commodity_suites() {
+        let settings = TradeAiSettings::default();
+        let cards = hand(&[(TradeCard::Grain, 3), (TradeCard::Ochre, 1)]);
+
+        assert_eq!(hand_value(&cards, &settings), 9.0 * 4.0 + 1.0);
+    }
+
+    #[test]
+    fn test_calamities_are_paid_before_commodities() {
+        let settings = TradeAiSettings::default();
+        let cards = hand(&[
+            (TradeCard::Grain, 3),
+            (TradeCard::Ochre, 2),
+            (TradeCard::Epidemic, 1),
+        ]);
+        let guaranteed = hand(&[(TradeCard::Ochre, 2)]);
+
+        let payment = cards_to_pay(&cards, &guaranteed, 3, &settings).unwrap();
+
+        assert_eq!(
+            payment,
+            hand(&[(TradeCard::Ochre, 2), (TradeCard::Epidemic, 1)])
+        );
+    }
+
+    #[test]
+    fn test_cannot_pay_missing_guaranteed_cards() {
+        let settings = TradeAiSettings::default();
+        let cards = hand(&[(TradeCard::Grain, 3)]);
+        let guaranteed = hand(&[(TradeCard::Ochre, 2)]);
+
+        assert_eq!(cards_to_pay(&cards, &guaranteed, 3, &settings), None);
+    }
+
+    #[test]
+    fn test_offer_completing_a_set_is_good_for_the_receiver() {
+        let settings = TradeAiSettings::default();
+        let initiator = create_entity();
+        let receiver = create_entity();
+        let mut offer = TradeOffer::propose_trade(initiator, "initiator", receiver, "receiver");
+        offer.pay_even_more(TradeCard::Grain, 3);
+        offer.get_even_more(TradeCard::Gold, 3);
+
+        let grain_collector = hand(&[
+            (TradeCard::Grain, 3),
+            (TradeCard::Gold, 2),
+            (TradeCard::Ochre, 1),
+        ]);
+        let gold_collector = hand(&[(TradeCard::Gold, 5)]);
+
+        assert!(offer_value(&offer, receiver, &grain_collector, &settings).unwrap() > 0.0);
+        assert!(offer_value(&offer, receiver, &gold_collector, &settings).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn test_hidden_cards_are_worth_less_than_guaranteed_ones() {
+        let settings = TradeAiSettings::default();
+
+        assert!(hidden_card_value(&settings) < settings.hidden_commodity_value);
+    }
+
+    #[test]
+    fn test_proposal_offers_what_the_receiver_wants() {
+        let settings = TradeAiSettings::default();
+        let cards = hand(&[
+            (TradeCard::Gold, 3),
+            (TradeCard::Salt, 2),
+            (TradeCard::Hides, 1),
+            (TradeCard::Treachery, 1),
+        ]);
+
+        let (pays, gets) = build_proposal(&cards, &[TradeCard::Salt], &settings).unwrap();
+
+        assert_eq!(&pays[..2], &[TradeCard::Salt, TradeCard::Salt]);
+        assert_eq!(pays[2], TradeCard::Treachery);
+        assert_eq!(gets, vec![TradeCard::Gold; CARDS_PER_OFFER]);
+    }
+}
@@ -0,0 +1,20 @@
+use crate::trade_ai::prelude::*;
+use crate::GameActivity;
+use bevy::app::{Plugin, Update};
+use bevy::prelude::{in_state, App, IntoScheduleConfigs, OnEnter};
+
+pub struct TradeAiPlugin;
+
+impl Plugin for TradeAiPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TradeAiMemory>()
+            .register_type::<TradeAiSettings>()
+            .init_resource::<TradeAiSettings>()
+            .add_message::<SelectTradeMove>()
+            .add_systems(OnEnter(GameActivity::Trade), forget_trade_memory)
+            .add_systems(
+                Update,
+                select_trade_move.run_if(in_state(GameActivity::Trade)),
+            );
+    }
+}
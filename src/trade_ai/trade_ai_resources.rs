@@ -0,0 +1,78 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use bevy::prelude::{Reflect, Resource};
+
+/// How the trade AI weighs cards, in points of commodity set value.
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct TradeAiSettings {
+    /// Cost of holding a tradeable calamity, per point of calamity value
+    pub calamity_penalty: f32,
+    /// Worth of a commodity card we know nothing about
+    pub hidden_commodity_value: f32,
+    /// Smallest gain that makes a trade worth doing
+    pub min_gain: f32,
+}
+
+impl TradeAiSettings {
+    /// The settings as seen by a player with the given personality.
+    pub fn with_personality(&self, personality: Option<&AiPersonality>) -> Self {
+        let Some(personality) = personality else {
+            return self.clone();
+        };
+        TradeAiSettings {
+            calamity_penalty: self.calamity_penalty / personality.risk_tolerance.max(0.1),
+            hidden_commodity_value: self.hidden_commodity_value,
+            min_gain: self.min_gain / personality.trade_frequency.max(0.1),
+        }
+    }
+}
+
+impl Default for TradeAiSettings {
+    fn default() -> Self {
+        TradeAiSettings {
+            calamity_penalty: 1.0,
+            hidden_commodity_value: 3.0,
+            min_gain: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequent_traders_take_smaller_gains() {
+        let settings = TradeAiSettings::default();
+
+        let adjusted =
+            settings.with_personality(Some(&AiPersonality::new("test", 1.0, 1.0, 2.0, 1.0)));
+
+        assert_eq!(adjusted.min_gain, settings.min_gain / 2.0);
+        assert_eq!(adjusted.calamity_penalty, settings.calamity_penalty);
+    }
+
+    #[test]
+    fn test_risk_takers_mind_calamities_less() {
+        let settings = TradeAiSettings::default();
+
+        let adjusted =
+            settings.with_personality(Some(&AiPersonality::new("test", 1.0, 1.0, 1.0, 4.0)));
+
+        assert_eq!(adjusted.calamity_penalty, settings.calamity_penalty / 4.0);
+        assert_eq!(
+            adjusted.hidden_commodity_value,
+            settings.hidden_commodity_value
+        );
+    }
+
+    #[test]
+    fn test_zero_weights_do_not_divide_by_zero() {
+        let settings = TradeAiSettings::default();
+
+        let adjusted =
+            settings.with_personality(Some(&AiPersonality::new("test", 1.0, 1.0, 0.0, 0.0)));
+
+        assert_eq!(adjusted.min_gain, settings.min_gain / 0.1);
+        assert_eq!(adjusted.calamity_penalty, settings.calamity_penalty / 0.1);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::civilization::concepts::ai_personality::ai_personality_components::AiPersonality;
+use crate::civilization::concepts::acquire_trade_cards::trade_card_components::PlayerTradeCards;
+use crate::civilization::concepts::trade::trade_components::{
+    CanTrade, PlayerTradeInterests, PublishedOffer, TradeOffer,
+};
+use crate::civilization::game_moves::game_moves_components::{AvailableMoves, Move, TradeMove};
+use crate::civilization::plugins::civilization_plugin::DebugOptions;
+use crate::trade_ai::trade_ai_components::TradeAiMemory;
+use crate::trade_ai::trade_ai_events::SelectTradeMove;
+use crate::trade_ai::trade_ai_functions::{build_proposal, cards_to_pay, offer_value};
+use crate::trade_ai::trade_ai_resources::TradeAiSettings;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::{debug, Commands, Entity, MessageReader, Name, Query, Res, With};
+
+pub fn forget_trade_memory(players: Query<Entity, With<TradeAiMemory>>, mut commands: Commands) {
+    for player in players.iter() {
+        commands.entity(player).remove::<TradeAiMemory>();
+    }
+}
+
+/// Settles accepted trades with the promised cards, answers the offers made to us, makes one
+/// offer to every player we have not traded with yet and leaves the table once there is nothing
+/// left to do.
+pub fn select_trade_move(
+    mut event_reader: MessageReader<SelectTradeMove>,
+    players: Query<(
+        &Name,
+        &AvailableMoves,
+        &PlayerTradeCards,
+        Option<&TradeAiMemory>,
+        Option<&AiPersonality>,
+    )>,
+    trading_partners: Query<(&Name, &PlayerTradeInterests), With<CanTrade>>,
+    mut trade_offers: Query<(Entity, &mut TradeOffer)>,
+    mut commands: Commands,
+    settings: Res<TradeAiSettings>,
+    debug_options: Res<DebugOptions>,
+) {
+    for event in event_reader.read() {
+        let player = event.player;
+        let Ok((player_name, available_moves, trade_cards, memory, personality)) =
+            players.get(player)
+        else {
+            continue;
+        };
+        let settings = settings.with_personality(personality);
+        let hand = trade_cards.cards();
+        let mut memory = memory.cloned().unwrap_or_default();
+        let mut keep_trading = false;
+        let mut can_stop = false;
+
+        for m in available_moves.moves.values() {
+            let Move::Trade(trade_move) = m else {
+                continue;
+            };
+            match trade_move {
+                TradeMove::SettleTrade(trade) => {
+                    keep_trading = true;
+                    if let Ok((_, mut offer)) = trade_offers.get_mut(*trade) {
+                        let (guaranteed, total) = if offer.am_i_the_initiator(player) {
+                            (
+                                offer.initiator_pays_guaranteed.clone(),
+                                offer.pays_number_of_cards(),
+                            )
+                        } else {
+                            (
+                                offer.initiator_gets_guaranteed.clone(),
+                                offer.gets_number_of_cards(),
+                            )
+                        };
+                        if let Some(cards) = cards_to_pay(hand, &guaranteed, total, &settings) {
+                            offer.settle(player, cards);
+                        } else {
+                            debug!("{} cannot pay for the trade any more", player_name);
+                            offer.reject(player);
+                            offer.withdraw(player);
+                        }
+                    }
+                }
+                TradeMove::AcceptOrDeclineTrade(trade) => {
+                    if let Ok((_, mut offer)) = trade_offers.get_mut(*trade) {
+                        if !offer.am_i_the_receiver(player) {
+                            continue;
+                        }
+                        let value = offer_value(&offer, player, hand, &settings);
+                        if debug_options.print_selected_moves {
+                            debug!(
+                                "{} values the offer from {} at {:?}",
+                                player_name, offer.initiator_name, value
+                            );
+                        }
+                        if value.is_some_and(|value| value >= settings.min_gain) {
+                            offer.accept(&player);
+                            keep_trading = true;
+                        } else {
+                            offer.reject(player);
+                        }
+                    }
+                }
+                TradeMove::AutoDeclineTrade(trade) => {
+                    if let Ok((_, mut offer)) = trade_offers.get_mut(*trade) {
+                        offer.reject(player);
+                    }
+                }
+                TradeMove::ProposeTrade(receiver, _) => {
+                    if !memory.proposed_to.insert(*receiver) {
+                        continue;
+                    }
+                    let Ok((receiver_name, interests)) = trading_partners.get(*receiver) else {
+                        continue;
+                    };
+                    let Some((pays, gets)) = build_proposal(hand, &interests.wants, &settings)
+                    else {
+                        continue;
+                    };
+                    let mut offer =
+                        TradeOffer::propose_trade(player, player_name, *receiver, receiver_name);
+                    for card in pays {
+                        offer.initiator_pays_more(card);
+                    }
+                    for card in gets {
+                        offer.initiator_gets_more(card);
+                    }
+                    let value = offer_value(&offer, player, hand, &settings);
+                    if value.is_some_and(|value| value >= settings.min_gain) {
+                        if debug_options.print_selected_moves {
+                            debug!(
+                                "{} offers {:?} to {} for {:?}",
+                                player_name,
+                                offer.initiator_pays_guaranteed,
+                                receiver_name,
+                                offer.initiator_gets_guaranteed
+                            );
+                        }
+                        commands.spawn((offer, PublishedOffer));
+                        keep_trading = true;
+                    }
+                }
+                TradeMove::StopTrading => {
+                    can_stop = true;
+                }
+            }
+        }
+
+        // Offers nobody answered since our last move are taken back
+        let mut unanswered_offers = HashSet::default();
+        for (offer_entity, mut offer) in trade_offers.iter_mut() {
+            if !offer.am_i_the_initiator(player) || offer.trade_accepted() || offer.trade_rejected()
+            {
+                continue;
+            }
+            if memory.unanswered_offers.contains(&offer_entity) {
+                offer.withdraw(player);
+            } else {
+                unanswered_offers.insert(offer_entity);
+                keep_trading = true;
+            }
+        }
+        memory.unanswered_offers = unanswered_offers;
+
+        if can_stop && !keep_trading {
+            if debug_options.print_selected_moves {
+                debug!("{} stops trading", player_name);
+            }
+            commands.entity(player).remove::<CanTrade>();
+        }
+        commands.entity(player).insert(memory);
+    }
+}